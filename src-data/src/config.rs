@@ -0,0 +1,58 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 导入配置文件
+//!
+//! 命令行参数优先于配置文件中的同名选项。
+//!
+//! ```toml
+//! install_path = "/opt/ffxiv"
+//! language = "ChineseSimplified"
+//! languages = ["Japanese", "English", "ChineseSimplified"]
+//! outputs = ["sqlite://xiv.db?mode=rwc"]
+//! sheets = ["Items", "Recipes", "RecipeLevelTables"]
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// 游戏安装路径
+    pub install_path: Option<String>,
+    /// 补丁文件所在的目录或补丁列表，设置时不读取游戏安装
+    pub patches: Option<PathBuf>,
+    /// 主表中名称使用的语言
+    pub language: Option<String>,
+    /// 导入翻译的语言，为空时导入客户端包含的所有语言
+    pub languages: Vec<String>,
+    /// 数据库URL，每个数据库都会导入相同的数据
+    pub outputs: Vec<String>,
+    /// 只导入这些表，为空时导入所有表
+    pub sheets: Vec<String>,
+    pub batch_size: Option<usize>,
+    pub update: bool,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read config {}: {err}", path.display()))?;
+        toml::from_str(&content).map_err(|err| format!("invalid config {}: {err}", path.display()))
+    }
+}
@@ -0,0 +1,373 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 比较两个版本的制作数据，生成更新日志
+//!
+//! 比较的对象可以是已导入的数据库，也可以是游戏安装目录或补丁文件；
+//! 后者先导入到内存数据库中，只导入配方及其依赖的表。
+
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use app_db::{collectables_shop_refine, items, prelude::*, recipe_level_tables, recipes};
+use clap::ValueEnum;
+use ironworks::excel::Language;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, JoinType, QueryFilter, QuerySelect,
+    RelationTrait,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{Failure, GameData, connect, import, language_value_parser, progress::Progress};
+
+/// 比较游戏数据时需要导入的表
+/// 比较时导入的表，需要包含配方通过外键引用的所有表
+const DIFF_SHEETS: [&str; 12] = [
+    "ItemUICategories",
+    "ItemSearchCategories",
+    "CraftTypes",
+    "ItemAction",
+    "Items",
+    "RecipeLevelTables",
+    "CollectablesShopRefine",
+    "NotebookDivisionCategories",
+    "NotebookDivisions",
+    "RecipeNotebookLists",
+    "SecretRecipeBooks",
+    "Recipes",
+];
+
+#[derive(clap::Args)]
+pub struct DiffArgs {
+    #[arg(
+        help = "Old data: a database URL (containing \"://\"), an installation path, a directory of .patch files or a patch list file"
+    )]
+    old: String,
+
+    #[arg(help = "New data, in the same forms as the old one")]
+    new: String,
+
+    #[arg(
+        short,
+        long = "lang",
+        value_parser = language_value_parser,
+        help = "Language of item names, required when reading game data"
+    )]
+    lang: Option<Language>,
+
+    #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+    format: DiffFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum DiffFormat {
+    Text,
+    Json,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Changelog {
+    pub recipes: TableChanges<recipes::Model>,
+    pub recipe_level_tables: TableChanges<recipe_level_tables::Model>,
+    pub collectables_shop_refine: TableChanges<collectables_shop_refine::Model>,
+    /// 新增、删除或变化的配方的成品名称，优先使用新版本中的名称
+    pub recipe_names: BTreeMap<u32, String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TableChanges<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub changed: Vec<RowChange>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RowChange {
+    pub id: u32,
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+pub async fn run(args: DiffArgs, progress: Progress) -> Result<(), Failure> {
+    let old = open(&args.old, args.lang, progress).await?;
+    let new = open(&args.new, args.lang, progress).await?;
+    let changelog = diff(&old, &new).await?;
+    match args.format {
+        DiffFormat::Text => print!("{changelog}"),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&changelog)?),
+    }
+    Ok(())
+}
+
+/// 连接数据库，或将游戏数据导入内存数据库
+async fn open(
+    source: &str,
+    language: Option<Language>,
+    progress: Progress,
+) -> Result<DatabaseConnection, Failure> {
+    if source.contains("://") {
+        return Ok(connect(source.to_string()).await?);
+    }
+    let Some(language) = language else {
+        return Err(Failure::Usage(
+            "--lang is required when reading game data".into(),
+        ));
+    };
+    let path = Path::new(source);
+    let game = if is_patches(path) {
+        GameData::patches(path, progress)?
+    } else {
+        GameData::install(path)
+    };
+    progress.message(format_args!("Reading {source}"));
+    let db = connect("sqlite::memory:".to_string()).await?;
+    let options = crate::writer::Options {
+        batch_size: 500,
+        update: false,
+        sheets: Some(DIFF_SHEETS.iter().map(|s| s.to_string()).collect()),
+    };
+    import(&db, &game, language, &[language], &options, progress).await?;
+    Ok(db)
+}
+
+/// 补丁列表文件或包含`.patch`文件的目录
+fn is_patches(path: &Path) -> bool {
+    if path.is_file() {
+        return true;
+    }
+    std::fs::read_dir(path).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|e| e.path().extension().is_some_and(|ext| ext == "patch"))
+    })
+}
+
+pub async fn diff(old: &DatabaseConnection, new: &DatabaseConnection) -> Result<Changelog, DbErr> {
+    let recipes = diff_table::<Recipes>(old, new, |r| r.id).await?;
+    let recipe_level_tables = diff_table::<RecipeLevelTables>(old, new, |r| r.id).await?;
+    let collectables_shop_refine = diff_table::<CollectablesShopRefine>(old, new, |r| r.id).await?;
+
+    let recipe_ids: Vec<u32> = recipes
+        .added
+        .iter()
+        .chain(&recipes.removed)
+        .map(|r| r.id)
+        .chain(recipes.changed.iter().map(|c| c.id))
+        .collect();
+    let mut recipe_names = BTreeMap::new();
+    for db in [old, new] {
+        let names: Vec<(u32, String)> = Recipes::find()
+            .join(JoinType::InnerJoin, recipes::Relation::ItemResultItem.def())
+            .select_only()
+            .column(recipes::Column::Id)
+            .column(items::Column::Name)
+            .filter(recipes::Column::Id.is_in(recipe_ids.iter().copied()))
+            .into_tuple()
+            .all(db)
+            .await?;
+        recipe_names.extend(names);
+    }
+    Ok(Changelog {
+        recipes,
+        recipe_level_tables,
+        collectables_shop_refine,
+        recipe_names,
+    })
+}
+
+/// 按id比较两个数据库中的同一张表，变化的行列出每个变化的字段
+async fn diff_table<E>(
+    old: &DatabaseConnection,
+    new: &DatabaseConnection,
+    id: impl Fn(&E::Model) -> u32,
+) -> Result<TableChanges<E::Model>, DbErr>
+where
+    E: EntityTrait,
+    E::Model: Serialize + PartialEq,
+{
+    let mut old_rows: BTreeMap<u32, E::Model> = E::find()
+        .all(old)
+        .await?
+        .into_iter()
+        .map(|r| (id(&r), r))
+        .collect();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    let mut new_rows = E::find().all(new).await?;
+    new_rows.sort_by_key(&id);
+    for row in new_rows {
+        match old_rows.remove(&id(&row)) {
+            None => added.push(row),
+            Some(old) if old != row => changed.push(RowChange {
+                id: id(&row),
+                fields: field_changes(&old, &row),
+            }),
+            Some(_) => {}
+        }
+    }
+    Ok(TableChanges {
+        added,
+        removed: old_rows.into_values().collect(),
+        changed,
+    })
+}
+
+fn field_changes(old: &impl Serialize, new: &impl Serialize) -> Vec<FieldChange> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+    new.into_iter()
+        .filter_map(|(field, new)| {
+            let old = old.get(&field).cloned().unwrap_or(Value::Null);
+            (old != new).then_some(FieldChange { field, old, new })
+        })
+        .collect()
+}
+
+impl Display for Changelog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let recipe_name = |id: u32| match self.recipe_names.get(&id) {
+            Some(name) => format!("{id} {name}"),
+            None => id.to_string(),
+        };
+        write_table(
+            f,
+            "Recipes",
+            &self.recipes,
+            |r| {
+                format!(
+                    "{} (craft type {}, level table {})",
+                    recipe_name(r.id),
+                    r.craft_type_id,
+                    r.recipe_level_id
+                )
+            },
+            recipe_name,
+        )?;
+        write_table(
+            f,
+            "RecipeLevelTables",
+            &self.recipe_level_tables,
+            |r| {
+                format!(
+                    "{}: level {}, difficulty {}, quality {}, durability {}",
+                    r.id, r.class_job_level, r.difficulty, r.quality, r.durability
+                )
+            },
+            |id| id.to_string(),
+        )?;
+        write_table(
+            f,
+            "CollectablesShopRefine",
+            &self.collectables_shop_refine,
+            |r| {
+                format!(
+                    "{}: {}/{}/{}",
+                    r.id, r.low_collectability, r.mid_collectability, r.high_collectability
+                )
+            },
+            |id| id.to_string(),
+        )
+    }
+}
+
+fn write_table<T>(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    changes: &TableChanges<T>,
+    describe: impl Fn(&T) -> String,
+    label: impl Fn(u32) -> String,
+) -> std::fmt::Result {
+    writeln!(
+        f,
+        "{name}: {} added, {} removed, {} changed",
+        changes.added.len(),
+        changes.removed.len(),
+        changes.changed.len()
+    )?;
+    for row in &changes.added {
+        writeln!(f, "  + {}", describe(row))?;
+    }
+    for row in &changes.removed {
+        writeln!(f, "  - {}", describe(row))?;
+    }
+    for change in &changes.changed {
+        let fields: Vec<String> = change
+            .fields
+            .iter()
+            .map(|c| format!("{} {} -> {}", c.field, c.old, c.new))
+            .collect();
+        writeln!(f, "  ~ {}: {}", label(change.id), fields.join(", "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DatabaseBackend, PaginatorTrait, sea_query::TableRef};
+
+    use super::*;
+    use crate::{progress::ProgressFormat, table_statements};
+
+    #[test]
+    fn diff_sheets_include_referenced_tables() {
+        for stat in table_statements(DatabaseBackend::Sqlite) {
+            let Some(TableRef::Table(table)) = stat.get_table_name() else {
+                continue;
+            };
+            let table = table.to_string();
+            if !DIFF_SHEETS.contains(&table.as_str()) {
+                continue;
+            }
+            for fk in stat.get_foreign_key_create_stmts() {
+                let Some(TableRef::Table(referenced)) = fk.get_foreign_key().get_ref_table() else {
+                    continue;
+                };
+                let referenced = referenced.to_string();
+                assert!(
+                    DIFF_SHEETS.contains(&referenced.as_str()),
+                    "{table} references {referenced}, which is not in DIFF_SHEETS"
+                );
+            }
+        }
+    }
+
+    /// 用`DIFF_SHEETS`从游戏数据导入，游戏安装目录或补丁目录由`BESTCRAFT_GAME_PATH`指定
+    #[tokio::test]
+    #[ignore = "requires game data in BESTCRAFT_GAME_PATH"]
+    async fn import_diff_sheets() {
+        let path = std::env::var("BESTCRAFT_GAME_PATH").unwrap();
+        let progress = Progress::new(ProgressFormat::Text).to_stderr();
+        let db = match open(&path, Some(Language::English), progress).await {
+            Ok(db) => db,
+            Err(Failure::Usage(msg)) => panic!("{msg}"),
+            Err(Failure::Import(err)) => panic!("{err}"),
+        };
+        assert!(Recipes::find().count(&db).await.unwrap() > 0);
+        assert!(RecipeNotebookLists::find().count(&db).await.unwrap() > 0);
+        let changelog = diff(&db, &db).await.unwrap();
+        assert!(changelog.recipes.added.is_empty());
+        assert!(changelog.recipes.changed.is_empty());
+    }
+}
@@ -15,7 +15,8 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use ironworks::excel::SheetMetadata;
-use sea_orm::ActiveValue;
+
+use crate::schema::{Columns, Kind, Required, Resolver, SchemaError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -35,18 +36,361 @@ impl From<ironworks::excel::Field> for Error {
     }
 }
 
-pub struct ItemUICategory;
-pub struct ItemSearchCategory;
-pub struct CraftType;
-pub struct ItemAction;
-pub struct Item;
-pub struct RecipeLevelTable;
-pub struct ItemFood;
-pub struct CollectablesShopRefine;
-pub struct Recipe;
-pub struct WKSMissionRecipe;
-pub struct WKSMissionToDo;
-pub struct WKSMissionUnit;
+#[derive(Clone)]
+pub struct ItemUICategory(Columns);
+#[derive(Clone)]
+pub struct ItemSearchCategory(Columns);
+#[derive(Clone)]
+pub struct CraftType(Columns);
+#[derive(Clone)]
+pub struct ItemAction(Columns);
+#[derive(Clone)]
+pub struct Item(Columns);
+#[derive(Clone)]
+pub struct RecipeLevelTable(Columns);
+#[derive(Clone)]
+pub struct ItemFood(Columns);
+#[derive(Clone)]
+pub struct CollectablesShopRefine(Columns);
+#[derive(Clone)]
+pub struct Recipe(Columns);
+#[derive(Clone)]
+pub struct WKSMissionRecipe(Columns);
+#[derive(Clone)]
+pub struct WKSMissionToDo(Columns);
+#[derive(Clone)]
+pub struct WKSMissionUnit(Columns);
+#[derive(Clone)]
+pub struct GilShopItem(Columns);
+#[derive(Clone)]
+pub struct ItemPrice(Columns);
+#[derive(Clone)]
+pub struct SpecialShop(Columns);
+#[derive(Clone)]
+pub struct GatheringItem(Columns);
+#[derive(Clone)]
+pub struct GatheringPointBase(Columns);
+#[derive(Clone)]
+pub struct GatheringPoint(Columns);
+#[derive(Clone)]
+pub struct ClassJobCategory(Columns);
+#[derive(Clone)]
+pub struct Gear(Columns);
+#[derive(Clone)]
+pub struct ItemLevel(Columns);
+#[derive(Clone)]
+pub struct BaseParam(Columns);
+#[derive(Clone)]
+pub struct Materia(Columns);
+#[derive(Clone)]
+pub struct NotebookDivisionCategory(Columns);
+#[derive(Clone)]
+pub struct NotebookDivision(Columns);
+#[derive(Clone)]
+pub struct RecipeNotebookList(Columns);
+#[derive(Clone)]
+pub struct SecretRecipeBook(Columns);
+
+/// 导入时读取的所有表格，导入前统一解析与校验列
+pub struct Sheets {
+    pub item_ui_category: ItemUICategory,
+    pub item_search_category: ItemSearchCategory,
+    pub craft_type: CraftType,
+    pub item_action: ItemAction,
+    pub item: Item,
+    pub recipe_level_table: RecipeLevelTable,
+    pub item_food: ItemFood,
+    pub collectables_shop_refine: CollectablesShopRefine,
+    pub recipe: Recipe,
+    pub wks_mission_recipe: WKSMissionRecipe,
+    pub wks_mission_to_do: WKSMissionToDo,
+    pub wks_mission_unit: WKSMissionUnit,
+    pub gil_shop_item: GilShopItem,
+    pub item_price: ItemPrice,
+    pub special_shop: SpecialShop,
+    pub gathering_item: GatheringItem,
+    pub gathering_point_base: GatheringPointBase,
+    pub gathering_point: GatheringPoint,
+    pub class_job_category: ClassJobCategory,
+    pub gear: Gear,
+    pub item_level: ItemLevel,
+    pub base_param: BaseParam,
+    pub materia: Materia,
+    pub notebook_division_category: NotebookDivisionCategory,
+    pub notebook_division: NotebookDivision,
+    pub recipe_notebook_list: RecipeNotebookList,
+    pub secret_recipe_book: SecretRecipeBook,
+    pub item_names: Names,
+    pub craft_type_names: Names,
+    pub wks_mission_unit_names: Names,
+    pub item_ui_category_names: Names,
+    pub item_search_category_names: Names,
+    pub gil_shop_names: Names,
+    pub special_shop_names: Names,
+    pub place_names: Names,
+    pub notebook_division_category_names: Names,
+    pub notebook_division_names: Names,
+    pub secret_recipe_book_names: Names,
+}
+
+impl Sheets {
+    pub fn resolve(mut resolver: Resolver<'_>) -> Result<Self, SchemaError> {
+        use Kind::*;
+        let r = &mut resolver;
+        let name = Required::default().column("Name", String);
+        let sheets = Self {
+            item_ui_category: ItemUICategory(r.resolve("ItemUICategory", name.clone())),
+            item_search_category: ItemSearchCategory(r.resolve("ItemSearchCategory", name.clone())),
+            craft_type: CraftType(r.resolve("CraftType", name.clone())),
+            item_action: ItemAction(
+                r.resolve(
+                    "ItemAction",
+                    Required::default()
+                        .column("Type", U16)
+                        .array("Data", 9, U16)
+                        .array("DataHQ", 9, U16),
+                ),
+            ),
+            item: Item(
+                r.resolve(
+                    "Item",
+                    Required::default()
+                        .column("Name", String)
+                        .column("LevelItem", U16)
+                        .column("CanBeHq", Bool)
+                        .column("ItemUICategory", U8)
+                        .column("ItemSearchCategory", U8)
+                        .column("ItemAction", U16)
+                        .column("IsCollectable", Bool)
+                        .column("AlwaysCollectable", Bool),
+                ),
+            ),
+            recipe_level_table: RecipeLevelTable(
+                r.resolve(
+                    "RecipeLevelTable",
+                    Required::default()
+                        .column("ClassJobLevel", U8)
+                        .column("SuggestedCraftsmanship", U16)
+                        .column("Difficulty", U16)
+                        .column("Quality", U32)
+                        .column("ProgressDivider", U8)
+                        .column("QualityDivider", U8)
+                        .column("ProgressModifier", U8)
+                        .column("QualityModifier", U8)
+                        .column("Durability", U16)
+                        .column("ConditionsFlag", U16),
+                ),
+            ),
+            item_food: ItemFood(r.resolve("ItemFood", {
+                let mut required = Required::default();
+                for i in 0..3 {
+                    required = required
+                        .column(&format!("Params[{i}].BaseParam"), U8)
+                        .column(&format!("Params[{i}].Value"), I8)
+                        .column(&format!("Params[{i}].Max"), I16)
+                        .column(&format!("Params[{i}].ValueHQ"), I8)
+                        .column(&format!("Params[{i}].MaxHQ"), I16);
+                }
+                required
+            })),
+            collectables_shop_refine: CollectablesShopRefine(
+                r.resolve(
+                    "CollectablesShopRefine",
+                    Required::default()
+                        .column("LowCollectability", U16)
+                        .column("MidCollectability", U16)
+                        .column("HighCollectability", U16),
+                ),
+            ),
+            recipe: Recipe(
+                r.resolve(
+                    "Recipe",
+                    Required::default()
+                        .column("Number", I32)
+                        .column("CraftType", I32)
+                        .column("RecipeLevelTable", U16)
+                        .column("ItemResult", I32)
+                        .column("AmountResult", U8)
+                        .array("Ingredient", 8, I32)
+                        .array("AmountIngredient", 8, U8)
+                        .column("RecipeNotebookList", U16)
+                        .column("MaterialQualityFactor", U8)
+                        .column("DifficultyFactor", U16)
+                        .column("QualityFactor", U16)
+                        .column("DurabilityFactor", U16)
+                        .column("RequiredQuality", U32)
+                        .column("RequiredCraftsmanship", U16)
+                        .column("RequiredControl", U16)
+                        .column("CanHq", Bool)
+                        .column("IsExpert", Bool)
+                        .column("CollectablesMetadataKey", U8)
+                        .column("CollectablesMetadata", U16)
+                        .column("SecretRecipeBook", U16),
+                ),
+            ),
+            wks_mission_recipe: WKSMissionRecipe(
+                r.resolve(
+                    "WKSMissionRecipe",
+                    Required::default()
+                        .array("Recipe", 5, U32)
+                        .column("IsExpert", Bool),
+                ),
+            ),
+            wks_mission_to_do: WKSMissionToDo(
+                r.resolve(
+                    "WKSMissionToDo",
+                    Required::default()
+                        .column("TemporaryAction", U32)
+                        .column("TemporaryActionCount", U8),
+                ),
+            ),
+            wks_mission_unit: WKSMissionUnit(
+                r.resolve(
+                    "WKSMissionUnit",
+                    Required::default()
+                        .column("Name", String)
+                        .column("WKSMissionRecipe", U16)
+                        .array("WKSMissionToDo", 3, U16),
+                ),
+            ),
+            gil_shop_item: GilShopItem(
+                r.resolve("GilShopItem", Required::default().column("Item", I32)),
+            ),
+            item_price: ItemPrice(r.resolve("Item", Required::default().column("PriceMid", U32))),
+            special_shop: SpecialShop(r.resolve("SpecialShop", {
+                let mut required = Required::default().column("Name", String);
+                for i in 0..2 {
+                    required = required
+                        .array(&format!("ReceiveItem[{i}]"), SPECIAL_SHOP_ENTRIES, I32)
+                        .array(&format!("ReceiveCount[{i}]"), SPECIAL_SHOP_ENTRIES, I32);
+                }
+                for i in 0..3 {
+                    required = required
+                        .array(&format!("CostItem[{i}]"), SPECIAL_SHOP_ENTRIES, I32)
+                        .array(&format!("CostCount[{i}]"), SPECIAL_SHOP_ENTRIES, I32);
+                }
+                required
+            })),
+            gathering_item: GatheringItem(
+                r.resolve(
+                    "GatheringItem",
+                    Required::default()
+                        .column("Item", I32)
+                        .column("GatheringItemLevel", U16),
+                ),
+            ),
+            gathering_point_base: GatheringPointBase(
+                r.resolve(
+                    "GatheringPointBase",
+                    Required::default()
+                        .column("GatheringType", I32)
+                        .column("GatheringLevel", U8)
+                        .array("Item", 8, I32)
+                        .column("IsLimited", Bool),
+                ),
+            ),
+            gathering_point: GatheringPoint(
+                r.resolve(
+                    "GatheringPoint",
+                    Required::default()
+                        .column("GatheringPointBase", I32)
+                        .column("TerritoryType", U16)
+                        .column("PlaceName", U16),
+                ),
+            ),
+            class_job_category: ClassJobCategory(
+                r.resolve(
+                    "ClassJobCategory",
+                    Required::default()
+                        .column("Name", String)
+                        .column("CRP", Bool)
+                        .column("BSM", Bool)
+                        .column("ARM", Bool)
+                        .column("GSM", Bool)
+                        .column("LTW", Bool)
+                        .column("WVR", Bool)
+                        .column("ALC", Bool)
+                        .column("CUL", Bool),
+                ),
+            ),
+            gear: Gear(
+                r.resolve(
+                    "Item",
+                    Required::default()
+                        .column("LevelItem", U16)
+                        .column("LevelEquip", U8)
+                        .column("EquipSlotCategory", U8)
+                        .column("ClassJobCategory", U8)
+                        .column("MateriaSlotCount", U8)
+                        .column("IsAdvancedMeldingPermitted", Bool)
+                        .array("BaseParam", GEAR_PARAMS, U8)
+                        .array("BaseParamValue", GEAR_PARAMS, I16)
+                        .array("BaseParamSpecial", GEAR_PARAMS, U8)
+                        .array("BaseParamValueSpecial", GEAR_PARAMS, I16),
+                ),
+            ),
+            item_level: ItemLevel(
+                r.resolve(
+                    "ItemLevel",
+                    Required::default()
+                        .column("Craftsmanship", U16)
+                        .column("Control", U16)
+                        .column("CP", U16),
+                ),
+            ),
+            base_param: BaseParam(r.resolve(
+                "BaseParam",
+                Required::default().array("EquipSlotCategoryPct", EQUIP_SLOT_CATEGORIES, U16),
+            )),
+            materia: Materia(
+                r.resolve(
+                    "Materia",
+                    Required::default()
+                        .array("Item", MATERIA_GRADES, I32)
+                        .column("BaseParam", U8)
+                        .array("Value", MATERIA_GRADES, I16),
+                ),
+            ),
+            notebook_division_category: NotebookDivisionCategory(
+                r.resolve("NotebookDivisionCategory", name.clone()),
+            ),
+            notebook_division: NotebookDivision(
+                r.resolve(
+                    "NotebookDivision",
+                    Required::default()
+                        .column("Name", String)
+                        .column("NotebookDivisionCategory", U8)
+                        .column("CraftOpeningLevel", U8),
+                ),
+            ),
+            // 只使用行id，见`notebook_division`
+            recipe_notebook_list: RecipeNotebookList(
+                r.resolve("RecipeNotebookList", Required::default()),
+            ),
+            secret_recipe_book: SecretRecipeBook(
+                r.resolve(
+                    "SecretRecipeBook",
+                    Required::default()
+                        .column("Item", I32)
+                        .column("Name", String),
+                ),
+            ),
+            item_names: Names::resolve(r, "Item"),
+            craft_type_names: Names::resolve(r, "CraftType"),
+            wks_mission_unit_names: Names::resolve(r, "WKSMissionUnit"),
+            item_ui_category_names: Names::resolve(r, "ItemUICategory"),
+            item_search_category_names: Names::resolve(r, "ItemSearchCategory"),
+            gil_shop_names: Names::resolve(r, "GilShop"),
+            special_shop_names: Names::resolve(r, "SpecialShop"),
+            place_names: Names::resolve(r, "PlaceName"),
+            notebook_division_category_names: Names::resolve(r, "NotebookDivisionCategory"),
+            notebook_division_names: Names::resolve(r, "NotebookDivision"),
+            secret_recipe_book_names: Names::resolve(r, "SecretRecipeBook"),
+        };
+        resolver.finish()?;
+        Ok(sheets)
+    }
+}
 
 impl SheetMetadata for ItemUICategory {
     fn name(&self) -> String {
@@ -58,7 +402,7 @@ impl SheetMetadata for ItemUICategory {
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
         Ok(Self::Row {
             id: row.row_id(),
-            name: row.field(0)?.into_string()?.format()?,
+            name: row.field(self.0.get("Name"))?.into_string()?.format()?,
         })
     }
 }
@@ -73,7 +417,7 @@ impl SheetMetadata for ItemSearchCategory {
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
         Ok(Self::Row {
             id: row.row_id(),
-            name: row.field(0)?.into_string()?.format()?,
+            name: row.field(self.0.get("Name"))?.into_string()?.format()?,
         })
     }
 }
@@ -88,7 +432,7 @@ impl SheetMetadata for CraftType {
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
         Ok(Self::Row {
             id: row.row_id(),
-            name: row.field(2)?.into_string()?.format()?,
+            name: row.field(self.0.get("Name"))?.into_string()?.format()?,
         })
     }
 }
@@ -101,27 +445,31 @@ impl SheetMetadata for ItemAction {
     type Row = app_db::item_action::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let data = |i: usize| -> Result<u16, Error> { Ok(row.field(c.at("Data", i))?.into_u16()?) };
+        let data_hq =
+            |i: usize| -> Result<u16, Error> { Ok(row.field(c.at("DataHQ", i))?.into_u16()?) };
         Ok(Self::Row {
             id: row.row_id(),
-            r#type: row.field(4 /* Action */)?.into_u16()?,
-            data1: row.field(5)?.into_u16()?,
-            data2: row.field(6)?.into_u16()?,
-            data3: row.field(7)?.into_u16()?,
-            data4: row.field(8)?.into_u16()?,
-            data5: row.field(9)?.into_u16()?,
-            data6: row.field(10)?.into_u16()?,
-            data7: row.field(11)?.into_u16()?,
-            data8: row.field(12)?.into_u16()?,
-            data9: row.field(13)?.into_u16()?,
-            data_hq1: row.field(14)?.into_u16()?,
-            data_hq2: row.field(15)?.into_u16()?,
-            data_hq3: row.field(16)?.into_u16()?,
-            data_hq4: row.field(17)?.into_u16()?,
-            data_hq5: row.field(18)?.into_u16()?,
-            data_hq6: row.field(19)?.into_u16()?,
-            data_hq7: row.field(20)?.into_u16()?,
-            data_hq8: row.field(21)?.into_u16()?,
-            data_hq9: row.field(22)?.into_u16()?,
+            r#type: row.field(c.get("Type"))?.into_u16()?,
+            data1: data(0)?,
+            data2: data(1)?,
+            data3: data(2)?,
+            data4: data(3)?,
+            data5: data(4)?,
+            data6: data(5)?,
+            data7: data(6)?,
+            data8: data(7)?,
+            data9: data(8)?,
+            data_hq1: data_hq(0)?,
+            data_hq2: data_hq(1)?,
+            data_hq3: data_hq(2)?,
+            data_hq4: data_hq(3)?,
+            data_hq5: data_hq(4)?,
+            data_hq6: data_hq(5)?,
+            data_hq7: data_hq(6)?,
+            data_hq8: data_hq(7)?,
+            data_hq9: data_hq(8)?,
         })
     }
 }
@@ -134,16 +482,20 @@ impl SheetMetadata for Item {
     type Row = app_db::items::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
         Ok(Self::Row {
             id: row.row_id(),
-            name: row.field(9)?.into_string()?.format()?,
-            level: row.field(11)?.into_u16()? as u32,
-            can_be_hq: row.field(27)?.into_bool()?,
-            item_ui_category_id: Some(row.field(15)?.into_u8()? as u32).filter(|x| *x != 0),
-            item_search_category_id: Some(row.field(16)?.into_u8()? as u32).filter(|x| *x != 0),
-            item_action_id: Some(row.field(30)?.into_u16()? as u32).filter(|x| *x != 0),
-            is_collectable: row.field(37)?.into_bool()?,
-            always_collectable: row.field(38)?.into_bool()?,
+            name: row.field(c.get("Name"))?.into_string()?.format()?,
+            level: row.field(c.get("LevelItem"))?.into_u16()? as u32,
+            can_be_hq: row.field(c.get("CanBeHq"))?.into_bool()?,
+            item_ui_category_id: Some(row.field(c.get("ItemUICategory"))?.into_u8()? as u32)
+                .filter(|x| *x != 0),
+            item_search_category_id:
+                Some(row.field(c.get("ItemSearchCategory"))?.into_u8()? as u32).filter(|x| *x != 0),
+            item_action_id: Some(row.field(c.get("ItemAction"))?.into_u16()? as u32)
+                .filter(|x| *x != 0),
+            is_collectable: row.field(c.get("IsCollectable"))?.into_bool()?,
+            always_collectable: row.field(c.get("AlwaysCollectable"))?.into_bool()?,
         })
     }
 }
@@ -156,22 +508,22 @@ impl SheetMetadata for RecipeLevelTable {
     type Row = app_db::recipe_level_tables::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
         Ok(Self::Row {
             id: row.row_id(),
-            class_job_level: row.field(0)?.into_u8()?,
-            suggested_craftsmanship: row.field(2)?.into_u16()?,
-            difficulty: row.field(3)?.into_u16()?,
-            quality: row.field(4)?.into_u32()?,
-            progress_divider: row.field(5)?.into_u8()?,
-            quality_divider: row.field(6)?.into_u8()?,
-            progress_modifier: row.field(7)?.into_u8()?,
-            quality_modifier: row.field(8)?.into_u8()?,
-            durability: row.field(9)?.into_u16()?,
-            conditions_flag: row.field(10)?.into_u16()?,
+            class_job_level: row.field(c.get("ClassJobLevel"))?.into_u8()?,
+            suggested_craftsmanship: row.field(c.get("SuggestedCraftsmanship"))?.into_u16()?,
+            difficulty: row.field(c.get("Difficulty"))?.into_u16()?,
+            quality: row.field(c.get("Quality"))?.into_u32()?,
+            progress_divider: row.field(c.get("ProgressDivider"))?.into_u8()?,
+            quality_divider: row.field(c.get("QualityDivider"))?.into_u8()?,
+            progress_modifier: row.field(c.get("ProgressModifier"))?.into_u8()?,
+            quality_modifier: row.field(c.get("QualityModifier"))?.into_u8()?,
+            durability: row.field(c.get("Durability"))?.into_u16()?,
+            conditions_flag: row.field(c.get("ConditionsFlag"))?.into_u16()?,
         })
     }
 }
-
 pub struct ItemFoodRow {
     pub id: u32,
     pub effects: [ItemFoodEffect; 3],
@@ -186,15 +538,19 @@ pub struct ItemFoodEffect {
 }
 
 impl ItemFoodEffect {
-    pub fn to_model(&self, item_food_id: u32) -> app_db::item_food_effect::ActiveModel {
-        app_db::item_food_effect::ActiveModel {
-            id: ActiveValue::NotSet,
-            base_param: ActiveValue::Set(self.base_param),
-            value: ActiveValue::Set(self.value),
-            max: ActiveValue::Set(self.max),
-            value_hq: ActiveValue::Set(self.value_hq),
-            max_hq: ActiveValue::Set(self.max_hq),
-            item_food_id: ActiveValue::Set(item_food_id),
+    /// `slot`为效果在`ItemFood`中的位置（0到2）。
+    ///
+    /// id由食物id与位置计算得出，使重复导入时同一效果的id保持不变；
+    /// 旧版本导入的数据库使用自增id，首次更新时所有效果都会被重新写入。
+    pub fn to_model(&self, item_food_id: u32, slot: u32) -> app_db::item_food_effect::Model {
+        app_db::item_food_effect::Model {
+            id: item_food_id * 3 + slot,
+            base_param: self.base_param,
+            value: self.value,
+            max: self.max,
+            value_hq: self.value_hq,
+            max_hq: self.max_hq,
+            item_food_id,
         }
     }
 }
@@ -207,31 +563,20 @@ impl SheetMetadata for ItemFood {
     type Row = ItemFoodRow;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let effect = |i: usize| -> Result<ItemFoodEffect, Error> {
+            let field = |name: &str| row.field(c.get(&format!("Params[{i}].{name}")));
+            Ok(ItemFoodEffect {
+                base_param: field("BaseParam")?.into_u8()?,
+                value: field("Value")?.into_i8()?,
+                max: field("Max")?.into_i16()?,
+                value_hq: field("ValueHQ")?.into_i8()?,
+                max_hq: field("MaxHQ")?.into_i16()?,
+            })
+        };
         Ok(Self::Row {
             id: row.row_id(),
-            effects: [
-                ItemFoodEffect {
-                    base_param: row.field(1)?.into_u8()?,
-                    value: row.field(3)?.into_i8()?,
-                    max: row.field(4)?.into_i16()?,
-                    value_hq: row.field(5)?.into_i8()?,
-                    max_hq: row.field(6)?.into_i16()?,
-                },
-                ItemFoodEffect {
-                    base_param: row.field(7)?.into_u8()?,
-                    value: row.field(9)?.into_i8()?,
-                    max: row.field(10)?.into_i16()?,
-                    value_hq: row.field(11)?.into_i8()?,
-                    max_hq: row.field(12)?.into_i16()?,
-                },
-                ItemFoodEffect {
-                    base_param: row.field(13)?.into_u8()?,
-                    value: row.field(15)?.into_i8()?,
-                    max: row.field(16)?.into_i16()?,
-                    value_hq: row.field(17)?.into_i8()?,
-                    max_hq: row.field(18)?.into_i16()?,
-                },
-            ],
+            effects: [effect(0)?, effect(1)?, effect(2)?],
         })
     }
 }
@@ -244,11 +589,12 @@ impl SheetMetadata for CollectablesShopRefine {
     type Row = app_db::collectables_shop_refine::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
         Ok(Self::Row {
             id: row.row_id(),
-            low_collectability: row.field(0)?.into_u16()?,
-            mid_collectability: row.field(1)?.into_u16()?,
-            high_collectability: row.field(2)?.into_u16()?,
+            low_collectability: row.field(c.get("LowCollectability"))?.into_u16()?,
+            mid_collectability: row.field(c.get("MidCollectability"))?.into_u16()?,
+            high_collectability: row.field(c.get("HighCollectability"))?.into_u16()?,
         })
     }
 }
@@ -275,39 +621,46 @@ impl SheetMetadata for Recipe {
     type Row = app_db::recipes::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
-        let collectables_metadata_key = row.field(44)?.into_u8()? as u32;
+        let c = &self.0;
+        let collectables_metadata_key =
+            row.field(c.get("CollectablesMetadataKey"))?.into_u8()? as u32;
         let collectables_metadata = match collectables_metadata_key {
-            1 => Some(row.field(45)?.into_u16()? as u32),
+            1 => Some(row.field(c.get("CollectablesMetadata"))?.into_u16()? as u32),
             _ => None,
         };
-        let (item_result_id, item_result_amount) = read_ingredient(&row, 4, 5)?;
-        let (ingredient0, ingredient_amount0) = read_ingredient(&row, 6, 7)?;
-        let (ingredient1, ingredient_amount1) = read_ingredient(&row, 8, 9)?;
-        let (ingredient2, ingredient_amount2) = read_ingredient(&row, 10, 11)?;
-        let (ingredient3, ingredient_amount3) = read_ingredient(&row, 12, 13)?;
-        let (ingredient4, ingredient_amount4) = read_ingredient(&row, 14, 15)?;
-        let (ingredient5, ingredient_amount5) = read_ingredient(&row, 16, 17)?;
-        let (ingredient6, ingredient_amount6) = read_ingredient(&row, 18, 19)?;
-        let (ingredient7, ingredient_amount7) = read_ingredient(&row, 20, 21)?;
+        let ingredient =
+            |i: usize| read_ingredient(&row, c.at("Ingredient", i), c.at("AmountIngredient", i));
+        let (item_result_id, item_result_amount) =
+            read_ingredient(&row, c.get("ItemResult"), c.get("AmountResult"))?;
+        let (ingredient0, ingredient_amount0) = ingredient(0)?;
+        let (ingredient1, ingredient_amount1) = ingredient(1)?;
+        let (ingredient2, ingredient_amount2) = ingredient(2)?;
+        let (ingredient3, ingredient_amount3) = ingredient(3)?;
+        let (ingredient4, ingredient_amount4) = ingredient(4)?;
+        let (ingredient5, ingredient_amount5) = ingredient(5)?;
+        let (ingredient6, ingredient_amount6) = ingredient(6)?;
+        let (ingredient7, ingredient_amount7) = ingredient(7)?;
         Ok(app_db::recipes::Model {
             id: row.row_id(),
-            number: row.field(0)?.into_i32()?,
-            craft_type_id: row.field(1)?.into_i32()? as u32,
-            recipe_level_id: row.field(2)?.into_u16()? as u32,
+            number: row.field(c.get("Number"))?.into_i32()?,
+            craft_type_id: row.field(c.get("CraftType"))?.into_i32()? as u32,
+            recipe_level_id: row.field(c.get("RecipeLevelTable"))?.into_u16()? as u32,
             item_result_id,
             item_result_amount,
-            material_quality_factor: row.field(25)?.into_u8()?,
-            difficulty_factor: row.field(26)?.into_u16()?,
-            quality_factor: row.field(27)?.into_u16()?,
-            durability_factor: row.field(28)?.into_u16()?,
-            required_quality: row.field(29)?.into_u32()?,
-            required_craftsmanship: row.field(30)?.into_u16()?,
-            required_control: row.field(31)?.into_u16()?,
-            can_hq: row.field(37)?.into_bool()?,
-            is_expert: row.field(43)?.into_bool()?,
+            material_quality_factor: row.field(c.get("MaterialQualityFactor"))?.into_u8()?,
+            difficulty_factor: row.field(c.get("DifficultyFactor"))?.into_u16()?,
+            quality_factor: row.field(c.get("QualityFactor"))?.into_u16()?,
+            durability_factor: row.field(c.get("DurabilityFactor"))?.into_u16()?,
+            required_quality: row.field(c.get("RequiredQuality"))?.into_u32()?,
+            required_craftsmanship: row.field(c.get("RequiredCraftsmanship"))?.into_u16()?,
+            required_control: row.field(c.get("RequiredControl"))?.into_u16()?,
+            can_hq: row.field(c.get("CanHq"))?.into_bool()?,
+            is_expert: row.field(c.get("IsExpert"))?.into_bool()?,
             collectables_metadata_key,
             collectables_metadata,
-            recipe_notebook_list: row.field(22)?.into_u16()? as u32,
+            recipe_notebook_list: row.field(c.get("RecipeNotebookList"))?.into_u16()? as u32,
+            secret_recipe_book_id: Some(row.field(c.get("SecretRecipeBook"))?.into_u16()? as u32)
+                .filter(|x| *x != 0),
             ingredient0,
             ingredient_amount0,
             ingredient1,
@@ -336,14 +689,18 @@ impl SheetMetadata for WKSMissionRecipe {
     type Row = app_db::wks_mission_recipe::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let recipe = |i: usize| -> Result<Option<u32>, Error> {
+            Ok(Some(row.field(c.at("Recipe", i))?.into_u32()?).filter(|x| *x != 0))
+        };
         Ok(Self::Row {
             id: row.row_id(),
-            recipe0_id: Some(row.field(0)?.into_u32()?).filter(|x| *x != 0),
-            recipe1_id: Some(row.field(1)?.into_u32()?).filter(|x| *x != 0),
-            recipe2_id: Some(row.field(2)?.into_u32()?).filter(|x| *x != 0),
-            recipe3_id: Some(row.field(3)?.into_u32()?).filter(|x| *x != 0),
-            recipe4_id: Some(row.field(4)?.into_u32()?).filter(|x| *x != 0),
-            is_expert: row.field(5)?.into_bool()?,
+            recipe0_id: recipe(0)?,
+            recipe1_id: recipe(1)?,
+            recipe2_id: recipe(2)?,
+            recipe3_id: recipe(3)?,
+            recipe4_id: recipe(4)?,
+            is_expert: row.field(c.get("IsExpert"))?.into_bool()?,
         })
     }
 }
@@ -356,10 +713,11 @@ impl SheetMetadata for WKSMissionToDo {
     type Row = app_db::wks_mission_to_do::Model;
     type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
         Ok(Self::Row {
             id: row.row_id(),
-            temporary_action: row.field(0)?.into_u32()?,
-            temporary_action_count: row.field(1)?.into_u8()? as u16,
+            temporary_action: row.field(c.get("TemporaryAction"))?.into_u32()?,
+            temporary_action_count: row.field(c.get("TemporaryActionCount"))?.into_u8()? as u16,
         })
     }
 }
@@ -371,14 +729,491 @@ impl SheetMetadata for WKSMissionUnit {
 
     type Row = app_db::wks_mission_unit::Model;
     type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let to_do = |i: usize| -> Result<Option<u32>, Error> {
+            Ok(Some(row.field(c.at("WKSMissionToDo", i))?.into_u16()? as u32).filter(|x| *x != 0))
+        };
+        Ok(Self::Row {
+            id: row.row_id(),
+            name: row.field(c.get("Name"))?.into_string()?.format()?,
+            recipe_id: Some(row.field(c.get("WKSMissionRecipe"))?.into_u16()? as u32)
+                .filter(|x| *x != 0),
+            to_do0_id: to_do(0)?,
+            to_do1_id: to_do(1)?,
+            to_do2_id: to_do(2)?,
+        })
+    }
+}
+
+pub struct GilShopItemRow {
+    pub shop_id: u32,
+    pub subrow_id: u16,
+    pub item_id: u32,
+}
+
+impl SheetMetadata for GilShopItem {
+    fn name(&self) -> String {
+        String::from("GilShopItem")
+    }
+
+    type Row = GilShopItemRow;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        Ok(Self::Row {
+            shop_id: row.row_id(),
+            subrow_id: row.subrow_id(),
+            item_id: row.field(self.0.get("Item"))?.into_i32()? as u32,
+        })
+    }
+}
+
+pub struct ItemPriceRow {
+    pub id: u32,
+    pub price_mid: u32,
+}
+
+impl SheetMetadata for ItemPrice {
+    fn name(&self) -> String {
+        String::from("Item")
+    }
+
+    type Row = ItemPriceRow;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        Ok(Self::Row {
+            id: row.row_id(),
+            price_mid: row.field(self.0.get("PriceMid"))?.into_u32()?,
+        })
+    }
+}
+
+/// `SpecialShop`中每个字段都是60列的数组，每列对应一个兑换项
+const SPECIAL_SHOP_ENTRIES: usize = 60;
+
+impl SheetMetadata for SpecialShop {
+    fn name(&self) -> String {
+        String::from("SpecialShop")
+    }
+
+    /// 每个兑换项的每个产出物品一行
+    type Row = Vec<app_db::special_shop_items::Model>;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let name = row.field(c.get("Name"))?.into_string()?.format()?;
+        let field = |column: &str, index: usize, entry: usize| -> Result<u32, Error> {
+            let column = c.get(&format!("{column}[{index}][{entry}]"));
+            Ok(row.field(column)?.into_i32()?.max(0) as u32)
+        };
+        let mut items = Vec::new();
+        for entry in 0..SPECIAL_SHOP_ENTRIES {
+            let mut costs = [(None, 0); 3];
+            for (i, cost) in costs.iter_mut().enumerate() {
+                let item_id = field("CostItem", i, entry)?;
+                let count = field("CostCount", i, entry)?;
+                *cost = (Some(item_id).filter(|x| *x != 0), count);
+            }
+            for slot in 0..2 {
+                let item_id = field("ReceiveItem", slot, entry)?;
+                if item_id == 0 {
+                    continue;
+                }
+                items.push(app_db::special_shop_items::Model {
+                    shop_id: row.row_id(),
+                    entry: entry as u8,
+                    slot: slot as u8,
+                    shop_name: name.clone(),
+                    item_id,
+                    count: field("ReceiveCount", slot, entry)?,
+                    cost_item0_id: costs[0].0,
+                    cost_count0: costs[0].1,
+                    cost_item1_id: costs[1].0,
+                    cost_count1: costs[1].1,
+                    cost_item2_id: costs[2].0,
+                    cost_count2: costs[2].1,
+                });
+            }
+        }
+        Ok(items)
+    }
+}
+
+impl SheetMetadata for GatheringItem {
+    fn name(&self) -> String {
+        String::from("GatheringItem")
+    }
+
+    type Row = app_db::gathering_items::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            item_id: row.field(c.get("Item"))?.into_i32()? as u32,
+            gathering_item_level: row.field(c.get("GatheringItemLevel"))?.into_u16()?,
+        })
+    }
+}
+
+impl SheetMetadata for GatheringPointBase {
+    fn name(&self) -> String {
+        String::from("GatheringPointBase")
+    }
+
+    type Row = app_db::gathering_point_bases::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let item = |i: usize| -> Result<Option<u32>, Error> {
+            Ok(Some(row.field(c.at("Item", i))?.into_i32()?.max(0) as u32).filter(|x| *x != 0))
+        };
+        Ok(Self::Row {
+            id: row.row_id(),
+            gathering_type: row.field(c.get("GatheringType"))?.into_i32()? as u8,
+            gathering_level: row.field(c.get("GatheringLevel"))?.into_u8()?,
+            is_limited: row.field(c.get("IsLimited"))?.into_bool()?,
+            item0_id: item(0)?,
+            item1_id: item(1)?,
+            item2_id: item(2)?,
+            item3_id: item(3)?,
+            item4_id: item(4)?,
+            item5_id: item(5)?,
+            item6_id: item(6)?,
+            item7_id: item(7)?,
+        })
+    }
+}
+
+pub struct GatheringPointRow {
+    pub id: u32,
+    pub gathering_point_base_id: u32,
+    pub territory_type_id: u32,
+    pub place_name_id: u32,
+}
+
+impl SheetMetadata for GatheringPoint {
+    fn name(&self) -> String {
+        String::from("GatheringPoint")
+    }
+
+    type Row = GatheringPointRow;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            gathering_point_base_id: row.field(c.get("GatheringPointBase"))?.into_i32()? as u32,
+            territory_type_id: row.field(c.get("TerritoryType"))?.into_u16()? as u32,
+            place_name_id: row.field(c.get("PlaceName"))?.into_u16()? as u32,
+        })
+    }
+}
+
+impl SheetMetadata for ClassJobCategory {
+    fn name(&self) -> String {
+        String::from("ClassJobCategory")
+    }
+
+    type Row = app_db::class_job_categories::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            name: row.field(c.get("Name"))?.into_string()?.format()?,
+            carpenter: row.field(c.get("CRP"))?.into_bool()?,
+            blacksmith: row.field(c.get("BSM"))?.into_bool()?,
+            armorer: row.field(c.get("ARM"))?.into_bool()?,
+            goldsmith: row.field(c.get("GSM"))?.into_bool()?,
+            leatherworker: row.field(c.get("LTW"))?.into_bool()?,
+            weaver: row.field(c.get("WVR"))?.into_bool()?,
+            alchemist: row.field(c.get("ALC"))?.into_bool()?,
+            culinarian: row.field(c.get("CUL"))?.into_bool()?,
+        })
+    }
+}
+
+/// `Item`中基础属性与优质品额外属性的数量
+const GEAR_PARAMS: usize = 6;
+
+pub struct GearRow {
+    pub id: u32,
+    pub level_item: u16,
+    pub level_equip: u8,
+    /// 为0时不是装备
+    pub equip_slot_category: u8,
+    pub class_job_category: u8,
+    pub materia_slot_count: u8,
+    pub is_advanced_melding_permitted: bool,
+    /// 基础属性，优质品在此基础上加上`params_special`
+    pub params: Vec<(u8, i16)>,
+    pub params_special: Vec<(u8, i16)>,
+}
+
+impl GearRow {
+    /// 合并基础属性与优质品的额外属性，只有优质品才有的属性基础值为0
+    pub fn to_models(&self) -> Vec<app_db::gear_params::Model> {
+        let mut models: Vec<app_db::gear_params::Model> = Vec::new();
+        for &(base_param, value) in &self.params {
+            models.push(app_db::gear_params::Model {
+                item_id: self.id,
+                base_param,
+                value,
+                value_hq: value,
+            });
+        }
+        for &(base_param, value) in &self.params_special {
+            match models.iter_mut().find(|m| m.base_param == base_param) {
+                Some(model) => model.value_hq += value,
+                None => models.push(app_db::gear_params::Model {
+                    item_id: self.id,
+                    base_param,
+                    value: 0,
+                    value_hq: value,
+                }),
+            }
+        }
+        models
+    }
+}
+
+impl SheetMetadata for Gear {
+    fn name(&self) -> String {
+        String::from("Item")
+    }
+
+    type Row = GearRow;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let params = |param: &str, value: &str| -> Result<Vec<(u8, i16)>, Error> {
+            let mut params = Vec::new();
+            for i in 0..GEAR_PARAMS {
+                let base_param = row.field(c.at(param, i))?.into_u8()?;
+                if base_param != 0 {
+                    params.push((base_param, row.field(c.at(value, i))?.into_i16()?));
+                }
+            }
+            Ok(params)
+        };
+        Ok(Self::Row {
+            id: row.row_id(),
+            level_item: row.field(c.get("LevelItem"))?.into_u16()?,
+            level_equip: row.field(c.get("LevelEquip"))?.into_u8()?,
+            equip_slot_category: row.field(c.get("EquipSlotCategory"))?.into_u8()?,
+            class_job_category: row.field(c.get("ClassJobCategory"))?.into_u8()?,
+            materia_slot_count: row.field(c.get("MateriaSlotCount"))?.into_u8()?,
+            is_advanced_melding_permitted: row
+                .field(c.get("IsAdvancedMeldingPermitted"))?
+                .into_bool()?,
+            params: params("BaseParam", "BaseParamValue")?,
+            params_special: params("BaseParamSpecial", "BaseParamValueSpecial")?,
+        })
+    }
+}
+
+/// 各物品等级的制作属性上限，与`BaseParam`中装备部位的比例相乘后为该部位装备的上限
+pub struct ItemLevelRow {
+    pub id: u32,
+    pub craftsmanship: u16,
+    pub control: u16,
+    pub craft_points: u16,
+}
+
+impl SheetMetadata for ItemLevel {
+    fn name(&self) -> String {
+        String::from("ItemLevel")
+    }
+
+    type Row = ItemLevelRow;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            craftsmanship: row.field(c.get("Craftsmanship"))?.into_u16()?,
+            control: row.field(c.get("Control"))?.into_u16()?,
+            craft_points: row.field(c.get("CP"))?.into_u16()?,
+        })
+    }
+}
+
+/// `EquipSlotCategory`的数量，`BaseParam`中每个装备部位一列
+const EQUIP_SLOT_CATEGORIES: usize = 22;
+
+pub struct BaseParamRow {
+    pub id: u32,
+    /// 按`EquipSlotCategory`索引的属性上限比例，单位为千分之一
+    pub equip_slot_category_pct: Vec<u16>,
+}
+
+impl SheetMetadata for BaseParam {
+    fn name(&self) -> String {
+        String::from("BaseParam")
+    }
+
+    type Row = BaseParamRow;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            equip_slot_category_pct: (0..EQUIP_SLOT_CATEGORIES)
+                .map(|i| Ok(row.field(c.at("EquipSlotCategoryPct", i))?.into_u16()?))
+                .collect::<Result<_, Error>>()?,
+        })
+    }
+}
+
+/// 每种魔晶石的等级数量
+const MATERIA_GRADES: usize = 10;
+
+impl SheetMetadata for Materia {
+    fn name(&self) -> String {
+        String::from("Materia")
+    }
+
+    /// 每个等级一行，没有对应物品的等级被跳过
+    type Row = Vec<app_db::materia::Model>;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        let base_param = row.field(c.get("BaseParam"))?.into_u8()?;
+        let mut materia = Vec::new();
+        for grade in 0..MATERIA_GRADES {
+            let item_id = row.field(c.at("Item", grade))?.into_i32()?;
+            if item_id <= 0 {
+                continue;
+            }
+            materia.push(app_db::materia::Model {
+                item_id: item_id as u32,
+                materia_id: row.row_id(),
+                grade: grade as u8,
+                base_param,
+                value: row.field(c.at("Value", grade))?.into_i16()?,
+            });
+        }
+        Ok(materia)
+    }
+}
+
+impl SheetMetadata for NotebookDivisionCategory {
+    fn name(&self) -> String {
+        String::from("NotebookDivisionCategory")
+    }
+
+    type Row = app_db::notebook_division_categories::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        Ok(Self::Row {
+            id: row.row_id(),
+            name: row.field(self.0.get("Name"))?.into_string()?.format()?,
+        })
+    }
+}
+
+impl SheetMetadata for NotebookDivision {
+    fn name(&self) -> String {
+        String::from("NotebookDivision")
+    }
+
+    type Row = app_db::notebook_divisions::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            name: row.field(c.get("Name"))?.into_string()?.format()?,
+            notebook_division_category_id: Some(
+                row.field(c.get("NotebookDivisionCategory"))?.into_u8()? as u32,
+            )
+            .filter(|x| *x != 0),
+            craft_opening_level: row.field(c.get("CraftOpeningLevel"))?.into_u8()?,
+        })
+    }
+}
+
+/// 普通配方在`RecipeNotebookList`中每个制作职业占40行，每页一行；
+/// 秘籍从第1000行开始，每页占8行，每个制作职业一行
+const NOTEBOOK_DIVISIONS: u32 = 40;
+const SECRET_NOTEBOOK_START: u32 = 1000;
+const CRAFT_TYPES: u32 = 8;
+
+/// `RecipeNotebookList`的行对应的`NotebookDivision`
+fn notebook_division(notebook_list: u32) -> u32 {
+    if notebook_list < SECRET_NOTEBOOK_START {
+        notebook_list % NOTEBOOK_DIVISIONS
+    } else {
+        SECRET_NOTEBOOK_START + (notebook_list - SECRET_NOTEBOOK_START) / CRAFT_TYPES
+    }
+}
+
+impl SheetMetadata for RecipeNotebookList {
+    fn name(&self) -> String {
+        String::from("RecipeNotebookList")
+    }
+
+    type Row = app_db::recipe_notebook_lists::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        Ok(Self::Row {
+            id: row.row_id(),
+            notebook_division_id: Some(notebook_division(row.row_id())),
+        })
+    }
+}
+
+impl SheetMetadata for SecretRecipeBook {
+    fn name(&self) -> String {
+        String::from("SecretRecipeBook")
+    }
+
+    type Row = app_db::secret_recipe_books::Model;
+    type Error = Error;
+    fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
+        let c = &self.0;
+        Ok(Self::Row {
+            id: row.row_id(),
+            item_id: row.field(c.get("Item"))?.into_i32()? as u32,
+            name: row.field(c.get("Name"))?.into_string()?.format()?,
+        })
+    }
+}
+
+/// 只读取名称列，用于导入其他语言的翻译
+#[derive(Clone)]
+pub struct Names {
+    sheet: &'static str,
+    columns: Columns,
+}
+
+impl Names {
+    fn resolve(resolver: &mut Resolver<'_>, sheet: &'static str) -> Self {
+        let columns = resolver.resolve(sheet, Required::default().column("Name", Kind::String));
+        Self { sheet, columns }
+    }
+}
+
+pub struct NameRow {
+    pub id: u32,
+    pub name: String,
+}
+
+impl SheetMetadata for Names {
+    fn name(&self) -> String {
+        String::from(self.sheet)
+    }
+
+    type Row = NameRow;
+    type Error = Error;
     fn populate_row(&self, row: ironworks::excel::Row) -> Result<Self::Row, Self::Error> {
         Ok(Self::Row {
             id: row.row_id(),
-            name: row.field(0)?.into_string()?.format()?,
-            recipe_id: Some(row.field(21)?.into_u16()? as u32).filter(|x| *x != 0),
-            to_do0_id: Some(row.field(13)?.into_u16()? as u32).filter(|x| *x != 0),
-            to_do1_id: Some(row.field(14)?.into_u16()? as u32).filter(|x| *x != 0),
-            to_do2_id: Some(row.field(15)?.into_u16()? as u32).filter(|x| *x != 0),
+            name: row
+                .field(self.columns.get("Name"))?
+                .into_string()?
+                .format()?,
         })
     }
 }
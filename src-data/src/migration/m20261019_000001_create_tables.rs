@@ -0,0 +1,125 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 初始的表结构
+//!
+//! 只包含本迁移加入时已有的表，之后新增的表由各自的迁移建立。
+//! 表结构直接由实体生成，因此之后给这些表新增列时，新的迁移需要先用`manager.has_column`
+//! 判断该列是否已经由本迁移建立。
+//!
+//! 支持迁移之前的`app-data`生成的数据库已经包含其中一部分表，因此只在不存在时创建，
+//! 这样旧的数据库也可以通过迁移升级。
+
+use sea_orm::{ConnectionTrait, DatabaseBackend, EntityName, Schema};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        if let DatabaseBackend::MySql = backend {
+            db.execute_unprepared("SET FOREIGN_KEY_CHECKS = 0;").await?;
+        }
+        let schema = Schema::new(backend);
+        for mut stat in [
+            schema.create_table_from_entity(app_db::item_ui_categories::Entity),
+            schema.create_table_from_entity(app_db::item_search_categories::Entity),
+            schema.create_table_from_entity(app_db::craft_types::Entity),
+            schema.create_table_from_entity(app_db::item_action::Entity),
+            schema.create_table_from_entity(app_db::items::Entity),
+            schema.create_table_from_entity(app_db::recipe_level_tables::Entity),
+            schema.create_table_from_entity(app_db::item_food::Entity),
+            schema.create_table_from_entity(app_db::item_food_effect::Entity),
+            schema.create_table_from_entity(app_db::collectables_shop_refine::Entity),
+            schema.create_table_from_entity(app_db::recipes::Entity),
+            schema.create_table_from_entity(app_db::wks_mission_recipe::Entity),
+            schema.create_table_from_entity(app_db::wks_mission_to_do::Entity),
+            schema.create_table_from_entity(app_db::wks_mission_unit::Entity),
+            schema.create_table_from_entity(app_db::recipe_search::Entity),
+            schema.create_table_from_entity(app_db::item_translations::Entity),
+            schema.create_table_from_entity(app_db::craft_type_translations::Entity),
+            schema.create_table_from_entity(app_db::wks_mission_unit_translations::Entity),
+            schema.create_table_from_entity(app_db::item_ui_category_translations::Entity),
+            schema.create_table_from_entity(app_db::item_search_category_translations::Entity),
+            schema.create_table_from_entity(app_db::gil_shop_items::Entity),
+            schema.create_table_from_entity(app_db::special_shop_items::Entity),
+            schema.create_table_from_entity(app_db::gathering_items::Entity),
+            schema.create_table_from_entity(app_db::gathering_point_bases::Entity),
+            schema.create_table_from_entity(app_db::gathering_points::Entity),
+            schema.create_table_from_entity(app_db::metadata::Entity),
+        ] {
+            manager
+                .create_table(stat.if_not_exists().to_owned())
+                .await?;
+        }
+        if let DatabaseBackend::MySql = backend {
+            db.execute_unprepared("SET FOREIGN_KEY_CHECKS = 1;").await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        if let DatabaseBackend::MySql = backend {
+            db.execute_unprepared("SET FOREIGN_KEY_CHECKS = 0;").await?;
+        }
+        for table in [
+            app_db::metadata::Entity.table_name(),
+            app_db::gathering_points::Entity.table_name(),
+            app_db::gathering_point_bases::Entity.table_name(),
+            app_db::gathering_items::Entity.table_name(),
+            app_db::special_shop_items::Entity.table_name(),
+            app_db::gil_shop_items::Entity.table_name(),
+            app_db::item_search_category_translations::Entity.table_name(),
+            app_db::item_ui_category_translations::Entity.table_name(),
+            app_db::wks_mission_unit_translations::Entity.table_name(),
+            app_db::craft_type_translations::Entity.table_name(),
+            app_db::item_translations::Entity.table_name(),
+            app_db::recipe_search::Entity.table_name(),
+            app_db::wks_mission_unit::Entity.table_name(),
+            app_db::wks_mission_to_do::Entity.table_name(),
+            app_db::wks_mission_recipe::Entity.table_name(),
+            app_db::recipes::Entity.table_name(),
+            app_db::collectables_shop_refine::Entity.table_name(),
+            app_db::item_food_effect::Entity.table_name(),
+            app_db::item_food::Entity.table_name(),
+            app_db::recipe_level_tables::Entity.table_name(),
+            app_db::items::Entity.table_name(),
+            app_db::item_action::Entity.table_name(),
+            app_db::craft_types::Entity.table_name(),
+            app_db::item_search_categories::Entity.table_name(),
+            app_db::item_ui_categories::Entity.table_name(),
+        ] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        if let DatabaseBackend::MySql = backend {
+            db.execute_unprepared("SET FOREIGN_KEY_CHECKS = 1;").await?;
+        }
+        Ok(())
+    }
+}
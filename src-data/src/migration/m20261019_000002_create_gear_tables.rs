@@ -0,0 +1,58 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 装备、魔晶石与职业类别
+
+use sea_orm::{EntityName, Schema};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(manager.get_database_backend());
+        for stat in [
+            schema.create_table_from_entity(app_db::class_job_categories::Entity),
+            schema.create_table_from_entity(app_db::gear::Entity),
+            schema.create_table_from_entity(app_db::gear_params::Entity),
+            schema.create_table_from_entity(app_db::materia::Entity),
+        ] {
+            manager.create_table(stat).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            app_db::materia::Entity.table_name(),
+            app_db::gear_params::Entity.table_name(),
+            app_db::gear::Entity.table_name(),
+            app_db::class_job_categories::Entity.table_name(),
+        ] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 制作笔记与秘籍
+//!
+//! 第一个迁移由实体生成配方表，新建的数据库已经包含秘籍列，因此只在不存在时添加该列。
+
+use sea_orm::{EntityName, IdenStatic, Schema};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(manager.get_database_backend());
+        for stat in [
+            schema.create_table_from_entity(app_db::notebook_division_categories::Entity),
+            schema.create_table_from_entity(app_db::notebook_divisions::Entity),
+            schema.create_table_from_entity(app_db::recipe_notebook_lists::Entity),
+            schema.create_table_from_entity(app_db::secret_recipe_books::Entity),
+            schema
+                .create_table_from_entity(app_db::notebook_division_category_translations::Entity),
+            schema.create_table_from_entity(app_db::notebook_division_translations::Entity),
+            schema.create_table_from_entity(app_db::secret_recipe_book_translations::Entity),
+        ] {
+            manager.create_table(stat).await?;
+        }
+
+        let recipes = app_db::recipes::Entity.table_name();
+        let column = app_db::recipes::Column::SecretRecipeBookId.as_str();
+        if !manager.has_column(recipes, column).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(recipes))
+                        .add_column(ColumnDef::new(Alias::new(column)).unsigned().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let recipes = app_db::recipes::Entity.table_name();
+        let column = app_db::recipes::Column::SecretRecipeBookId.as_str();
+        if manager.has_column(recipes, column).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(recipes))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        for table in [
+            app_db::secret_recipe_book_translations::Entity.table_name(),
+            app_db::notebook_division_translations::Entity.table_name(),
+            app_db::notebook_division_category_translations::Entity.table_name(),
+            app_db::secret_recipe_books::Entity.table_name(),
+            app_db::recipe_notebook_lists::Entity.table_name(),
+            app_db::notebook_divisions::Entity.table_name(),
+            app_db::notebook_division_categories::Entity.table_name(),
+        ] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
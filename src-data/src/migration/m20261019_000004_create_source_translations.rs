@@ -0,0 +1,89 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 商店与地点名称的翻译
+//!
+//! 第一个迁移由实体生成采集点表，新建的数据库已经包含地名的id列，因此只在不存在时添加该列。
+//! 已有的行需要重新导入才能查到翻译后的地名。
+
+use sea_orm::{EntityName, IdenStatic, Schema};
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let schema = Schema::new(manager.get_database_backend());
+        for stat in [
+            schema.create_table_from_entity(app_db::gil_shop_translations::Entity),
+            schema.create_table_from_entity(app_db::special_shop_translations::Entity),
+            schema.create_table_from_entity(app_db::place_name_translations::Entity),
+        ] {
+            manager.create_table(stat).await?;
+        }
+
+        let points = app_db::gathering_points::Entity.table_name();
+        let column = app_db::gathering_points::Column::PlaceNameId.as_str();
+        if !manager.has_column(points, column).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(points))
+                        .add_column(
+                            ColumnDef::new(Alias::new(column))
+                                .unsigned()
+                                .not_null()
+                                .default(0),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let points = app_db::gathering_points::Entity.table_name();
+        let column = app_db::gathering_points::Column::PlaceNameId.as_str();
+        if manager.has_column(points, column).await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(points))
+                        .drop_column(Alias::new(column))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        for table in [
+            app_db::place_name_translations::Entity.table_name(),
+            app_db::special_shop_translations::Entity.table_name(),
+            app_db::gil_shop_translations::Entity.table_name(),
+        ] {
+            manager
+                .drop_table(
+                    Table::drop()
+                        .table(Alias::new(table))
+                        .if_exists()
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
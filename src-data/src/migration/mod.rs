@@ -0,0 +1,40 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 数据库结构迁移
+//!
+//! 新增或修改表结构时添加一个迁移，并将[`app_db::version::SCHEMA_VERSION`]加一。
+
+use sea_orm_migration::prelude::*;
+
+mod m20261019_000001_create_tables;
+mod m20261019_000002_create_gear_tables;
+mod m20261019_000003_create_notebook_tables;
+mod m20261019_000004_create_source_translations;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261019_000001_create_tables::Migration),
+            Box::new(m20261019_000002_create_gear_tables::Migration),
+            Box::new(m20261019_000003_create_notebook_tables::Migration),
+            Box::new(m20261019_000004_create_source_translations::Migration),
+        ]
+    }
+}
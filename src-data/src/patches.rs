@@ -0,0 +1,94 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 从ZiPatch补丁文件读取游戏数据，无需安装游戏客户端
+//!
+//! 表格数据都位于`ffxiv`仓库中，因此只需要基础游戏的补丁，按顺序应用后即为对应版本的数据。
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use ironworks::zipatch::{Patch, PatchRepository, VersionSpecifier, View, ZiPatch};
+
+/// `ffxiv`仓库的编号
+const FFXIV_REPOSITORY: u8 = 0;
+
+/// 读取补丁文件。
+///
+/// `path`为目录时使用其中所有的`.patch`文件；否则视为补丁列表，
+/// 每行一个补丁文件路径，相对路径相对于列表所在的目录，空行与`#`开头的行被忽略。
+pub fn find_patches(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        from_directory(path)
+    } else {
+        from_list(path)
+    }
+}
+
+/// 补丁文件名为`H`或`D`加上版本号，去掉首字母后按版本号排序
+fn from_directory(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut patches = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "patch") {
+            patches.push(path);
+        }
+    }
+    patches.sort_by_cached_key(|p| patch_version(p));
+    Ok(patches)
+}
+
+fn from_list(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let base = path.parent().unwrap_or(Path::new("."));
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base.join(line))
+        .collect())
+}
+
+/// 补丁对应的游戏版本，例如`D2024.11.06.0000.0000.patch`对应`2024.11.06.0000.0000`
+pub fn patch_version(patch: &Path) -> String {
+    let name = patch
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+    let mut chars = name.chars();
+    match chars.next() {
+        Some('H' | 'D') => chars.as_str().to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// 按顺序应用所有补丁后的文件视图，可直接作为`SqPack`的资源
+pub fn patch_view(patches: &[PathBuf]) -> View {
+    let repository = PatchRepository {
+        patches: patches
+            .iter()
+            .map(|path| Patch {
+                name: patch_version(path),
+                path: path.clone(),
+            })
+            .collect(),
+    };
+    ZiPatch::new()
+        .with_repository(FFXIV_REPOSITORY, repository)
+        .version(VersionSpecifier::latest())
+        .build()
+}
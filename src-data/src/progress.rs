@@ -0,0 +1,125 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 导入进度的输出
+//!
+//! 文本格式供人阅读；JSON格式每行输出一个事件对象，`event`字段为事件类型，供自动化流程解析。
+//! 进度默认输出到标准输出，`diff`子命令的标准输出用于更新日志，进度改为输出到标准错误。
+
+use std::fmt::Display;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum ProgressFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    Message {
+        message: &'a str,
+    },
+    /// 跳过的行或语言等不影响导入结果的问题
+    Warning {
+        message: &'a str,
+    },
+    /// 向表中插入了一批行，`offset`为该批第一行的序号
+    Batch {
+        table: &'a str,
+        offset: usize,
+        rows: usize,
+    },
+    /// 更新模式下与数据库中已有行比较的结果
+    Diff {
+        table: &'a str,
+        added: usize,
+        changed: usize,
+        removed: usize,
+    },
+    Done,
+    Error {
+        message: &'a str,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    format: ProgressFormat,
+    stderr: bool,
+}
+
+impl Progress {
+    pub fn new(format: ProgressFormat) -> Self {
+        Self {
+            format,
+            stderr: false,
+        }
+    }
+
+    /// 所有事件都输出到标准错误，标准输出留给命令的结果
+    pub fn to_stderr(self) -> Self {
+        Self {
+            stderr: true,
+            ..self
+        }
+    }
+
+    pub fn emit(&self, event: Event) {
+        let line = match self.format {
+            ProgressFormat::Json => serde_json::to_string(&event).unwrap(),
+            ProgressFormat::Text => match event {
+                Event::Message { message } => message.to_string(),
+                Event::Warning { message } => message.to_string(),
+                Event::Batch { table, offset, .. } => format!("Pushing {table} batch {offset}"),
+                Event::Diff {
+                    table,
+                    added,
+                    changed,
+                    removed,
+                } => {
+                    format!("Updating {table}: {added} added, {changed} changed, {removed} removed")
+                }
+                Event::Done => "Success!".to_string(),
+                Event::Error { message } => {
+                    eprintln!("Error: {message}");
+                    return;
+                }
+            },
+        };
+        if self.stderr {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    pub fn message(&self, message: impl Display) {
+        self.emit(Event::Message {
+            message: &message.to_string(),
+        });
+    }
+
+    pub fn warning(&self, message: impl Display) {
+        self.emit(Event::Warning {
+            message: &message.to_string(),
+        });
+    }
+}
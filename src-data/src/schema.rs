@@ -0,0 +1,248 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 通过EXDSchema按名称查找表格的列
+//!
+//! 游戏更新时列的位置可能改变，直接使用列号读取会在不知情的情况下读到错误的数据。
+//! 导入前先根据EXDSchema解析所有需要的列，并与表头中的类型比对，任何问题都会使导入失败。
+
+use std::collections::HashMap;
+
+use ironworks::{
+    excel::Excel,
+    file::exh::{ColumnDefinition, ColumnKind},
+};
+use ironworks_schema::{Node, Schema as _, exdschema};
+
+/// 未知游戏版本时使用的EXDSchema版本
+const LATEST: &str = "latest";
+
+/// 列的数据类型，与读取该列时使用的`Field::into_*`对应
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    String,
+    Bool,
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+}
+
+impl From<ColumnKind> for Kind {
+    fn from(kind: ColumnKind) -> Self {
+        match kind {
+            ColumnKind::String => Self::String,
+            ColumnKind::Int8 => Self::I8,
+            ColumnKind::UInt8 => Self::U8,
+            ColumnKind::Int16 => Self::I16,
+            ColumnKind::UInt16 => Self::U16,
+            ColumnKind::Int32 => Self::I32,
+            ColumnKind::UInt32 => Self::U32,
+            ColumnKind::Int64 => Self::I64,
+            ColumnKind::UInt64 => Self::U64,
+            ColumnKind::Float32 => Self::F32,
+            _ => Self::Bool,
+        }
+    }
+}
+
+/// 需要读取的列及其类型。
+///
+/// 数组中的列名为`Name[0]`，结构体中的字段为`Name.Field`，与EXDSchema中的层级一致。
+#[derive(Clone, Default)]
+pub struct Required(Vec<(String, Kind)>);
+
+impl Required {
+    pub fn column(mut self, name: &str, kind: Kind) -> Self {
+        self.0.push((name.to_string(), kind));
+        self
+    }
+
+    /// `name[0]`到`name[count - 1]`
+    pub fn array(mut self, name: &str, count: usize, kind: Kind) -> Self {
+        self.0
+            .extend((0..count).map(|i| (format!("{name}[{i}]"), kind)));
+        self
+    }
+}
+
+/// 解析后的列号，用于`Row::field`
+#[derive(Clone, Debug)]
+pub struct Columns {
+    indices: HashMap<String, usize>,
+}
+
+impl Columns {
+    /// `name`必须在解析时列于[`Required`]中；通过校验后所有声明的列都存在
+    pub fn get(&self, name: &str) -> usize {
+        self.indices[name]
+    }
+
+    /// 数组`name`中第`index`列
+    pub fn at(&self, name: &str, index: usize) -> usize {
+        self.get(&format!("{name}[{index}]"))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("game data does not match the schema:\n{}", .problems.join("\n"))]
+pub struct SchemaError {
+    problems: Vec<String>,
+}
+
+pub struct Schema {
+    version: exdschema::Version,
+}
+
+impl Schema {
+    /// 获取与`game_version`对应的EXDSchema
+    pub fn load(game_version: Option<&str>) -> Result<Self, ironworks_schema::Error> {
+        let provider = exdschema::Provider::new()?;
+        let specifier = provider.specifier(LATEST, game_version.unwrap_or(LATEST))?;
+        Ok(Self {
+            version: provider.version(specifier)?,
+        })
+    }
+
+    /// 开始校验，所有表格解析完成后调用[`Resolver::finish`]
+    pub fn resolver<'a>(&'a self, excel: &'a Excel) -> Resolver<'a> {
+        Resolver {
+            schema: self,
+            excel,
+            problems: Vec::new(),
+        }
+    }
+
+    /// EXDSchema中按顺序排列的列名
+    fn column_names(&self, sheet: &str) -> Result<Vec<String>, ironworks_schema::Error> {
+        let sheet = self.version.sheet(sheet)?;
+        let mut names = Vec::new();
+        flatten(&sheet.node, String::new(), &mut names);
+        Ok(names)
+    }
+}
+
+fn flatten(node: &Node, name: String, names: &mut Vec<String>) {
+    match node {
+        Node::Array { count, node } => {
+            for i in 0..*count {
+                flatten(node, format!("{name}[{i}]"), names);
+            }
+        }
+        Node::Struct(fields) => {
+            for field in fields {
+                let name = match name.as_str() {
+                    "" => field.name.clone(),
+                    _ => format!("{name}.{}", field.name),
+                };
+                flatten(&field.node, name, names);
+            }
+        }
+        _ => names.push(name),
+    }
+}
+
+/// EXDSchema中的列按偏移量排序，同一字节中的布尔值按位排序；
+/// 而`Row::field`使用表头中定义的顺序，需要转换
+fn schema_order(columns: &[ColumnDefinition]) -> Vec<usize> {
+    let bit = |kind: ColumnKind| match kind {
+        ColumnKind::PackedBool0 => 0,
+        ColumnKind::PackedBool1 => 1,
+        ColumnKind::PackedBool2 => 2,
+        ColumnKind::PackedBool3 => 3,
+        ColumnKind::PackedBool4 => 4,
+        ColumnKind::PackedBool5 => 5,
+        ColumnKind::PackedBool6 => 6,
+        ColumnKind::PackedBool7 => 7,
+        _ => 0,
+    };
+    let mut order: Vec<usize> = (0..columns.len()).collect();
+    order.sort_by_key(|&i| (columns[i].offset(), bit(columns[i].kind())));
+    order
+}
+
+/// 收集所有表格的问题，使一次导入就能看到全部需要修改的地方
+pub struct Resolver<'a> {
+    schema: &'a Schema,
+    excel: &'a Excel,
+    problems: Vec<String>,
+}
+
+impl Resolver<'_> {
+    /// 解析`sheet`中`required`的列号，发现的问题留到[`Resolver::finish`]时一并报告
+    pub fn resolve(&mut self, sheet: &str, required: Required) -> Columns {
+        let mut indices = HashMap::new();
+        let names = match self.schema.column_names(sheet) {
+            Ok(names) => names,
+            Err(err) => {
+                self.problems.push(format!("{sheet}: {err}"));
+                return Columns { indices };
+            }
+        };
+        let definitions = match self.excel.sheet(sheet).and_then(|s| s.columns()) {
+            Ok(definitions) => definitions,
+            Err(err) => {
+                self.problems.push(format!("{sheet}: {err}"));
+                return Columns { indices };
+            }
+        };
+        if names.len() != definitions.len() {
+            self.problems.push(format!(
+                "{sheet}: schema has {} columns but the sheet has {}",
+                names.len(),
+                definitions.len()
+            ));
+            return Columns { indices };
+        }
+        let order = schema_order(&definitions);
+        let positions: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), order[i]))
+            .collect();
+        for (name, kind) in required.0 {
+            let Some(&index) = positions.get(name.as_str()) else {
+                self.problems
+                    .push(format!("{sheet}: column {name} not found"));
+                continue;
+            };
+            let found = Kind::from(definitions[index].kind());
+            if found != kind {
+                self.problems.push(format!(
+                    "{sheet}: column {name} is {found:?}, expected {kind:?}"
+                ));
+                continue;
+            }
+            indices.insert(name, index);
+        }
+        Columns { indices }
+    }
+
+    pub fn finish(self) -> Result<(), SchemaError> {
+        if self.problems.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaError {
+                problems: self.problems,
+            })
+        }
+    }
+}
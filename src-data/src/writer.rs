@@ -0,0 +1,198 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 将表格数据写入数据库
+//!
+//! 默认直接插入所有行，要求表为空；更新模式下先读取数据库中已有的行，
+//! 按主键比较后只插入新增的行、覆盖变化的行并删除游戏中已移除的行。
+
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, IdenStatic,
+    IntoActiveModel, Iterable, ModelTrait, PrimaryKeyToColumn, QueryFilter, QueryTrait, Statement,
+    Value,
+    sea_query::{Condition, IntoCondition, OnConflict},
+};
+
+use crate::progress::{Event, Progress};
+
+#[derive(Debug)]
+pub struct Options {
+    pub batch_size: usize,
+    pub update: bool,
+    /// 只写入这些表，`None`时写入所有表
+    pub sheets: Option<HashSet<String>>,
+}
+
+pub struct Writer<'a> {
+    db: &'a DatabaseConnection,
+    options: &'a Options,
+    progress: Progress,
+    /// 更新模式下需要删除的行，在所有表写入完成后按相反的顺序删除，以免违反外键约束
+    deletions: Vec<Statement>,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(db: &'a DatabaseConnection, options: &'a Options, progress: Progress) -> Self {
+        Self {
+            db,
+            options,
+            progress,
+            deletions: Vec::new(),
+        }
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// 表是否需要写入
+    pub fn includes(&self, table: &str) -> bool {
+        self.options
+            .sheets
+            .as_ref()
+            .is_none_or(|sheets| sheets.contains(table))
+    }
+
+    /// 写入整张表
+    pub async fn write<E>(&mut self, entity: E, rows: Vec<E::Model>) -> Result<(), DbErr>
+    where
+        E: EntityTrait,
+        E::Model: IntoActiveModel<E::ActiveModel> + PartialEq,
+    {
+        self.write_scoped(entity, rows, Condition::all()).await
+    }
+
+    /// 写入表中满足`scope`的部分，更新模式下不满足`scope`的已有行不会被删除
+    pub async fn write_scoped<E>(
+        &mut self,
+        entity: E,
+        rows: Vec<E::Model>,
+        scope: impl IntoCondition,
+    ) -> Result<(), DbErr>
+    where
+        E: EntityTrait,
+        E::Model: IntoActiveModel<E::ActiveModel> + PartialEq,
+    {
+        if !self.includes(entity.table_name()) {
+            return Ok(());
+        }
+        if !self.options.update {
+            return self.insert(entity, rows).await;
+        }
+
+        let primary_key: Vec<E::Column> = E::PrimaryKey::iter().map(|k| k.into_column()).collect();
+        let key = |m: &E::Model| -> Vec<Value> { primary_key.iter().map(|&c| m.get(c)).collect() };
+        let mut existing: HashMap<Vec<Value>, E::Model> = E::find()
+            .filter(scope)
+            .all(self.db)
+            .await?
+            .into_iter()
+            .map(|m| (key(&m), m))
+            .collect();
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for row in rows {
+            match existing.remove(&key(&row)) {
+                None => added.push(row),
+                Some(old) if old != row => changed.push(row),
+                Some(_) => {}
+            }
+        }
+        let removed: Vec<E::Model> = existing.into_values().collect();
+        self.progress.emit(Event::Diff {
+            table: entity.table_name(),
+            added: added.len(),
+            changed: changed.len(),
+            removed: removed.len(),
+        });
+
+        for batch in added.chunks(self.options.batch_size) {
+            E::insert_many(
+                batch
+                    .iter()
+                    .cloned()
+                    .map(IntoActiveModel::into_active_model),
+            )
+            .exec(self.db)
+            .await?;
+        }
+        let update_columns: Vec<E::Column> = E::Column::iter()
+            .filter(|c| !primary_key.iter().any(|k| k.as_str() == c.as_str()))
+            .collect();
+        for batch in changed.chunks(self.options.batch_size) {
+            E::insert_many(
+                batch
+                    .iter()
+                    .cloned()
+                    .map(IntoActiveModel::into_active_model),
+            )
+            .on_conflict(
+                OnConflict::columns(primary_key.iter().copied())
+                    .update_columns(update_columns.iter().copied())
+                    .to_owned(),
+            )
+            .exec(self.db)
+            .await?;
+        }
+        let backend = self.db.get_database_backend();
+        for batch in removed.chunks(self.options.batch_size) {
+            let mut condition = Condition::any();
+            for row in batch {
+                let mut row_condition = Condition::all();
+                for &column in &primary_key {
+                    row_condition = row_condition.add(column.eq(row.get(column)));
+                }
+                condition = condition.add(row_condition);
+            }
+            let stat = E::delete_many().filter(condition).build(backend);
+            self.deletions.push(stat);
+        }
+        Ok(())
+    }
+
+    async fn insert<E>(&self, entity: E, rows: Vec<E::Model>) -> Result<(), DbErr>
+    where
+        E: EntityTrait,
+        E::Model: IntoActiveModel<E::ActiveModel>,
+    {
+        for (batch_id, batch) in rows.chunks(self.options.batch_size).enumerate() {
+            self.progress.emit(Event::Batch {
+                table: entity.table_name(),
+                offset: batch_id * self.options.batch_size,
+                rows: batch.len(),
+            });
+            E::insert_many(
+                batch
+                    .iter()
+                    .cloned()
+                    .map(IntoActiveModel::into_active_model),
+            )
+            .exec(self.db)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// 删除更新模式下游戏中已移除的行
+    pub async fn finish(self) -> Result<(), DbErr> {
+        for stat in self.deletions.into_iter().rev() {
+            self.db.execute(stat).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,371 @@
+//! 素材清单（BOM）：展开目标物品的制作配方，汇总所需的素材数量
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::Display,
+};
+
+use sea_orm::{DatabaseConnection, DbErr, entity::*, query::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    prelude::*,
+    queries::{item_names, merge_ingredients},
+    recipes,
+};
+
+/// 水晶、晶簇与碎晶的物品id均小于该值
+const CRYSTAL_ID_END: u32 = 20;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BomRequest {
+    /// 目标物品及其数量
+    pub targets: Vec<(u32, u32)>,
+    /// 已持有的物品数量
+    #[serde(default)]
+    pub holdings: BTreeMap<u32, u32>,
+    /// 为物品指定使用的配方，未指定时使用配方id最小的配方
+    #[serde(default)]
+    pub recipes: BTreeMap<u32, u32>,
+    /// 是否将水晶计入素材
+    #[serde(default)]
+    pub include_crystals: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Bom {
+    /// 每个目标物品的素材树，数量按单独制作该节点计算，不考虑持有的物品与多余的产出
+    pub tree: Vec<BomNode>,
+    /// 按制作顺序的逆序（成品在前，素材在后）排列的所有物品
+    pub items: Vec<BomItem>,
+    /// 需要采集或购买的素材，按物品id排序
+    pub shopping_list: Vec<ShoppingItem>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BomNode {
+    pub item_id: u32,
+    pub amount: u32,
+    pub recipe_id: Option<u32>,
+    pub children: Vec<BomNode>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BomItem {
+    pub item_id: u32,
+    pub item_name: String,
+    /// 需要的总数量，包括作为目标的数量
+    pub required: u32,
+    /// 各个上级物品需要的数量
+    pub required_by: Vec<(u32, u32)>,
+    /// 使用的持有数量
+    pub holding: u32,
+    /// 使用的配方，无法制作的物品为`None`
+    pub recipe_id: Option<u32>,
+    pub craft_type_id: Option<u32>,
+    /// 每次制作的产出数量
+    pub item_amount: u32,
+    /// 制作次数
+    pub crafts: u32,
+    /// 制作后多余的数量
+    pub leftover: u32,
+    /// 距离目标物品的最大层数，目标物品为0
+    pub depth: u32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ShoppingItem {
+    pub item_id: u32,
+    pub item_name: String,
+    pub amount: u32,
+}
+
+#[derive(Debug)]
+pub enum BomError {
+    Db(DbErr),
+    /// 素材之间存在循环依赖，包含无法排序的物品id
+    Cycle(Vec<u32>),
+    /// 指定的配方不存在或不产出该物品
+    InvalidRecipe {
+        item_id: u32,
+        recipe_id: u32,
+    },
+    /// 物品的需求数量超出`u32`的范围
+    Overflow(u32),
+}
+
+impl Display for BomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Db(err) => write!(f, "{err}"),
+            Self::Cycle(items) => write!(f, "ingredients contain a cycle: {items:?}"),
+            Self::InvalidRecipe { item_id, recipe_id } => {
+                write!(f, "recipe {recipe_id} doesn't craft item {item_id}")
+            }
+            Self::Overflow(item_id) => write!(f, "required amount of item {item_id} is too large"),
+        }
+    }
+}
+
+impl std::error::Error for BomError {}
+
+impl From<DbErr> for BomError {
+    fn from(err: DbErr) -> Self {
+        Self::Db(err)
+    }
+}
+
+struct Recipe {
+    id: u32,
+    craft_type_id: u32,
+    item_amount: u32,
+    ingredients: Vec<(u32, u32)>,
+}
+
+impl Recipe {
+    fn new(recipe: &recipes::Model, include_crystals: bool) -> Self {
+        let ingredients = merge_ingredients(recipe)
+            .into_iter()
+            .filter(|&(id, _)| include_crystals || id >= CRYSTAL_ID_END)
+            .map(|(id, amount)| (id, amount as u32))
+            .collect();
+        Self {
+            id: recipe.id,
+            craft_type_id: recipe.craft_type_id,
+            item_amount: (recipe.item_result_amount as u32).max(1),
+            ingredients,
+        }
+    }
+
+    fn crafts(&self, amount: u32) -> u32 {
+        amount.div_ceil(self.item_amount)
+    }
+}
+
+/// 展开目标物品的素材树并汇总数量。
+///
+/// 每个物品只使用一个配方，同一物品在不同位置需要时会合并计算制作次数，
+/// 持有的物品会抵扣所需数量，并相应减少下级素材的需求。
+pub async fn resolve_bom(
+    conn: &DatabaseConnection,
+    request: &BomRequest,
+    lang: Option<&str>,
+) -> Result<Bom, BomError> {
+    let recipes = discover(conn, request).await?;
+    let order = topological_order(request, &recipes)?;
+
+    let mut required: HashMap<u32, u32> = HashMap::new();
+    for &(item_id, amount) in &request.targets {
+        let required = required.entry(item_id).or_default();
+        *required = checked(required.checked_add(amount), item_id)?;
+    }
+    let mut required_by: HashMap<u32, BTreeMap<u32, u32>> = HashMap::new();
+    let mut depths: HashMap<u32, u32> = HashMap::new();
+    let mut items = Vec::with_capacity(order.len());
+    for &item_id in &order {
+        let by = required_by.remove(&item_id).unwrap_or_default();
+        let total = by
+            .values()
+            .try_fold(required.get(&item_id).copied().unwrap_or(0), |acc, &n| {
+                acc.checked_add(n)
+            });
+        let total = checked(total, item_id)?;
+        let holding = request
+            .holdings
+            .get(&item_id)
+            .copied()
+            .unwrap_or(0)
+            .min(total);
+        let needs = total - holding;
+        let depth = depths.get(&item_id).copied().unwrap_or(0);
+        let recipe = recipes.get(&item_id).and_then(Option::as_ref);
+        let crafts = recipe.map_or(0, |r| r.crafts(needs));
+        if let Some(recipe) = recipe {
+            for &(ingredient, amount) in &recipe.ingredients {
+                let amount = checked(crafts.checked_mul(amount), ingredient)?;
+                let entry = required_by
+                    .entry(ingredient)
+                    .or_default()
+                    .entry(item_id)
+                    .or_default();
+                *entry = checked(entry.checked_add(amount), ingredient)?;
+                let d = depths.entry(ingredient).or_default();
+                *d = (*d).max(depth + 1);
+            }
+        }
+        let leftover = match recipe {
+            Some(r) => checked(r.item_amount.checked_mul(crafts), item_id)? - needs,
+            None => 0,
+        };
+        items.push(BomItem {
+            item_id,
+            item_name: String::new(),
+            required: total,
+            required_by: by.into_iter().collect(),
+            holding,
+            recipe_id: recipe.map(|r| r.id),
+            craft_type_id: recipe.map(|r| r.craft_type_id),
+            item_amount: recipe.map_or(0, |r| r.item_amount),
+            crafts,
+            leftover,
+            depth,
+        });
+    }
+
+    let names = item_names(conn, order.iter().copied(), lang).await?;
+    let mut shopping_list = Vec::new();
+    for item in &mut items {
+        item.item_name = names.get(&item.item_id).cloned().unwrap_or_default();
+        if item.recipe_id.is_none() && item.required > item.holding {
+            shopping_list.push(ShoppingItem {
+                item_id: item.item_id,
+                item_name: item.item_name.clone(),
+                amount: item.required - item.holding,
+            });
+        }
+    }
+    shopping_list.sort_by_key(|item| item.item_id);
+
+    let tree = request
+        .targets
+        .iter()
+        .map(|&(item_id, amount)| build_tree(&recipes, item_id, amount))
+        .collect::<Result<_, _>>()?;
+    Ok(Bom {
+        tree,
+        items,
+        shopping_list,
+    })
+}
+
+/// 从目标物品开始逐层查询配方，每层只需要一次查询
+async fn discover(
+    conn: &DatabaseConnection,
+    request: &BomRequest,
+) -> Result<HashMap<u32, Option<Recipe>>, BomError> {
+    let chosen: HashMap<u32, recipes::Model> = Recipes::find()
+        .filter(recipes::Column::Id.is_in(request.recipes.values().copied()))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|r| (r.id, r))
+        .collect();
+
+    let mut recipes: HashMap<u32, Option<Recipe>> = HashMap::new();
+    let mut frontier: Vec<u32> = request.targets.iter().map(|&(id, _)| id).collect();
+    frontier.sort_unstable();
+    frontier.dedup();
+    while !frontier.is_empty() {
+        let mut found: HashMap<u32, recipes::Model> = HashMap::new();
+        let candidates = Recipes::find()
+            .filter(recipes::Column::ItemResultId.is_in(frontier.iter().copied()))
+            .order_by_asc(recipes::Column::Id)
+            .all(conn)
+            .await?;
+        for recipe in candidates {
+            found.entry(recipe.item_result_id).or_insert(recipe);
+        }
+
+        let mut next = Vec::new();
+        for item_id in frontier {
+            let recipe = match request.recipes.get(&item_id) {
+                Some(&recipe_id) => match chosen.get(&recipe_id) {
+                    Some(r) if r.item_result_id == item_id => Some(r),
+                    _ => return Err(BomError::InvalidRecipe { item_id, recipe_id }),
+                },
+                None => found.get(&item_id),
+            };
+            let recipe = recipe.map(|r| Recipe::new(r, request.include_crystals));
+            for (ingredient, _) in recipe.iter().flat_map(|r| &r.ingredients) {
+                if !recipes.contains_key(ingredient) {
+                    next.push(*ingredient);
+                }
+            }
+            recipes.insert(item_id, recipe);
+        }
+        next.sort_unstable();
+        next.dedup();
+        next.retain(|id| !recipes.contains_key(id));
+        frontier = next;
+    }
+    Ok(recipes)
+}
+
+/// 使用Kahn算法排序，成品在前，素材在后
+fn topological_order(
+    request: &BomRequest,
+    recipes: &HashMap<u32, Option<Recipe>>,
+) -> Result<Vec<u32>, BomError> {
+    let mut indegrees: BTreeMap<u32, u32> = recipes.keys().map(|&id| (id, 0)).collect();
+    for recipe in recipes.values().flatten() {
+        for (ingredient, _) in &recipe.ingredients {
+            *indegrees.get_mut(ingredient).unwrap() += 1;
+        }
+    }
+    // 目标物品按请求中的顺序优先
+    let mut queue: VecDeque<u32> = VecDeque::new();
+    for &(item_id, _) in &request.targets {
+        if indegrees[&item_id] == 0 && !queue.contains(&item_id) {
+            queue.push_back(item_id);
+        }
+    }
+    for (&item_id, &indegree) in &indegrees {
+        if indegree == 0 && !queue.contains(&item_id) {
+            queue.push_back(item_id);
+        }
+    }
+
+    let mut order = Vec::with_capacity(recipes.len());
+    while let Some(item_id) = queue.pop_front() {
+        order.push(item_id);
+        for (ingredient, _) in recipes[&item_id].iter().flat_map(|r| &r.ingredients) {
+            let indegree = indegrees.get_mut(ingredient).unwrap();
+            *indegree -= 1;
+            if *indegree == 0 {
+                queue.push_back(*ingredient);
+            }
+        }
+    }
+    if order.len() != recipes.len() {
+        let cycle = indegrees
+            .into_iter()
+            .filter(|&(_, indegree)| indegree > 0)
+            .map(|(id, _)| id)
+            .collect();
+        return Err(BomError::Cycle(cycle));
+    }
+    Ok(order)
+}
+
+fn build_tree(
+    recipes: &HashMap<u32, Option<Recipe>>,
+    item_id: u32,
+    amount: u32,
+) -> Result<BomNode, BomError> {
+    let recipe = recipes.get(&item_id).and_then(Option::as_ref);
+    let children = match recipe {
+        Some(recipe) => {
+            let crafts = recipe.crafts(amount);
+            recipe
+                .ingredients
+                .iter()
+                .map(|&(ingredient, n)| {
+                    let amount = checked(crafts.checked_mul(n), ingredient)?;
+                    build_tree(recipes, ingredient, amount)
+                })
+                .collect::<Result<_, _>>()?
+        }
+        None => Vec::new(),
+    };
+    Ok(BomNode {
+        item_id,
+        amount,
+        recipe_id: recipe.map(|r| r.id),
+        children,
+    })
+}
+
+/// 数量的运算溢出时返回[`BomError::Overflow`]
+fn checked(amount: Option<u32>, item_id: u32) -> Result<u32, BomError> {
+    amount.ok_or(BomError::Overflow(item_id))
+}
@@ -14,6 +14,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::craft_type_translations::Entity")]
+    CraftTypeTranslations,
     #[sea_orm(has_many = "super::recipes::Entity")]
     Recipes,
 }
@@ -24,4 +26,10 @@ impl Related<super::recipes::Entity> for Entity {
     }
 }
 
+impl Related<super::craft_type_translations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CraftTypeTranslations.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
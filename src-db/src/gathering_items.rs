@@ -0,0 +1,35 @@
+//! 可采集的物品，来自`GatheringItem`表
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "GatheringItems")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: u32,
+    #[sea_orm(column_name = "ItemId")]
+    pub item_id: u32,
+    #[sea_orm(column_name = "GatheringItemLevel")]
+    pub gathering_item_level: u16,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::items::Entity",
+        from = "Column::ItemId",
+        to = "super::items::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    Items,
+}
+
+impl Related<super::items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Items.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
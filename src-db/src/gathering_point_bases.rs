@@ -0,0 +1,50 @@
+//! 采集点的类型与可采集物品，来自`GatheringPointBase`表，多个采集点可以共用同一行
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "GatheringPointBases")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: u32,
+    /// 采集类型：0采掘、1碎石、2采伐、3割草，其余类型（如刺鱼）不会被导入
+    #[sea_orm(column_name = "GatheringType")]
+    pub gathering_type: u8,
+    #[sea_orm(column_name = "GatheringLevel")]
+    pub gathering_level: u8,
+    /// 限时采集点
+    #[sea_orm(column_name = "IsLimited")]
+    pub is_limited: bool,
+    /// `GatheringItem`表的id
+    #[sea_orm(column_name = "Item0Id")]
+    pub item0_id: Option<u32>,
+    #[sea_orm(column_name = "Item1Id")]
+    pub item1_id: Option<u32>,
+    #[sea_orm(column_name = "Item2Id")]
+    pub item2_id: Option<u32>,
+    #[sea_orm(column_name = "Item3Id")]
+    pub item3_id: Option<u32>,
+    #[sea_orm(column_name = "Item4Id")]
+    pub item4_id: Option<u32>,
+    #[sea_orm(column_name = "Item5Id")]
+    pub item5_id: Option<u32>,
+    #[sea_orm(column_name = "Item6Id")]
+    pub item6_id: Option<u32>,
+    #[sea_orm(column_name = "Item7Id")]
+    pub item7_id: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::gathering_points::Entity")]
+    GatheringPoints,
+}
+
+impl Related<super::gathering_points::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GatheringPoints.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
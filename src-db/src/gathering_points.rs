@@ -0,0 +1,41 @@
+//! 采集点所在的地点，来自`GatheringPoint`表
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "GatheringPoints")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: u32,
+    #[sea_orm(column_name = "GatheringPointBaseId")]
+    pub gathering_point_base_id: u32,
+    #[sea_orm(column_name = "TerritoryTypeId")]
+    pub territory_type_id: u32,
+    /// `PlaceName`表的id，其它语言的地名见[`super::place_name_translations`]
+    #[sea_orm(column_name = "PlaceNameId")]
+    pub place_name_id: u32,
+    /// 导入时默认语言的地名
+    #[sea_orm(column_name = "PlaceName", column_type = "custom(\"LONGTEXT\")")]
+    pub place_name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::gathering_point_bases::Entity",
+        from = "Column::GatheringPointBaseId",
+        to = "super::gathering_point_bases::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    GatheringPointBases,
+}
+
+impl Related<super::gathering_point_bases::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GatheringPointBases.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,360 @@
+//! 装备：根据装备与镶嵌的魔晶石计算制作属性，以及构建魔晶石镶嵌优化的输入
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use app_libs::melding::{GearPiece, MateriaGrade, Stat, Stats};
+use sea_orm::{DatabaseConnection, DbErr, entity::*, query::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{class_job_categories, gear, gear_params, materia, prelude::*};
+
+/// 作业精度、加工精度与制作力在`BaseParam`中的id
+pub const CRAFTSMANSHIP: u8 = 70;
+pub const CONTROL: u8 = 71;
+pub const CRAFT_POINTS: u8 = 11;
+
+/// 禁断镶嵌时每件装备最多镶嵌的魔晶石数量
+pub use app_libs::melding::MAX_MATERIA_SLOTS;
+
+/// 能工巧匠的名称，与前端的`Jobs`一致
+const JOBS: [&str; 8] = [
+    "carpenter",
+    "blacksmith",
+    "armorer",
+    "goldsmith",
+    "leatherworker",
+    "weaver",
+    "alchemist",
+    "culinarian",
+];
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct EquippedGear {
+    pub item_id: u32,
+    #[serde(default)]
+    pub hq: bool,
+    /// 镶嵌的魔晶石的物品id
+    #[serde(default)]
+    pub materia: Vec<u32>,
+}
+
+/// 一种可以购买的魔晶石，价格与禁断镶嵌的成功率由调用方提供
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct MateriaOffer {
+    pub item_id: u32,
+    /// 单个魔晶石的价格
+    pub cost: u32,
+    /// 在第1、2、3……个禁断孔中镶嵌的成功率（百分比）
+    #[serde(default)]
+    pub overmeld_rates: Vec<u8>,
+}
+
+/// 为一套装备挑选魔晶石的请求
+#[derive(Deserialize, Debug, Clone)]
+pub struct MeldRequest {
+    pub gearset: Vec<EquippedGear>,
+    pub materia: Vec<MateriaOffer>,
+    /// 包括角色自身属性在内的当前属性
+    pub current: Stats,
+    pub target: Stats,
+}
+
+/// 装备提供的制作属性，不包含角色自身的属性
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct GearAttributes {
+    pub craftsmanship: u32,
+    pub control: u32,
+    pub craft_points: u32,
+    /// 能够穿戴全部装备的职业
+    pub compatible_jobs: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum GearsetError {
+    Db(DbErr),
+    /// 物品不是装备
+    NotGear(u32),
+    /// 物品不是魔晶石
+    NotMateria(u32),
+    /// 魔晶石不提供作业精度、加工精度或制作力
+    NotCrafterMateria(u32),
+    /// 镶嵌的魔晶石超过装备允许的数量
+    TooManyMateria {
+        item_id: u32,
+        count: usize,
+        max: u8,
+    },
+}
+
+impl Display for GearsetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Db(err) => write!(f, "{err}"),
+            Self::NotGear(item_id) => write!(f, "item {item_id} is not gear"),
+            Self::NotMateria(item_id) => write!(f, "item {item_id} is not materia"),
+            Self::NotCrafterMateria(item_id) => {
+                write!(f, "materia {item_id} does not raise crafter attributes")
+            }
+            Self::TooManyMateria {
+                item_id,
+                count,
+                max,
+            } => write!(
+                f,
+                "item {item_id} can hold at most {max} materia, got {count}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GearsetError {}
+
+impl From<DbErr> for GearsetError {
+    fn from(err: DbErr) -> Self {
+        Self::Db(err)
+    }
+}
+
+/// 制作属性在`[作业精度, 加工精度, 制作力]`中的位置
+fn attribute_index(base_param: u8) -> Option<usize> {
+    match base_param {
+        CRAFTSMANSHIP => Some(0),
+        CONTROL => Some(1),
+        CRAFT_POINTS => Some(2),
+        _ => None,
+    }
+}
+
+fn category_jobs(category: &class_job_categories::Model) -> [bool; 8] {
+    [
+        category.carpenter,
+        category.blacksmith,
+        category.armorer,
+        category.goldsmith,
+        category.leatherworker,
+        category.weaver,
+        category.alchemist,
+        category.culinarian,
+    ]
+}
+
+/// 装备、装备的属性与镶嵌的魔晶石
+struct LoadedGear {
+    gear: HashMap<u32, gear::Model>,
+    params: HashMap<u32, Vec<gear_params::Model>>,
+    materia: HashMap<u32, materia::Model>,
+}
+
+impl LoadedGear {
+    async fn load(conn: &DatabaseConnection, gearset: &[EquippedGear]) -> Result<Self, DbErr> {
+        let item_ids: HashSet<u32> = gearset.iter().map(|g| g.item_id).collect();
+        let gear = Gear::find()
+            .filter(gear::Column::Id.is_in(item_ids.iter().copied()))
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|g| (g.id, g))
+            .collect();
+        let mut params: HashMap<u32, Vec<gear_params::Model>> = HashMap::new();
+        for p in GearParams::find()
+            .filter(gear_params::Column::ItemId.is_in(item_ids.iter().copied()))
+            .filter(gear_params::Column::BaseParam.is_in([CRAFTSMANSHIP, CONTROL, CRAFT_POINTS]))
+            .all(conn)
+            .await?
+        {
+            params.entry(p.item_id).or_default().push(p);
+        }
+        let materia_ids: HashSet<u32> = gearset
+            .iter()
+            .flat_map(|g| g.materia.iter().copied())
+            .collect();
+        let materia = Materia::find()
+            .filter(materia::Column::ItemId.is_in(materia_ids))
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|m| (m.item_id, m))
+            .collect();
+        Ok(Self {
+            gear,
+            params,
+            materia,
+        })
+    }
+
+    /// 返回装备信息与镶嵌后的属性（不超过装备的上限）
+    fn piece(&self, equipped: &EquippedGear) -> Result<(&gear::Model, [u32; 3]), GearsetError> {
+        let item_id = equipped.item_id;
+        let info = self
+            .gear
+            .get(&item_id)
+            .ok_or(GearsetError::NotGear(item_id))?;
+        let max = if info.is_advanced_melding_permitted {
+            MAX_MATERIA_SLOTS
+        } else {
+            info.materia_slot_count
+        };
+        if equipped.materia.len() > max as usize {
+            return Err(GearsetError::TooManyMateria {
+                item_id,
+                count: equipped.materia.len(),
+                max,
+            });
+        }
+        let mut base = [0i32; 3];
+        for p in self.params.get(&item_id).into_iter().flatten() {
+            if let Some(i) = attribute_index(p.base_param) {
+                let value = if equipped.hq { p.value_hq } else { p.value };
+                base[i] += value as i32;
+            }
+        }
+        let mut melded = [0i32; 3];
+        for materia_id in &equipped.materia {
+            let m = self
+                .materia
+                .get(materia_id)
+                .ok_or(GearsetError::NotMateria(*materia_id))?;
+            if let Some(i) = attribute_index(m.base_param) {
+                melded[i] += m.value as i32;
+            }
+        }
+        let caps = gear_caps(info);
+        let stats = std::array::from_fn(|i| {
+            let cap = (caps[i] as i32).max(base[i]);
+            (base[i] + melded[i]).min(cap).max(0) as u32
+        });
+        Ok((info, stats))
+    }
+}
+
+fn gear_caps(info: &gear::Model) -> [u32; 3] {
+    [
+        info.craftsmanship_max as u32,
+        info.control_max as u32,
+        info.craft_points_max as u32,
+    ]
+}
+
+/// 计算一套装备的制作属性。
+///
+/// 每件装备的属性为基础属性（优质品使用优质品的数值）加上魔晶石的数值，
+/// 但镶嵌后不能超过该装备对应属性的上限。
+pub async fn gear_attributes(
+    conn: &DatabaseConnection,
+    gearset: &[EquippedGear],
+) -> Result<GearAttributes, GearsetError> {
+    let loaded = LoadedGear::load(conn, gearset).await?;
+    let categories: HashMap<u32, class_job_categories::Model> = ClassJobCategories::find()
+        .filter(
+            class_job_categories::Column::Id
+                .is_in(loaded.gear.values().map(|g| g.class_job_category_id)),
+        )
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|c| (c.id, c))
+        .collect();
+
+    let mut total = [0u32; 3];
+    let mut jobs = [true; 8];
+    for equipped in gearset {
+        let (info, stats) = loaded.piece(equipped)?;
+        for i in 0..3 {
+            total[i] += stats[i];
+        }
+        // 没有记录的职业类别视为所有职业均不可装备
+        let category = categories.get(&info.class_job_category_id);
+        let allowed = category.map_or([false; 8], category_jobs);
+        for (job, allowed) in jobs.iter_mut().zip(allowed) {
+            *job &= allowed;
+        }
+    }
+    Ok(GearAttributes {
+        craftsmanship: total[0],
+        control: total[1],
+        craft_points: total[2],
+        compatible_jobs: JOBS
+            .iter()
+            .zip(jobs)
+            .filter(|(_, allowed)| *allowed)
+            .map(|(job, _)| job.to_string())
+            .collect(),
+    })
+}
+
+/// 根据装备与已经镶嵌的魔晶石构建[`app_libs::melding::optimize`]的装备列表，顺序与`gearset`一致。
+///
+/// 普通孔先于禁断孔镶嵌，已镶嵌的魔晶石超过普通孔数量的部分计为已经禁断镶嵌的孔。
+pub async fn gear_pieces(
+    conn: &DatabaseConnection,
+    gearset: &[EquippedGear],
+) -> Result<Vec<GearPiece>, GearsetError> {
+    let loaded = LoadedGear::load(conn, gearset).await?;
+    gearset
+        .iter()
+        .map(|equipped| {
+            let (info, [craftsmanship, control, craft_points]) = loaded.piece(equipped)?;
+            let [cm_max, ct_max, cp_max] = gear_caps(info);
+            let melded = equipped.materia.len() as u8;
+            let slots = info.materia_slot_count.min(MAX_MATERIA_SLOTS);
+            let free_overmeld_slots = if info.is_advanced_melding_permitted {
+                MAX_MATERIA_SLOTS - slots.max(melded)
+            } else {
+                0
+            };
+            Ok(GearPiece {
+                stats: Stats {
+                    craftsmanship,
+                    control,
+                    craft_points,
+                },
+                caps: Stats {
+                    craftsmanship: cm_max,
+                    control: ct_max,
+                    craft_points: cp_max,
+                },
+                free_slots: slots.saturating_sub(melded),
+                free_overmeld_slots,
+                overmelded: melded.saturating_sub(slots),
+            })
+        })
+        .collect()
+}
+
+/// 根据魔晶石的属性构建[`app_libs::melding::optimize`]可用的魔晶石列表，顺序与`offers`一致
+pub async fn materia_grades(
+    conn: &DatabaseConnection,
+    offers: &[MateriaOffer],
+) -> Result<Vec<MateriaGrade>, GearsetError> {
+    let materia: HashMap<u32, materia::Model> = Materia::find()
+        .filter(materia::Column::ItemId.is_in(offers.iter().map(|o| o.item_id)))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|m| (m.item_id, m))
+        .collect();
+    offers
+        .iter()
+        .map(|offer| {
+            let m = materia
+                .get(&offer.item_id)
+                .ok_or(GearsetError::NotMateria(offer.item_id))?;
+            let stat = match m.base_param {
+                CRAFTSMANSHIP => Stat::Craftsmanship,
+                CONTROL => Stat::Control,
+                CRAFT_POINTS => Stat::CraftPoints,
+                _ => return Err(GearsetError::NotCrafterMateria(offer.item_id)),
+            };
+            Ok(MateriaGrade {
+                item_id: offer.item_id,
+                stat,
+                value: m.value.max(0) as u32,
+                cost: offer.cost,
+                overmeld_rates: offer.overmeld_rates.clone(),
+            })
+        })
+        .collect()
+}
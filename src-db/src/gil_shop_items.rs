@@ -0,0 +1,41 @@
+//! 使用金币购买的商店物品，来自`GilShopItem`表
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "GilShopItems")]
+pub struct Model {
+    #[sea_orm(column_name = "ShopId", primary_key, auto_increment = false)]
+    pub shop_id: u32,
+    #[sea_orm(column_name = "SubrowId", primary_key, auto_increment = false)]
+    pub subrow_id: u16,
+    /// 导入时默认语言的商店名称，其它语言见[`super::gil_shop_translations`]
+    #[sea_orm(column_name = "ShopName", column_type = "custom(\"LONGTEXT\")")]
+    pub shop_name: String,
+    #[sea_orm(column_name = "ItemId")]
+    pub item_id: u32,
+    /// 购买单价，即物品的`PriceMid`
+    #[sea_orm(column_name = "Price")]
+    pub price: u32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::items::Entity",
+        from = "Column::ItemId",
+        to = "super::items::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    Items,
+}
+
+impl Related<super::items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Items.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+//! 金币商店名称的多语言翻译，每种语言一行
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+/// 没有对应的主表，`Id`即[`super::gil_shop_items::Model::shop_id`]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "GilShopTranslations")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: u32,
+    /// 语言代码，见[`crate::LANGUAGES`]
+    #[sea_orm(
+        column_name = "Lang",
+        primary_key,
+        auto_increment = false,
+        column_type = "String(StringLen::N(8))"
+    )]
+    pub lang: String,
+    #[sea_orm(column_name = "Name", column_type = "custom(\"LONGTEXT\")")]
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -14,6 +14,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::item_search_category_translations::Entity")]
+    ItemSearchCategoryTranslations,
     #[sea_orm(has_many = "super::items::Entity")]
     Items,
 }
@@ -24,4 +26,10 @@ impl Related<super::items::Entity> for Entity {
     }
 }
 
+impl Related<super::item_search_category_translations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ItemSearchCategoryTranslations.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+//! 物品分类名称的多语言翻译，每种语言一行
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "ItemUICategoryTranslations")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: u32,
+    /// 语言代码，见[`crate::LANGUAGES`]
+    #[sea_orm(
+        column_name = "Lang",
+        primary_key,
+        auto_increment = false,
+        column_type = "String(StringLen::N(8))"
+    )]
+    pub lang: String,
+    #[sea_orm(column_name = "Name", column_type = "custom(\"LONGTEXT\")")]
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::item_ui_categories::Entity",
+        from = "Column::Id",
+        to = "super::item_ui_categories::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    ItemUiCategories,
+}
+
+impl Related<super::item_ui_categories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ItemUiCategories.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -28,6 +28,14 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::gathering_items::Entity")]
+    GatheringItems,
+    #[sea_orm(has_many = "super::gil_shop_items::Entity")]
+    GilShopItems,
+    #[sea_orm(has_many = "super::item_translations::Entity")]
+    ItemTranslations,
+    #[sea_orm(has_many = "super::special_shop_items::Entity")]
+    SpecialShopItems,
     #[sea_orm(
         belongs_to = "super::item_action::Entity",
         from = "Column::ItemActionId",
@@ -72,4 +80,28 @@ impl Related<super::item_ui_categories::Entity> for Entity {
     }
 }
 
+impl Related<super::item_translations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ItemTranslations.def()
+    }
+}
+
+impl Related<super::gathering_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GatheringItems.def()
+    }
+}
+
+impl Related<super::gil_shop_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GilShopItems.def()
+    }
+}
+
+impl Related<super::special_shop_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SpecialShopItems.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,17 +1,53 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.12
 
+pub mod bom;
+pub mod gearset;
+pub mod plan;
 pub mod prelude;
+pub mod queries;
+pub mod search;
+pub mod version;
 
+pub mod class_job_categories;
 pub mod collectables_shop_refine;
+pub mod craft_type_translations;
 pub mod craft_types;
+pub mod gathering_items;
+pub mod gathering_point_bases;
+pub mod gathering_points;
+pub mod gear;
+pub mod gear_params;
+pub mod gil_shop_items;
+pub mod gil_shop_translations;
 pub mod item_action;
 pub mod item_food;
 pub mod item_food_effect;
 pub mod item_search_categories;
+pub mod item_search_category_translations;
+pub mod item_translations;
 pub mod item_ui_categories;
+pub mod item_ui_category_translations;
 pub mod items;
+pub mod materia;
+pub mod metadata;
+pub mod notebook_division_categories;
+pub mod notebook_division_category_translations;
+pub mod notebook_division_translations;
+pub mod notebook_divisions;
+pub mod place_name_translations;
 pub mod recipe_level_tables;
+pub mod recipe_notebook_lists;
+pub mod recipe_search;
 pub mod recipes;
+pub mod secret_recipe_book_translations;
+pub mod secret_recipe_books;
+pub mod shared_rotations;
+pub mod special_shop_items;
+pub mod special_shop_translations;
 pub mod wks_mission_recipe;
 pub mod wks_mission_to_do;
 pub mod wks_mission_unit;
+pub mod wks_mission_unit_translations;
+
+/// 多语言翻译表中使用的语言代码，与前端数据源的语言一致
+pub const LANGUAGES: [&str; 7] = ["ja", "en", "de", "fr", "zh-CN", "zh-TW", "ko"];
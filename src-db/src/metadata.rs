@@ -0,0 +1,18 @@
+//! 数据库的元数据，例如结构版本与游戏版本，键见[`crate::version`]
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "Metadata")]
+pub struct Model {
+    #[sea_orm(column_name = "Key", primary_key, auto_increment = false)]
+    pub key: String,
+    #[sea_orm(column_name = "Value", column_type = "Text")]
+    pub value: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,149 @@
+//! 制作计划：将素材清单中需要制作的物品按依赖关系分阶段，并在每个阶段内按职业分组
+
+use std::collections::{BTreeMap, HashMap};
+
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::{
+    bom::{Bom, BomError, BomRequest, ShoppingItem, resolve_bom},
+    queries::craft_types,
+};
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CraftingPlan {
+    /// 开始制作前需要采集或购买的素材
+    pub shopping_list: Vec<ShoppingItem>,
+    /// 按执行顺序排列的制作步骤，同一阶段内按职业分组
+    pub groups: Vec<JobGroup>,
+    /// 每个职业的制作次数汇总，按职业id排序
+    pub jobs: Vec<JobSummary>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct JobGroup {
+    /// 阶段序号，从0开始；每个阶段只使用之前阶段的产出
+    pub stage: u32,
+    pub craft_type_id: u32,
+    pub craft_type_name: String,
+    pub steps: Vec<PlanStep>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlanStep {
+    pub recipe_id: u32,
+    pub item_id: u32,
+    pub item_name: String,
+    /// 制作次数
+    pub crafts: u32,
+    /// 总产出数量，等于制作次数乘以每次的产出数量
+    pub amount: u32,
+    /// 产出中多余的数量
+    pub leftover: u32,
+    /// 本步骤消耗的素材及其总数量
+    pub ingredients: Vec<(u32, u32)>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct JobSummary {
+    pub craft_type_id: u32,
+    pub craft_type_name: String,
+    /// 需要制作的配方数量
+    pub recipes: u32,
+    /// 制作次数之和
+    pub crafts: u32,
+}
+
+/// 展开目标物品的素材清单并生成制作计划
+pub async fn crafting_plan(
+    conn: &DatabaseConnection,
+    request: &BomRequest,
+    lang: Option<&str>,
+) -> Result<CraftingPlan, BomError> {
+    let bom = resolve_bom(conn, request, lang).await?;
+    let names = craft_types(conn, lang)
+        .await?
+        .into_iter()
+        .map(|c| (c.id, c.name))
+        .collect();
+    Ok(schedule(bom, &names))
+}
+
+/// 物品所在的阶段为其所有需要制作的素材的最大阶段加一，
+/// 因此同一阶段内的步骤互不依赖，可以按职业任意合并。
+fn schedule(bom: Bom, craft_type_names: &HashMap<u32, String>) -> CraftingPlan {
+    let mut ingredients: HashMap<u32, Vec<(u32, u32)>> = HashMap::new();
+    for item in &bom.items {
+        for &(by, amount) in &item.required_by {
+            if amount > 0 {
+                ingredients
+                    .entry(by)
+                    .or_default()
+                    .push((item.item_id, amount));
+            }
+        }
+    }
+
+    // `bom.items`中成品在前，逆序遍历时素材总是先于成品
+    let mut stages: HashMap<u32, u32> = HashMap::new();
+    let mut groups: BTreeMap<(u32, u32), Vec<PlanStep>> = BTreeMap::new();
+    let mut jobs: BTreeMap<u32, JobSummary> = BTreeMap::new();
+    for item in bom.items.iter().rev() {
+        let (Some(recipe_id), Some(craft_type_id)) = (item.recipe_id, item.craft_type_id) else {
+            continue;
+        };
+        if item.crafts == 0 {
+            continue;
+        }
+        let mut ingredients = ingredients.remove(&item.item_id).unwrap_or_default();
+        ingredients.sort_unstable();
+        let stage = ingredients
+            .iter()
+            .filter_map(|(id, _)| stages.get(id))
+            .map(|s| s + 1)
+            .max()
+            .unwrap_or(0);
+        stages.insert(item.item_id, stage);
+
+        let job = jobs.entry(craft_type_id).or_insert_with(|| JobSummary {
+            craft_type_id,
+            craft_type_name: craft_type_name(craft_type_names, craft_type_id),
+            recipes: 0,
+            crafts: 0,
+        });
+        job.recipes += 1;
+        job.crafts = job.crafts.saturating_add(item.crafts);
+
+        groups
+            .entry((stage, craft_type_id))
+            .or_default()
+            .push(PlanStep {
+                recipe_id,
+                item_id: item.item_id,
+                item_name: item.item_name.clone(),
+                crafts: item.crafts,
+                amount: item.crafts * item.item_amount,
+                leftover: item.leftover,
+                ingredients,
+            });
+    }
+
+    let groups = groups
+        .into_iter()
+        .map(|((stage, craft_type_id), steps)| JobGroup {
+            stage,
+            craft_type_id,
+            craft_type_name: craft_type_name(craft_type_names, craft_type_id),
+            steps,
+        })
+        .collect();
+    CraftingPlan {
+        shopping_list: bom.shopping_list,
+        groups,
+        jobs: jobs.into_values().collect(),
+    }
+}
+
+fn craft_type_name(names: &HashMap<u32, String>, id: u32) -> String {
+    names.get(&id).cloned().unwrap_or_default()
+}
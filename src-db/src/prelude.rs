@@ -1,15 +1,42 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.12
 
+pub use super::class_job_categories::Entity as ClassJobCategories;
 pub use super::collectables_shop_refine::Entity as CollectablesShopRefine;
+pub use super::craft_type_translations::Entity as CraftTypeTranslations;
 pub use super::craft_types::Entity as CraftTypes;
+pub use super::gathering_items::Entity as GatheringItems;
+pub use super::gathering_point_bases::Entity as GatheringPointBases;
+pub use super::gathering_points::Entity as GatheringPoints;
+pub use super::gear::Entity as Gear;
+pub use super::gear_params::Entity as GearParams;
+pub use super::gil_shop_items::Entity as GilShopItems;
+pub use super::gil_shop_translations::Entity as GilShopTranslations;
 pub use super::item_action::Entity as ItemAction;
 pub use super::item_food::Entity as ItemFood;
 pub use super::item_food_effect::Entity as ItemFoodEffect;
 pub use super::item_search_categories::Entity as ItemSearchCategories;
+pub use super::item_search_category_translations::Entity as ItemSearchCategoryTranslations;
+pub use super::item_translations::Entity as ItemTranslations;
 pub use super::item_ui_categories::Entity as ItemUiCategories;
+pub use super::item_ui_category_translations::Entity as ItemUiCategoryTranslations;
 pub use super::items::Entity as Items;
+pub use super::materia::Entity as Materia;
+pub use super::metadata::Entity as Metadata;
+pub use super::notebook_division_categories::Entity as NotebookDivisionCategories;
+pub use super::notebook_division_category_translations::Entity as NotebookDivisionCategoryTranslations;
+pub use super::notebook_division_translations::Entity as NotebookDivisionTranslations;
+pub use super::notebook_divisions::Entity as NotebookDivisions;
+pub use super::place_name_translations::Entity as PlaceNameTranslations;
 pub use super::recipe_level_tables::Entity as RecipeLevelTables;
+pub use super::recipe_notebook_lists::Entity as RecipeNotebookLists;
+pub use super::recipe_search::Entity as RecipeSearch;
 pub use super::recipes::Entity as Recipes;
+pub use super::secret_recipe_book_translations::Entity as SecretRecipeBookTranslations;
+pub use super::secret_recipe_books::Entity as SecretRecipeBooks;
+pub use super::shared_rotations::Entity as SharedRotations;
+pub use super::special_shop_items::Entity as SpecialShopItems;
+pub use super::special_shop_translations::Entity as SpecialShopTranslations;
 pub use super::wks_mission_recipe::Entity as WksMissionRecipe;
 pub use super::wks_mission_to_do::Entity as WksMissionToDo;
 pub use super::wks_mission_unit::Entity as WksMissionUnit;
+pub use super::wks_mission_unit_translations::Entity as WksMissionUnitTranslations;
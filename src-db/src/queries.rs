@@ -0,0 +1,1016 @@
+//! 桌面端与web-source-server共用的查询
+
+use std::collections::{BTreeMap, HashMap};
+
+use app_libs::{QualityIngredient, enhancer::Enhancer};
+use sea_orm::{
+    DatabaseConnection, DbErr, FromQueryResult, RelationDef, SelectModel, Selector,
+    entity::*,
+    query::*,
+    sea_query::{Expr, Func, IntoCondition, SimpleExpr},
+};
+use serde::Serialize;
+
+use crate::{
+    collectables_shop_refine, craft_type_translations, craft_types, gathering_items,
+    gathering_point_bases, gil_shop_items, gil_shop_translations, item_action, item_food,
+    item_food_effect, item_translations, items, notebook_division_categories,
+    notebook_division_category_translations, notebook_division_translations, notebook_divisions,
+    place_name_translations, prelude::*, recipe_level_tables, recipe_notebook_lists, recipes,
+    search, secret_recipe_book_translations, secret_recipe_books, special_shop_items,
+    special_shop_translations, wks_mission_recipe, wks_mission_to_do, wks_mission_unit,
+    wks_mission_unit_translations,
+};
+
+/// 配方列表每页的条目数
+pub const RECIPE_PAGE_SIZE: u64 = 200;
+
+pub const MEDICINE_SEARCH_ID: u32 = 43;
+pub const MEALS_SEARCH_ID: u32 = 45;
+
+#[derive(FromQueryResult, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecipeInfo {
+    pub id: u32,
+    pub rlv: u32,
+    pub item_id: u32,
+    pub item_name: String,
+    pub item_amount: u8,
+    pub job: String,
+
+    pub difficulty_factor: u16,
+    pub quality_factor: u16,
+    pub durability_factor: u16,
+    pub material_quality_factor: u8,
+
+    pub required_craftsmanship: u16,
+    pub required_control: u16,
+
+    pub can_hq: bool,
+    pub is_expert: bool,
+    pub recipe_notebook_list: u32,
+    /// 配方所在的制作笔记页
+    pub notebook_division_id: Option<u32>,
+    pub notebook_division: Option<String>,
+    /// 解锁配方的秘籍
+    pub secret_recipe_book_id: Option<u32>,
+    pub secret_recipe_book: Option<String>,
+}
+
+/// 配方列表的筛选条件
+#[derive(Default, Debug, Clone)]
+pub struct RecipeFilter {
+    pub recipe_level: Option<u32>,
+    pub craft_type_id: Option<u32>,
+    pub job_level_min: Option<u32>,
+    pub job_level_max: Option<u32>,
+    /// 制作笔记的分类，即配方所属的资料片，见[`notebook_division_categories`]
+    pub expansion: Option<u32>,
+    pub notebook_division_id: Option<u32>,
+    pub secret_recipe_book_id: Option<u32>,
+}
+
+/// 左连接翻译表中`lang`语言的行
+fn translation(relation: RelationDef, lang_column: impl ColumnTrait, lang: &str) -> RelationDef {
+    let lang = lang.to_string();
+    relation.on_condition(move |_, right| {
+        Expr::col((right, lang_column))
+            .eq(lang.clone())
+            .into_condition()
+    })
+}
+
+/// 优先使用翻译后的名称，没有对应语言的翻译时使用导入时默认语言的名称
+fn translated_name(
+    name: impl ColumnTrait,
+    translated: impl ColumnTrait,
+    lang: Option<&str>,
+) -> SimpleExpr {
+    match lang {
+        Some(_) => Func::coalesce([
+            Expr::col(translated.as_column_ref()).into(),
+            Expr::col(name.as_column_ref()).into(),
+        ])
+        .into(),
+        None => Expr::col(name.as_column_ref()).into(),
+    }
+}
+
+/// 查询`E`的所有行，指定`lang`时`name`列使用该语言的翻译
+async fn translated_rows<E: EntityTrait>(
+    conn: &DatabaseConnection,
+    relation: RelationDef,
+    lang_column: impl ColumnTrait,
+    name: E::Column,
+    translated: impl ColumnTrait,
+    order: E::Column,
+    lang: Option<&str>,
+) -> Result<Vec<E::Model>, DbErr> {
+    let mut query = E::find();
+    if let Some(lang) = lang {
+        query = query
+            .join(JoinType::LeftJoin, translation(relation, lang_column, lang))
+            .select_only();
+        for column in E::Column::iter() {
+            query = if column.as_str() == name.as_str() {
+                query.column_as(translated_name(name, translated, Some(lang)), name.as_str())
+            } else {
+                query.column(column)
+            };
+        }
+    }
+    query.order_by(order, Order::Asc).all(conn).await
+}
+
+/// 连接配方成品、制作职业、制作笔记与秘籍的翻译表，需与[`select_recipe_info`]使用相同的`lang`
+fn join_recipe_translations(query: Select<Recipes>, lang: Option<&str>) -> Select<Recipes> {
+    let query = query
+        .join(JoinType::InnerJoin, recipes::Relation::CraftTypes.def())
+        .join(JoinType::InnerJoin, recipes::Relation::ItemResultItem.def())
+        .join(
+            JoinType::InnerJoin,
+            recipes::Relation::RecipeLevelTables.def(),
+        )
+        .join(
+            JoinType::LeftJoin,
+            recipes::Relation::RecipeNotebookLists.def(),
+        )
+        .join(
+            JoinType::LeftJoin,
+            recipe_notebook_lists::Relation::NotebookDivisions.def(),
+        )
+        .join(
+            JoinType::LeftJoin,
+            recipes::Relation::SecretRecipeBooks.def(),
+        );
+    let Some(lang) = lang else {
+        return query;
+    };
+    query
+        .join(
+            JoinType::LeftJoin,
+            translation(
+                items::Relation::ItemTranslations.def(),
+                item_translations::Column::Lang,
+                lang,
+            ),
+        )
+        .join(
+            JoinType::LeftJoin,
+            translation(
+                craft_types::Relation::CraftTypeTranslations.def(),
+                craft_type_translations::Column::Lang,
+                lang,
+            ),
+        )
+        .join(
+            JoinType::LeftJoin,
+            translation(
+                notebook_divisions::Relation::NotebookDivisionTranslations.def(),
+                notebook_division_translations::Column::Lang,
+                lang,
+            ),
+        )
+        .join(
+            JoinType::LeftJoin,
+            translation(
+                secret_recipe_books::Relation::SecretRecipeBookTranslations.def(),
+                secret_recipe_book_translations::Column::Lang,
+                lang,
+            ),
+        )
+}
+
+fn item_name(lang: Option<&str>) -> SimpleExpr {
+    translated_name(items::Column::Name, item_translations::Column::Name, lang)
+}
+
+fn select_recipe_info(
+    query: Select<Recipes>,
+    lang: Option<&str>,
+) -> Selector<SelectModel<RecipeInfo>> {
+    join_recipe_translations(query, lang)
+        .select_only()
+        .column_as(recipes::Column::Id, "id")
+        .column_as(recipes::Column::RecipeLevelId, "rlv")
+        .column_as(items::Column::Id, "item_id")
+        .column_as(item_name(lang), "item_name")
+        .column_as(recipes::Column::ItemResultAmount, "item_amount")
+        .column_as(
+            translated_name(
+                craft_types::Column::Name,
+                craft_type_translations::Column::Name,
+                lang,
+            ),
+            "job",
+        )
+        .column_as(recipes::Column::DifficultyFactor, "difficulty_factor")
+        .column_as(recipes::Column::QualityFactor, "quality_factor")
+        .column_as(recipes::Column::DurabilityFactor, "durability_factor")
+        .column_as(
+            recipes::Column::MaterialQualityFactor,
+            "material_quality_factor",
+        )
+        .column_as(
+            recipes::Column::RequiredCraftsmanship,
+            "required_craftsmanship",
+        )
+        .column_as(recipes::Column::RequiredControl, "required_control")
+        .column_as(recipes::Column::CanHq, "can_hq")
+        .column_as(recipes::Column::IsExpert, "is_expert")
+        .column_as(recipes::Column::RecipeNotebookList, "recipe_notebook_list")
+        .column_as(notebook_divisions::Column::Id, "notebook_division_id")
+        .column_as(
+            translated_name(
+                notebook_divisions::Column::Name,
+                notebook_division_translations::Column::Name,
+                lang,
+            ),
+            "notebook_division",
+        )
+        .column_as(recipes::Column::SecretRecipeBookId, "secret_recipe_book_id")
+        .column_as(
+            translated_name(
+                secret_recipe_books::Column::Name,
+                secret_recipe_book_translations::Column::Name,
+                lang,
+            ),
+            "secret_recipe_book",
+        )
+        .into_model::<RecipeInfo>()
+}
+
+fn apply_filter(mut query: Select<Recipes>, filter: &RecipeFilter) -> Select<Recipes> {
+    if let Some(rlv) = filter.recipe_level {
+        query = query.filter(recipes::Column::RecipeLevelId.eq(rlv))
+    }
+    if let Some(craft_type_id) = filter.craft_type_id {
+        query = query.filter(recipes::Column::CraftTypeId.eq(craft_type_id))
+    }
+    if let Some(job_level_min) = filter.job_level_min {
+        query = query.filter(recipe_level_tables::Column::ClassJobLevel.gte(job_level_min))
+    }
+    if let Some(job_level_max) = filter.job_level_max {
+        query = query.filter(recipe_level_tables::Column::ClassJobLevel.lte(job_level_max))
+    }
+    if let Some(expansion) = filter.expansion {
+        query = query.filter(notebook_divisions::Column::NotebookDivisionCategoryId.eq(expansion))
+    }
+    if let Some(division) = filter.notebook_division_id {
+        query = query.filter(notebook_divisions::Column::Id.eq(division))
+    }
+    if let Some(book) = filter.secret_recipe_book_id {
+        query = query.filter(recipes::Column::SecretRecipeBookId.eq(book))
+    }
+    query
+}
+
+/// 分页查询配方列表，按配方id排序，返回当页数据和总页数。
+///
+/// `search_name`为SQL LIKE模式，匹配成品名，或匹配宇宙探索任务名（此时返回该任务的所有配方）。
+/// 指定`lang`时名称使用该语言的翻译，见[`crate::LANGUAGES`]。
+pub async fn recipe_table(
+    conn: &DatabaseConnection,
+    page_id: u64,
+    search_name: &str,
+    filter: &RecipeFilter,
+    lang: Option<&str>,
+) -> Result<(Vec<RecipeInfo>, u64), DbErr> {
+    let mut wks = WksMissionRecipe::find().join(
+        JoinType::InnerJoin,
+        wks_mission_recipe::Relation::WksMissionUnit.def(),
+    );
+    if let Some(lang) = lang {
+        wks = wks.join(
+            JoinType::LeftJoin,
+            translation(
+                wks_mission_unit::Relation::WksMissionUnitTranslations.def(),
+                wks_mission_unit_translations::Column::Lang,
+                lang,
+            ),
+        );
+    }
+    let wks_ids = wks
+        .filter(
+            translated_name(
+                wks_mission_unit::Column::Name,
+                wks_mission_unit_translations::Column::Name,
+                lang,
+            )
+            .like(search_name),
+        )
+        .all(conn)
+        .await?
+        .into_iter()
+        .flat_map(|r| {
+            [
+                r.recipe0_id,
+                r.recipe1_id,
+                r.recipe2_id,
+                r.recipe3_id,
+                r.recipe4_id,
+            ]
+        })
+        .flatten();
+
+    let query = Recipes::find().filter(
+        item_name(lang)
+            .like(search_name)
+            .or(recipes::Column::Id.is_in(wks_ids)),
+    );
+    let query = apply_filter(query, filter).order_by(recipes::Column::Id, Order::Asc);
+    let paginate = select_recipe_info(query, lang).paginate(conn, RECIPE_PAGE_SIZE);
+    let p = paginate.num_pages().await?;
+    let data = paginate.fetch_page(page_id).await?;
+    Ok((data, p))
+}
+
+/// 使用全文索引分页搜索配方，按相关度排序，返回当页数据和总页数。
+///
+/// 搜索方式见[`search::search`]，搜索词可以是任意语言的名称，搜索词为空时按配方id返回所有配方。
+pub async fn search_recipes(
+    conn: &DatabaseConnection,
+    page_id: u64,
+    query: &str,
+    filter: &RecipeFilter,
+    lang: Option<&str>,
+) -> Result<(Vec<RecipeInfo>, u64), DbErr> {
+    if search::normalize(query).is_empty() {
+        return recipe_table(conn, page_id, "%", filter, lang).await;
+    }
+    let ids = search::search(conn, query).await?;
+    let rank: HashMap<u32, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    let query = apply_filter(
+        Recipes::find().filter(recipes::Column::Id.is_in(ids)),
+        filter,
+    );
+    let mut data = select_recipe_info(query, lang).all(conn).await?;
+    data.sort_by_key(|r| rank.get(&r.id).copied());
+
+    let page_size = RECIPE_PAGE_SIZE as usize;
+    let p = data.len().div_ceil(page_size) as u64;
+    let data = data
+        .into_iter()
+        .skip(page_id as usize * page_size)
+        .take(page_size)
+        .collect();
+    Ok((data, p))
+}
+
+pub async fn recipe_info(
+    conn: &DatabaseConnection,
+    recipe_id: u32,
+    lang: Option<&str>,
+) -> Result<Option<RecipeInfo>, DbErr> {
+    select_recipe_info(Recipes::find_by_id(recipe_id), lang)
+        .one(conn)
+        .await
+}
+
+pub async fn recipe_level_table(
+    conn: &DatabaseConnection,
+    rlv: u32,
+) -> Result<Option<recipe_level_tables::Model>, DbErr> {
+    RecipeLevelTables::find_by_id(rlv).one(conn).await
+}
+
+/// 查询指定职业等级对应的最低配方等级
+pub async fn recipe_level_table_by_job_level(
+    conn: &DatabaseConnection,
+    job_level: u32,
+) -> Result<Option<recipe_level_tables::Model>, DbErr> {
+    RecipeLevelTables::find()
+        .filter(recipe_level_tables::Column::ClassJobLevel.eq(job_level))
+        .order_by_asc(recipe_level_tables::Column::Id)
+        .one(conn)
+        .await
+}
+
+/// 查询配方所需的素材及数量，按物品id排序，重复出现的素材会被合并。
+/// 配方不存在时返回`None`。
+pub async fn recipe_ingredients(
+    conn: &DatabaseConnection,
+    recipe_id: u32,
+) -> Result<Option<Vec<(u32, u8)>>, DbErr> {
+    let recipe = Recipes::find_by_id(recipe_id).one(conn).await?;
+    Ok(recipe.as_ref().map(merge_ingredients))
+}
+
+/// 合并配方中重复出现的素材，按物品id排序
+pub(crate) fn merge_ingredients(recipe: &recipes::Model) -> Vec<(u32, u8)> {
+    let ingredients = [
+        (recipe.ingredient0, recipe.ingredient_amount0),
+        (recipe.ingredient1, recipe.ingredient_amount1),
+        (recipe.ingredient2, recipe.ingredient_amount2),
+        (recipe.ingredient3, recipe.ingredient_amount3),
+        (recipe.ingredient4, recipe.ingredient_amount4),
+        (recipe.ingredient5, recipe.ingredient_amount5),
+        (recipe.ingredient6, recipe.ingredient_amount6),
+        (recipe.ingredient7, recipe.ingredient_amount7),
+    ];
+    let mut needs = BTreeMap::new();
+    for (id, amount) in ingredients {
+        if id != 0 {
+            needs
+                .entry(id)
+                .and_modify(|e| *e += amount)
+                .or_insert(amount);
+        }
+    }
+    needs.into_iter().collect()
+}
+
+/// 计算初期品质所需的配方信息
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecipeMaterialQuality {
+    /// 配方的最高品质
+    pub quality: u32,
+    pub material_quality_factor: u8,
+    /// 按物品id排序
+    pub ingredients: Vec<MaterialItem>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MaterialItem {
+    pub item_id: u32,
+    pub level: u32,
+    pub can_be_hq: bool,
+    pub amount: u8,
+}
+
+impl RecipeMaterialQuality {
+    /// 根据每种素材使用的HQ数量（物品id到数量的映射）计算初期品质，见[`app_libs::initial_quality`]
+    pub fn initial_quality(&self, hq_amounts: &BTreeMap<u32, u32>) -> u32 {
+        let ingredients: Vec<_> = self
+            .ingredients
+            .iter()
+            .map(|i| QualityIngredient {
+                level: i.level,
+                can_be_hq: i.can_be_hq,
+                amount: i.amount as u32,
+                hq_amount: hq_amounts.get(&i.item_id).copied().unwrap_or(0),
+            })
+            .collect();
+        app_libs::initial_quality(self.quality, self.material_quality_factor, &ingredients)
+    }
+}
+
+/// 查询配方的最高品质与各个素材的品级，配方不存在时返回`None`
+pub async fn recipe_material_quality(
+    conn: &DatabaseConnection,
+    recipe_id: u32,
+) -> Result<Option<RecipeMaterialQuality>, DbErr> {
+    let Some((recipe, Some(rlv))) = Recipes::find_by_id(recipe_id)
+        .find_also_related(RecipeLevelTables)
+        .one(conn)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let ingredients = merge_ingredients(&recipe);
+    let items: HashMap<u32, items::Model> = Items::find()
+        .filter(items::Column::Id.is_in(ingredients.iter().map(|&(id, _)| id)))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|i| (i.id, i))
+        .collect();
+    let ingredients = ingredients
+        .into_iter()
+        .map(|(item_id, amount)| {
+            let item = items.get(&item_id);
+            MaterialItem {
+                item_id,
+                level: item.map_or(0, |i| i.level),
+                can_be_hq: item.is_some_and(|i| i.can_be_hq),
+                amount,
+            }
+        })
+        .collect();
+    Ok(Some(RecipeMaterialQuality {
+        quality: rlv.quality * recipe.quality_factor as u32 / 100,
+        material_quality_factor: recipe.material_quality_factor,
+        ingredients,
+    }))
+}
+
+/// 查询配方并根据每种素材使用的HQ数量计算初期品质，配方不存在时返回`None`
+pub async fn initial_quality(
+    conn: &DatabaseConnection,
+    recipe_id: u32,
+    hq_amounts: &BTreeMap<u32, u32>,
+) -> Result<Option<u32>, DbErr> {
+    let info = recipe_material_quality(conn, recipe_id).await?;
+    Ok(info.map(|info| info.initial_quality(hq_amounts)))
+}
+
+pub async fn recipe_collectability(
+    conn: &DatabaseConnection,
+    recipe_id: u32,
+) -> Result<Option<collectables_shop_refine::Model>, DbErr> {
+    CollectablesShopRefine::find()
+        .reverse_join(Recipes)
+        .filter(recipes::Column::Id.eq(recipe_id))
+        .one(conn)
+        .await
+}
+
+pub async fn item_info(
+    conn: &DatabaseConnection,
+    item_id: u32,
+    lang: Option<&str>,
+) -> Result<Option<items::Model>, DbErr> {
+    let mut query = Items::find_by_id(item_id);
+    if let Some(lang) = lang {
+        query = query
+            .join(
+                JoinType::LeftJoin,
+                translation(
+                    items::Relation::ItemTranslations.def(),
+                    item_translations::Column::Lang,
+                    lang,
+                ),
+            )
+            .select_only()
+            .columns(items::Column::iter().filter(|c| !matches!(c, items::Column::Name)))
+            .column_as(item_name(Some(lang)), items::Column::Name.as_str());
+    }
+    query.one(conn).await
+}
+
+/// 批量查询物品名称，不存在的物品不会出现在结果中
+pub async fn item_names(
+    conn: &DatabaseConnection,
+    item_ids: impl IntoIterator<Item = u32>,
+    lang: Option<&str>,
+) -> Result<HashMap<u32, String>, DbErr> {
+    let mut query = Items::find().filter(items::Column::Id.is_in(item_ids));
+    if let Some(lang) = lang {
+        query = query.join(
+            JoinType::LeftJoin,
+            translation(
+                items::Relation::ItemTranslations.def(),
+                item_translations::Column::Lang,
+                lang,
+            ),
+        );
+    }
+    let names = query
+        .select_only()
+        .column(items::Column::Id)
+        .column_as(item_name(lang), "name")
+        .into_tuple::<(u32, String)>()
+        .all(conn)
+        .await?;
+    Ok(names.into_iter().collect())
+}
+
+pub async fn craft_types(
+    conn: &DatabaseConnection,
+    lang: Option<&str>,
+) -> Result<Vec<craft_types::Model>, DbErr> {
+    translated_rows::<CraftTypes>(
+        conn,
+        craft_types::Relation::CraftTypeTranslations.def(),
+        craft_type_translations::Column::Lang,
+        craft_types::Column::Name,
+        craft_type_translations::Column::Name,
+        craft_types::Column::Id,
+        lang,
+    )
+    .await
+}
+
+/// 制作笔记的分类、页与秘籍，用于筛选配方列表
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecipeNotebooks {
+    pub categories: Vec<notebook_division_categories::Model>,
+    pub divisions: Vec<notebook_divisions::Model>,
+    pub secret_recipe_books: Vec<secret_recipe_books::Model>,
+}
+
+pub async fn recipe_notebooks(
+    conn: &DatabaseConnection,
+    lang: Option<&str>,
+) -> Result<RecipeNotebooks, DbErr> {
+    let categories = translated_rows::<NotebookDivisionCategories>(
+        conn,
+        notebook_division_categories::Relation::NotebookDivisionCategoryTranslations.def(),
+        notebook_division_category_translations::Column::Lang,
+        notebook_division_categories::Column::Name,
+        notebook_division_category_translations::Column::Name,
+        notebook_division_categories::Column::Id,
+        lang,
+    )
+    .await?;
+    let divisions = translated_rows::<NotebookDivisions>(
+        conn,
+        notebook_divisions::Relation::NotebookDivisionTranslations.def(),
+        notebook_division_translations::Column::Lang,
+        notebook_divisions::Column::Name,
+        notebook_division_translations::Column::Name,
+        notebook_divisions::Column::Id,
+        lang,
+    )
+    .await?;
+    let secret_recipe_books = translated_rows::<SecretRecipeBooks>(
+        conn,
+        secret_recipe_books::Relation::SecretRecipeBookTranslations.def(),
+        secret_recipe_book_translations::Column::Lang,
+        secret_recipe_books::Column::Name,
+        secret_recipe_book_translations::Column::Name,
+        secret_recipe_books::Column::Id,
+        lang,
+    )
+    .await?;
+    Ok(RecipeNotebooks {
+        categories,
+        divisions,
+        secret_recipe_books,
+    })
+}
+
+#[derive(FromQueryResult, Debug)]
+struct ItemFoodAction {
+    name: String,
+    level: u32,
+    item_food_id: u16,
+    #[allow(dead_code)]
+    item_food_duration: u16,
+}
+
+pub async fn medicine_table(
+    conn: &DatabaseConnection,
+    lang: Option<&str>,
+) -> Result<Vec<Enhancer>, DbErr> {
+    query_enhancers(conn, MEDICINE_SEARCH_ID, lang).await
+}
+
+pub async fn meals_table(
+    conn: &DatabaseConnection,
+    lang: Option<&str>,
+) -> Result<Vec<Enhancer>, DbErr> {
+    query_enhancers(conn, MEALS_SEARCH_ID, lang).await
+}
+
+/// 查询指定物品搜索分类下提供制作属性加成的物品，每个物品分别返回NQ和HQ两项
+pub async fn query_enhancers(
+    conn: &DatabaseConnection,
+    search_id: u32,
+    lang: Option<&str>,
+) -> Result<Vec<Enhancer>, DbErr> {
+    let crafting_item_food = ItemFood::find()
+        .join(
+            JoinType::InnerJoin,
+            item_food::Relation::ItemFoodEffect.def(),
+        )
+        .select_with(ItemFoodEffect)
+        .filter(item_food_effect::Column::BaseParam.is_in([11, 70, 71]))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|(item_food, effects)| (item_food.id, effects));
+    let crafting_item_food = BTreeMap::from_iter(crafting_item_food);
+    let mut items = Items::find();
+    if let Some(lang) = lang {
+        items = items.join(
+            JoinType::LeftJoin,
+            translation(
+                items::Relation::ItemTranslations.def(),
+                item_translations::Column::Lang,
+                lang,
+            ),
+        );
+    }
+    let result = items
+        .select_only()
+        .column_as(item_name(lang), "name")
+        .select_column_as(items::Column::Level, "level")
+        .filter(items::Column::ItemSearchCategoryId.eq(search_id))
+        .join(JoinType::InnerJoin, items::Relation::ItemAction.def())
+        .select_column_as(item_action::Column::Data2, "item_food_id")
+        .select_column_as(item_action::Column::Data3, "item_food_duration")
+        .filter(item_action::Column::Type.between(844, 846))
+        .filter(item_action::Column::Data2.is_in(crafting_item_food.iter().map(|v| *v.0)))
+        .order_by_asc(items::Column::Id)
+        .into_model::<ItemFoodAction>()
+        .all(conn)
+        .await?;
+    let result = result
+        .into_iter()
+        .flat_map(|item| {
+            let mut enh = Enhancer {
+                name: item.name.clone(),
+                level: item.level,
+                is_hq: false,
+                ..Enhancer::default()
+            };
+            let mut enh_hq = Enhancer {
+                name: item.name.clone(),
+                level: item.level,
+                is_hq: true,
+                ..Enhancer::default()
+            };
+            for item_food in crafting_item_food
+                .get(&(item.item_food_id as u32))
+                .into_iter()
+                .flatten()
+            {
+                match item_food.base_param {
+                    11 => {
+                        enh.cp = Some(item_food.value);
+                        enh.cp_max = Some(item_food.max);
+                        enh_hq.cp = Some(item_food.value_hq);
+                        enh_hq.cp_max = Some(item_food.max_hq);
+                    }
+                    70 => {
+                        enh.cm = Some(item_food.value);
+                        enh.cm_max = Some(item_food.max);
+                        enh_hq.cm = Some(item_food.value_hq);
+                        enh_hq.cm_max = Some(item_food.max_hq);
+                    }
+                    71 => {
+                        enh.ct = Some(item_food.value);
+                        enh.ct_max = Some(item_food.max);
+                        enh_hq.ct = Some(item_food.value_hq);
+                        enh_hq.ct_max = Some(item_food.max_hq);
+                    }
+                    _ => {}
+                }
+            }
+            [enh, enh_hq].into_iter()
+        })
+        .collect();
+    Ok(result)
+}
+
+#[derive(Default, Serialize, FromQueryResult, Debug, Clone, PartialEq, Eq)]
+pub struct TemporaryActionInfo {
+    pub action: u32,
+    pub count: u32,
+}
+
+/// 查询宇宙探索任务配方可使用的临时技能及其次数
+pub async fn temporary_action_info(
+    conn: &DatabaseConnection,
+    recipe_id: u32,
+) -> Result<Option<TemporaryActionInfo>, DbErr> {
+    WksMissionUnit::find()
+        .join(
+            JoinType::InnerJoin,
+            wks_mission_unit::Relation::WksMissionRecipe.def(),
+        )
+        .join(
+            JoinType::LeftJoin,
+            wks_mission_unit::Relation::WksMissionToDo3.def(),
+        )
+        .filter(
+            wks_mission_recipe::Column::Recipe0Id
+                .eq(recipe_id)
+                .or(wks_mission_recipe::Column::Recipe1Id.eq(recipe_id))
+                .or(wks_mission_recipe::Column::Recipe2Id.eq(recipe_id))
+                .or(wks_mission_recipe::Column::Recipe3Id.eq(recipe_id))
+                .or(wks_mission_recipe::Column::Recipe4Id.eq(recipe_id)),
+        )
+        .select_only()
+        .column_as(wks_mission_to_do::Column::TemporaryAction, "action")
+        .column_as(wks_mission_to_do::Column::TemporaryActionCount, "count")
+        .into_model::<TemporaryActionInfo>()
+        .one(conn)
+        .await
+}
+
+/// 物品的获取途径，用于区分素材应当制作、购买还是采集
+#[derive(Default, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ItemSources {
+    pub item_id: u32,
+    /// 产出该物品的配方id
+    pub recipes: Vec<u32>,
+    pub gil_shops: Vec<GilShopSource>,
+    pub special_shops: Vec<SpecialShopSource>,
+    pub gathering: Vec<GatheringSource>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct GilShopSource {
+    pub shop_id: u32,
+    pub shop_name: String,
+    pub price: u32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpecialShopSource {
+    pub shop_id: u32,
+    pub shop_name: String,
+    /// 每次兑换获得的数量
+    pub count: u32,
+    /// 每次兑换消耗的物品（或货币类型）及数量
+    pub costs: Vec<(u32, u32)>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct GatheringSource {
+    pub gathering_point_base_id: u32,
+    pub gathering_type: u8,
+    pub gathering_level: u8,
+    pub gathering_item_level: u16,
+    pub is_limited: bool,
+    /// 使用该采集点类型的地点名称，已去重并排序
+    pub places: Vec<String>,
+}
+
+/// 查询`ids`在`lang`语言中的名称，用于没有主表的翻译表，没有翻译的id不在结果中
+async fn translation_names<E: EntityTrait>(
+    conn: &DatabaseConnection,
+    id: E::Column,
+    lang_column: E::Column,
+    name: E::Column,
+    ids: Vec<u32>,
+    lang: Option<&str>,
+) -> Result<HashMap<u32, String>, DbErr> {
+    let Some(lang) = lang else {
+        return Ok(HashMap::new());
+    };
+    let rows: Vec<(u32, String)> = E::find()
+        .filter(lang_column.eq(lang))
+        .filter(id.is_in(ids))
+        .select_only()
+        .column(id)
+        .column(name)
+        .into_tuple()
+        .all(conn)
+        .await?;
+    Ok(rows.into_iter().collect())
+}
+
+/// 批量查询物品的获取途径，结果与`item_ids`的顺序一致。
+///
+/// 指定`lang`时商店与地点名称使用该语言的翻译。
+pub async fn item_sources(
+    conn: &DatabaseConnection,
+    item_ids: &[u32],
+    lang: Option<&str>,
+) -> Result<Vec<ItemSources>, DbErr> {
+    let mut sources: BTreeMap<u32, ItemSources> = item_ids
+        .iter()
+        .map(|&item_id| {
+            let sources = ItemSources {
+                item_id,
+                ..Default::default()
+            };
+            (item_id, sources)
+        })
+        .collect();
+    let ids = || item_ids.iter().copied();
+
+    let recipes = Recipes::find()
+        .filter(recipes::Column::ItemResultId.is_in(ids()))
+        .order_by_asc(recipes::Column::Id)
+        .all(conn)
+        .await?;
+    for r in recipes {
+        if let Some(s) = sources.get_mut(&r.item_result_id) {
+            s.recipes.push(r.id);
+        }
+    }
+
+    let gil_shop_items = GilShopItems::find()
+        .filter(gil_shop_items::Column::ItemId.is_in(ids()))
+        .order_by_asc(gil_shop_items::Column::ShopId)
+        .all(conn)
+        .await?;
+    let shop_names = translation_names::<GilShopTranslations>(
+        conn,
+        gil_shop_translations::Column::Id,
+        gil_shop_translations::Column::Lang,
+        gil_shop_translations::Column::Name,
+        gil_shop_items.iter().map(|i| i.shop_id).collect(),
+        lang,
+    )
+    .await?;
+    for i in gil_shop_items {
+        if let Some(s) = sources.get_mut(&i.item_id) {
+            s.gil_shops.push(GilShopSource {
+                shop_id: i.shop_id,
+                shop_name: shop_names.get(&i.shop_id).cloned().unwrap_or(i.shop_name),
+                price: i.price,
+            });
+        }
+    }
+
+    let special_shop_items = SpecialShopItems::find()
+        .filter(special_shop_items::Column::ItemId.is_in(ids()))
+        .order_by_asc(special_shop_items::Column::ShopId)
+        .order_by_asc(special_shop_items::Column::Entry)
+        .all(conn)
+        .await?;
+    let shop_names = translation_names::<SpecialShopTranslations>(
+        conn,
+        special_shop_translations::Column::Id,
+        special_shop_translations::Column::Lang,
+        special_shop_translations::Column::Name,
+        special_shop_items.iter().map(|i| i.shop_id).collect(),
+        lang,
+    )
+    .await?;
+    for i in special_shop_items {
+        let costs = [
+            (i.cost_item0_id, i.cost_count0),
+            (i.cost_item1_id, i.cost_count1),
+            (i.cost_item2_id, i.cost_count2),
+        ];
+        if let Some(s) = sources.get_mut(&i.item_id) {
+            s.special_shops.push(SpecialShopSource {
+                shop_id: i.shop_id,
+                shop_name: shop_names.get(&i.shop_id).cloned().unwrap_or(i.shop_name),
+                count: i.count,
+                costs: costs
+                    .into_iter()
+                    .filter_map(|(id, count)| Some((id?, count)))
+                    .collect(),
+            });
+        }
+    }
+
+    let gathering_items: HashMap<u32, gathering_items::Model> = GatheringItems::find()
+        .filter(gathering_items::Column::ItemId.is_in(ids()))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|i| (i.id, i))
+        .collect();
+    if !gathering_items.is_empty() {
+        let gathering_ids = || gathering_items.keys().copied();
+        let bases = GatheringPointBases::find()
+            .filter(
+                Condition::any()
+                    .add(gathering_point_bases::Column::Item0Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item1Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item2Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item3Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item4Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item5Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item6Id.is_in(gathering_ids()))
+                    .add(gathering_point_bases::Column::Item7Id.is_in(gathering_ids())),
+            )
+            .order_by_asc(gathering_point_bases::Column::Id)
+            .find_with_related(GatheringPoints)
+            .all(conn)
+            .await?;
+        let place_names = translation_names::<PlaceNameTranslations>(
+            conn,
+            place_name_translations::Column::Id,
+            place_name_translations::Column::Lang,
+            place_name_translations::Column::Name,
+            bases
+                .iter()
+                .flat_map(|(_, points)| points.iter().map(|p| p.place_name_id))
+                .collect(),
+            lang,
+        )
+        .await?;
+        for (base, points) in bases {
+            let mut places: Vec<String> = points
+                .into_iter()
+                .map(|p| {
+                    place_names
+                        .get(&p.place_name_id)
+                        .cloned()
+                        .unwrap_or(p.place_name)
+                })
+                .collect();
+            places.sort_unstable();
+            places.dedup();
+            let items = [
+                base.item0_id,
+                base.item1_id,
+                base.item2_id,
+                base.item3_id,
+                base.item4_id,
+                base.item5_id,
+                base.item6_id,
+                base.item7_id,
+            ];
+            for item in items
+                .iter()
+                .flatten()
+                .filter_map(|id| gathering_items.get(id))
+            {
+                if let Some(s) = sources.get_mut(&item.item_id) {
+                    s.gathering.push(GatheringSource {
+                        gathering_point_base_id: base.id,
+                        gathering_type: base.gathering_type,
+                        gathering_level: base.gathering_level,
+                        gathering_item_level: item.gathering_item_level,
+                        is_limited: base.is_limited,
+                        places: places.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(item_ids
+        .iter()
+        .filter_map(|id| sources.get(id).cloned())
+        .collect())
+}
@@ -0,0 +1,34 @@
+//! 配方搜索索引，由`app-data`在导入后根据配方成品名生成，见[`crate::search`]
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "RecipeSearch")]
+pub struct Model {
+    #[sea_orm(column_name = "RecipeId", primary_key, auto_increment = false)]
+    pub recipe_id: u32,
+    /// 以空格分隔的搜索词，均已经过[`crate::search::normalize`]处理
+    #[sea_orm(column_name = "Keys", column_type = "Text")]
+    pub keys: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::recipes::Entity",
+        from = "Column::RecipeId",
+        to = "super::recipes::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    Recipes,
+}
+
+impl Related<super::recipes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Recipes.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
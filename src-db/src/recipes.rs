@@ -42,6 +42,9 @@ pub struct Model {
     pub collectables_metadata: Option<u32>,
     #[sea_orm(column_name = "RecipeNotebookList")]
     pub recipe_notebook_list: u32,
+    /// 需要阅读秘籍才能制作时为秘籍的id
+    #[sea_orm(column_name = "SecretRecipeBookId")]
+    pub secret_recipe_book_id: Option<u32>,
     #[sea_orm(column_name = "Ingredient0")]
     pub ingredient0: u32,
     #[sea_orm(column_name = "IngredientAmount0")]
@@ -112,6 +115,22 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     CollectablesShopRefine,
+    #[sea_orm(
+        belongs_to = "super::recipe_notebook_lists::Entity",
+        from = "Column::RecipeNotebookList",
+        to = "super::recipe_notebook_lists::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    RecipeNotebookLists,
+    #[sea_orm(
+        belongs_to = "super::secret_recipe_books::Entity",
+        from = "Column::SecretRecipeBookId",
+        to = "super::secret_recipe_books::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    SecretRecipeBooks,
 }
 
 impl Related<super::craft_types::Entity> for Entity {
@@ -138,4 +157,16 @@ impl Related<super::collectables_shop_refine::Entity> for Entity {
     }
 }
 
+impl Related<super::recipe_notebook_lists::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RecipeNotebookLists.def()
+    }
+}
+
+impl Related<super::secret_recipe_books::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SecretRecipeBooks.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
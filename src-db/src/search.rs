@@ -0,0 +1,303 @@
+//! 配方全文搜索
+//!
+//! `app-data`导入完成后调用[`rebuild_index`]，将配方成品名与宇宙探索任务名（包括所有语言的翻译）写入`RecipeSearch`表，
+//! 并根据数据库类型建立全文索引：SQLite使用FTS5虚拟表，MySQL使用ngram全文索引，Postgres使用GIN索引。
+//!
+//! 索引内容与搜索词均经过[`normalize`]处理，因此全角半角、大小写以及平假名与片假名不影响匹配。
+//! 导入时还可以为每个名称附加额外的搜索词（例如中文名称的拼音及其首字母）。
+
+use std::collections::BTreeMap;
+
+use sea_orm::{
+    DatabaseBackend, DatabaseConnection, DbErr, FromQueryResult, Statement, entity::*, query::*,
+};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    item_translations, items, prelude::*, recipe_search, recipes, wks_mission_unit_translations,
+};
+
+/// 全文索引的名称，SQLite中为FTS5虚拟表名，MySQL与Postgres中为索引名
+const FTS_NAME: &str = "RecipeSearchFts";
+
+/// 单次搜索返回的最大配方数量
+pub const MAX_RESULTS: u64 = 1000;
+
+/// 每批写入索引表的行数
+const INSERT_BATCH_SIZE: usize = 500;
+
+/// 将文本拆分为搜索词。
+///
+/// 文本先经过NFKC规范化并转为小写，片假名转为平假名；
+/// 汉字、假名与谚文每个字单独作为一个词，其余连续的字母和数字作为一个词，标点与空白被丢弃。
+pub fn normalize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    for c in text
+        .nfkc()
+        .flat_map(char::to_lowercase)
+        .map(katakana_to_hiragana)
+    {
+        if is_cjk(c) {
+            tokens.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            tokens.push(c.to_string());
+        } else if c.is_alphanumeric() {
+            word.push(c);
+        } else if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+    }
+    tokens.extend((!word.is_empty()).then_some(word));
+    tokens
+}
+
+fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' | 'ヽ' | 'ヾ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // 平假名、片假名
+        | '\u{3400}'..='\u{4DBF}' // 汉字扩展A
+        | '\u{4E00}'..='\u{9FFF}' // 汉字
+        | '\u{AC00}'..='\u{D7AF}' // 谚文音节
+        | '\u{F900}'..='\u{FAFF}' // 兼容汉字
+        | '\u{20000}'..='\u{2FFFF}' // 汉字扩展B及以后
+    )
+}
+
+/// 重建配方搜索索引。
+///
+/// `lang`为主表中名称的语言代码。`extra_keys`以名称及其语言代码为参数，
+/// 为每个配方成品名与宇宙探索任务名提供额外的搜索词，不需要时传入`|_, _| Vec::new()`。
+pub async fn rebuild_index(
+    conn: &DatabaseConnection,
+    lang: &str,
+    extra_keys: impl Fn(&str, &str) -> Vec<String>,
+) -> Result<(), DbErr> {
+    let item_translations = ItemTranslations::find()
+        .select_only()
+        .column(item_translations::Column::Id)
+        .column(item_translations::Column::Lang)
+        .column(item_translations::Column::Name)
+        .into_tuple()
+        .all(conn)
+        .await?;
+    let item_translations = group_by_id(item_translations);
+    let unit_translations = WksMissionUnitTranslations::find()
+        .select_only()
+        .column(wks_mission_unit_translations::Column::Id)
+        .column(wks_mission_unit_translations::Column::Lang)
+        .column(wks_mission_unit_translations::Column::Name)
+        .into_tuple()
+        .all(conn)
+        .await?;
+    let unit_translations = group_by_id(unit_translations);
+
+    // 每个配方的所有名称及其语言代码
+    let mut names: BTreeMap<u32, Vec<(String, String)>> = BTreeMap::new();
+    let recipe_names = Recipes::find()
+        .join(JoinType::InnerJoin, recipes::Relation::ItemResultItem.def())
+        .select_only()
+        .column(recipes::Column::Id)
+        .column(items::Column::Id)
+        .column(items::Column::Name)
+        .into_tuple::<(u32, u32, String)>()
+        .all(conn)
+        .await?;
+    for (id, item_id, name) in recipe_names {
+        let names = names.entry(id).or_default();
+        names.push((lang.to_string(), name));
+        names.extend(
+            item_translations
+                .get(&item_id)
+                .into_iter()
+                .flatten()
+                .cloned(),
+        );
+    }
+    let missions = WksMissionRecipe::find()
+        .find_with_related(WksMissionUnit)
+        .all(conn)
+        .await?;
+    for (r, units) in missions {
+        let ids = [
+            r.recipe0_id,
+            r.recipe1_id,
+            r.recipe2_id,
+            r.recipe3_id,
+            r.recipe4_id,
+        ];
+        for id in ids.into_iter().flatten() {
+            if let Some(names) = names.get_mut(&id) {
+                for unit in &units {
+                    names.push((lang.to_string(), unit.name.clone()));
+                    names.extend(
+                        unit_translations
+                            .get(&unit.id)
+                            .into_iter()
+                            .flatten()
+                            .cloned(),
+                    );
+                }
+            }
+        }
+    }
+
+    let rows = names.into_iter().map(|(recipe_id, names)| {
+        let mut keys: Vec<String> = Vec::new();
+        for (lang, name) in &names {
+            let extra = extra_keys(name, lang);
+            for key in normalize(name)
+                .into_iter()
+                .chain(extra.iter().flat_map(|k| normalize(k)))
+            {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+        recipe_search::ActiveModel {
+            recipe_id: Set(recipe_id),
+            keys: Set(keys.join(" ")),
+        }
+    });
+
+    RecipeSearch::delete_many().exec(conn).await?;
+    let rows: Vec<_> = rows.collect();
+    for batch in rows.chunks(INSERT_BATCH_SIZE) {
+        RecipeSearch::insert_many(batch.to_vec()).exec(conn).await?;
+    }
+    create_fts_index(conn).await
+}
+
+fn group_by_id(rows: Vec<(u32, String, String)>) -> BTreeMap<u32, Vec<(String, String)>> {
+    let mut names: BTreeMap<u32, Vec<(String, String)>> = BTreeMap::new();
+    for (id, lang, name) in rows {
+        names.entry(id).or_default().push((lang, name));
+    }
+    names
+}
+
+async fn create_fts_index(conn: &DatabaseConnection) -> Result<(), DbErr> {
+    let backend = conn.get_database_backend();
+    let exec = |sql: String| conn.execute(Statement::from_string(backend, sql));
+    match backend {
+        DatabaseBackend::Sqlite => {
+            // FTS5外部内容表不会自动同步，每次导入后重新建立
+            exec(format!("DROP TABLE IF EXISTS {FTS_NAME}")).await?;
+            exec(format!(
+                "CREATE VIRTUAL TABLE {FTS_NAME} USING fts5(Keys, content='RecipeSearch', \
+                 content_rowid='RecipeId', tokenize='unicode61 remove_diacritics 2')"
+            ))
+            .await?;
+            exec(format!(
+                "INSERT INTO {FTS_NAME}({FTS_NAME}) VALUES('rebuild')"
+            ))
+            .await?;
+        }
+        DatabaseBackend::MySql => {
+            // 单个汉字或假名需要服务器设置`ngram_token_size=1`才能被索引
+            let exists = conn
+                .query_one(Statement::from_string(
+                    backend,
+                    format!(
+                        "SELECT COUNT(*) FROM information_schema.STATISTICS \
+                         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'RecipeSearch' \
+                         AND INDEX_NAME = '{FTS_NAME}'"
+                    ),
+                ))
+                .await?
+                .map(|row| row.try_get_by_index::<i64>(0))
+                .transpose()?
+                .unwrap_or(0)
+                > 0;
+            if !exists {
+                exec(format!(
+                    "ALTER TABLE `RecipeSearch` ADD FULLTEXT INDEX `{FTS_NAME}` (`Keys`) \
+                     WITH PARSER ngram"
+                ))
+                .await?;
+            }
+        }
+        DatabaseBackend::Postgres => {
+            exec(format!(
+                "CREATE INDEX IF NOT EXISTS \"{FTS_NAME}\" ON \"RecipeSearch\" \
+                 USING GIN (to_tsvector('simple', \"Keys\"))"
+            ))
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(FromQueryResult)]
+struct SearchHit {
+    recipe_id: u32,
+}
+
+/// 搜索配方，按相关度从高到低返回配方id。
+///
+/// 每个搜索词均按前缀匹配，全部匹配的配方才会被返回；
+/// 全文索引没有结果时，退化为在搜索词表中任意位置包含各个搜索词的模糊匹配。
+pub async fn search(conn: &DatabaseConnection, query: &str) -> Result<Vec<u32>, DbErr> {
+    let tokens = normalize(query);
+    if tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+    let backend = conn.get_database_backend();
+    let stat = match backend {
+        DatabaseBackend::Sqlite => Statement::from_sql_and_values(
+            backend,
+            format!(
+                "SELECT rowid AS recipe_id FROM {FTS_NAME} WHERE {FTS_NAME} MATCH ? \
+                 ORDER BY rank, rowid LIMIT {MAX_RESULTS}"
+            ),
+            [fts_query(&tokens, |t| format!("\"{t}\"*"), " ").into()],
+        ),
+        DatabaseBackend::MySql => Statement::from_sql_and_values(
+            backend,
+            format!(
+                "SELECT `RecipeId` AS recipe_id, MATCH(`Keys`) AGAINST(? IN BOOLEAN MODE) AS score \
+                 FROM `RecipeSearch` HAVING score > 0 \
+                 ORDER BY score DESC, `RecipeId` LIMIT {MAX_RESULTS}"
+            ),
+            [fts_query(&tokens, |t| format!("+{t}*"), " ").into()],
+        ),
+        DatabaseBackend::Postgres => Statement::from_sql_and_values(
+            backend,
+            format!(
+                "SELECT \"RecipeId\" AS recipe_id FROM \"RecipeSearch\", \
+                 to_tsquery('simple', $1) AS q WHERE to_tsvector('simple', \"Keys\") @@ q \
+                 ORDER BY ts_rank(to_tsvector('simple', \"Keys\"), q) DESC, \"RecipeId\" \
+                 LIMIT {MAX_RESULTS}"
+            ),
+            [fts_query(&tokens, |t| format!("'{t}':*"), " & ").into()],
+        ),
+    };
+    let hits = SearchHit::find_by_statement(stat).all(conn).await?;
+    if !hits.is_empty() {
+        return Ok(hits.into_iter().map(|h| h.recipe_id).collect());
+    }
+
+    let mut fuzzy = RecipeSearch::find();
+    for token in &tokens {
+        fuzzy = fuzzy.filter(recipe_search::Column::Keys.contains(token));
+    }
+    fuzzy
+        .select_only()
+        .column(recipe_search::Column::RecipeId)
+        .order_by_asc(recipe_search::Column::RecipeId)
+        .limit(MAX_RESULTS)
+        .into_tuple()
+        .all(conn)
+        .await
+}
+
+/// [`normalize`]的结果只包含字母、数字和单个汉字，可以直接拼入查询语法
+fn fts_query(tokens: &[String], term: impl Fn(&str) -> String, sep: &str) -> String {
+    tokens.iter().map(|t| term(t)).collect::<Vec<_>>().join(sep)
+}
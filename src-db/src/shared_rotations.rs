@@ -0,0 +1,51 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 玩家分享的技能序列，保存在服务端单独配置的可写数据库中，
+//! 不属于由app-data管理的游戏数据，表由[`create_table`]创建
+
+use sea_orm::{Schema, entity::prelude::*};
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "SharedRotations")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: String,
+    #[sea_orm(column_name = "RecipeId")]
+    pub recipe_id: u32,
+    #[sea_orm(column_name = "Attributes")]
+    pub attributes: Json,
+    #[sea_orm(column_name = "Actions")]
+    pub actions: Json,
+    #[sea_orm(column_name = "Notes", column_type = "Text")]
+    pub notes: String,
+    #[sea_orm(column_name = "CreatedAt")]
+    pub created_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 在表不存在时创建，数据库中已有的分享不受影响
+pub async fn create_table(conn: &DatabaseConnection) -> Result<(), DbErr> {
+    let backend = conn.get_database_backend();
+    let mut stat = Schema::new(backend).create_table_from_entity(Entity);
+    conn.execute(backend.build(stat.if_not_exists())).await?;
+    Ok(())
+}
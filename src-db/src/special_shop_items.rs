@@ -0,0 +1,57 @@
+//! 使用物品或货币兑换的商店物品，来自`SpecialShop`表，每个兑换项的每个产出物品一行
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "SpecialShopItems")]
+pub struct Model {
+    #[sea_orm(column_name = "ShopId", primary_key, auto_increment = false)]
+    pub shop_id: u32,
+    /// 兑换项在商店中的序号
+    #[sea_orm(column_name = "Entry", primary_key, auto_increment = false)]
+    pub entry: u8,
+    /// 产出物品在兑换项中的序号
+    #[sea_orm(column_name = "Slot", primary_key, auto_increment = false)]
+    pub slot: u8,
+    /// 导入时默认语言的商店名称，其它语言见[`super::special_shop_translations`]
+    #[sea_orm(column_name = "ShopName", column_type = "custom(\"LONGTEXT\")")]
+    pub shop_name: String,
+    #[sea_orm(column_name = "ItemId")]
+    pub item_id: u32,
+    #[sea_orm(column_name = "Count")]
+    pub count: u32,
+    /// 消耗的物品，小于100的值为货币类型而非物品id
+    #[sea_orm(column_name = "CostItem0Id")]
+    pub cost_item0_id: Option<u32>,
+    #[sea_orm(column_name = "CostCount0")]
+    pub cost_count0: u32,
+    #[sea_orm(column_name = "CostItem1Id")]
+    pub cost_item1_id: Option<u32>,
+    #[sea_orm(column_name = "CostCount1")]
+    pub cost_count1: u32,
+    #[sea_orm(column_name = "CostItem2Id")]
+    pub cost_item2_id: Option<u32>,
+    #[sea_orm(column_name = "CostCount2")]
+    pub cost_count2: u32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::items::Entity",
+        from = "Column::ItemId",
+        to = "super::items::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    Items,
+}
+
+impl Related<super::items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Items.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,159 @@
+//! 数据库结构版本与数据来源
+//!
+//! `app-data`通过迁移建立表结构，并在`Metadata`表中记录结构版本；
+//! 读取数据库的一方在打开时调用[`check_schema`]，拒绝结构不一致的数据库，而不是在查询时才出错。
+//! 导入完成后还会记录游戏版本、语言等信息，可以通过[`data_info`]读取。
+
+use std::{collections::HashMap, fmt::Display};
+
+use sea_orm::{
+    DatabaseBackend, DatabaseConnection, DbErr, Statement, entity::*, query::*,
+    sea_query::OnConflict,
+};
+use serde::Serialize;
+
+use crate::{metadata, prelude::*};
+
+/// 当前的结构版本，每次在`app-data`中新增迁移时加一
+pub const SCHEMA_VERSION: u32 = 4;
+
+pub const SCHEMA_VERSION_KEY: &str = "SchemaVersion";
+/// 数据对应的游戏版本，例如`2024.11.06.0000.0000`
+pub const GAME_VERSION_KEY: &str = "GameVersion";
+/// 主表中名称的语言代码，见[`crate::LANGUAGES`]
+pub const LANGUAGE_KEY: &str = "Language";
+/// 导入完成的时间，Unix时间戳（秒）
+pub const IMPORTED_AT_KEY: &str = "ImportedAt";
+/// 导入数据的`app-data`的版本
+pub const TOOL_VERSION_KEY: &str = "ToolVersion";
+
+/// 数据库的来源信息，没有记录的项为`None`
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataInfo {
+    pub schema_version: Option<u32>,
+    pub game_version: Option<String>,
+    pub language: Option<String>,
+    pub imported_at: Option<i64>,
+    pub tool_version: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum VersionError {
+    Db(DbErr),
+    /// 数据库中没有结构版本，由不支持迁移的旧版本`app-data`生成，重新导入时会升级
+    Unversioned,
+    Incompatible {
+        found: u32,
+        expected: u32,
+    },
+}
+
+impl Display for VersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Db(err) => write!(f, "{err}"),
+            Self::Unversioned => write!(
+                f,
+                "database has no schema version, please upgrade it with app-data"
+            ),
+            Self::Incompatible { found, expected } => {
+                write!(
+                    f,
+                    "database schema version {found} is incompatible, expected {expected}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for VersionError {}
+
+impl From<DbErr> for VersionError {
+    fn from(err: DbErr) -> Self {
+        Self::Db(err)
+    }
+}
+
+/// 读取数据库的结构版本，没有`Metadata`表或版本记录时返回`None`
+pub async fn schema_version(conn: &DatabaseConnection) -> Result<Option<u32>, DbErr> {
+    if !has_metadata_table(conn).await? {
+        return Ok(None);
+    }
+    let version = Metadata::find_by_id(SCHEMA_VERSION_KEY)
+        .one(conn)
+        .await?
+        .and_then(|m| m.value.parse().ok());
+    Ok(version)
+}
+
+/// 检查数据库的结构版本是否与当前版本一致
+pub async fn check_schema(conn: &DatabaseConnection) -> Result<(), VersionError> {
+    match schema_version(conn).await? {
+        None => Err(VersionError::Unversioned),
+        Some(SCHEMA_VERSION) => Ok(()),
+        Some(found) => Err(VersionError::Incompatible {
+            found,
+            expected: SCHEMA_VERSION,
+        }),
+    }
+}
+
+/// 读取数据库的来源信息
+pub async fn data_info(conn: &DatabaseConnection) -> Result<DataInfo, DbErr> {
+    if !has_metadata_table(conn).await? {
+        return Ok(DataInfo::default());
+    }
+    let mut values: HashMap<String, String> = Metadata::find()
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|m| (m.key, m.value))
+        .collect();
+    Ok(DataInfo {
+        schema_version: values.get(SCHEMA_VERSION_KEY).and_then(|v| v.parse().ok()),
+        game_version: values.remove(GAME_VERSION_KEY),
+        language: values.remove(LANGUAGE_KEY),
+        imported_at: values.get(IMPORTED_AT_KEY).and_then(|v| v.parse().ok()),
+        tool_version: values.remove(TOOL_VERSION_KEY),
+    })
+}
+
+/// 写入一条元数据，已存在时覆盖
+pub async fn set_metadata(conn: &DatabaseConnection, key: &str, value: &str) -> Result<(), DbErr> {
+    Metadata::insert(metadata::ActiveModel {
+        key: Set(key.to_string()),
+        value: Set(value.to_string()),
+    })
+    .on_conflict(
+        OnConflict::column(metadata::Column::Key)
+            .update_column(metadata::Column::Value)
+            .to_owned(),
+    )
+    .exec(conn)
+    .await?;
+    Ok(())
+}
+
+async fn has_metadata_table(conn: &DatabaseConnection) -> Result<bool, DbErr> {
+    let backend = conn.get_database_backend();
+    let sql = match backend {
+        DatabaseBackend::Sqlite => {
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'Metadata'"
+        }
+        DatabaseBackend::MySql => {
+            "SELECT COUNT(*) FROM information_schema.TABLES \
+             WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'Metadata'"
+        }
+        DatabaseBackend::Postgres => {
+            "SELECT COUNT(*) FROM information_schema.tables \
+             WHERE table_schema = current_schema() AND table_name = 'Metadata'"
+        }
+    };
+    let count = conn
+        .query_one(Statement::from_string(backend, sql))
+        .await?
+        .map(|row| row.try_get_by_index::<i64>(0))
+        .transpose()?
+        .unwrap_or(0);
+    Ok(count > 0)
+}
@@ -22,6 +22,8 @@ pub struct Model {
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::wks_mission_unit_translations::Entity")]
+    WksMissionUnitTranslations,
     #[sea_orm(
         belongs_to = "super::wks_mission_recipe::Entity",
         from = "Column::RecipeId",
@@ -62,4 +64,10 @@ impl Related<super::wks_mission_recipe::Entity> for Entity {
     }
 }
 
+impl Related<super::wks_mission_unit_translations::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WksMissionUnitTranslations.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,41 @@
+//! 宇宙探索任务名称的多语言翻译，每种语言一行
+
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "WKSMissionUnitTranslations")]
+pub struct Model {
+    #[sea_orm(column_name = "Id", primary_key, auto_increment = false)]
+    pub id: u32,
+    /// 语言代码，见[`crate::LANGUAGES`]
+    #[sea_orm(
+        column_name = "Lang",
+        primary_key,
+        auto_increment = false,
+        column_type = "String(StringLen::N(8))"
+    )]
+    pub lang: String,
+    #[sea_orm(column_name = "Name", column_type = "custom(\"LONGTEXT\")")]
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::wks_mission_unit::Entity",
+        from = "Column::Id",
+        to = "super::wks_mission_unit::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    WksMissionUnit,
+}
+
+impl Related<super::wks_mission_unit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::WksMissionUnit.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
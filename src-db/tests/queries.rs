@@ -0,0 +1,1291 @@
+use std::collections::BTreeMap;
+
+use app_db::{
+    bom, class_job_categories, craft_type_translations, craft_types, gathering_items,
+    gathering_point_bases, gathering_points, gear, gear_params, gearset, gil_shop_items,
+    gil_shop_translations, item_action, item_food, item_food_effect, item_search_categories,
+    item_translations, items, materia, metadata, notebook_division_categories,
+    notebook_division_translations, notebook_divisions, place_name_translations, plan, queries,
+    recipe_level_tables, recipe_notebook_lists, recipes, search, secret_recipe_book_translations,
+    secret_recipe_books, shared_rotations, special_shop_items, special_shop_translations, version,
+    wks_mission_recipe, wks_mission_to_do, wks_mission_unit, wks_mission_unit_translations,
+};
+use app_libs::{
+    enhancer::Enhancer,
+    melding::{self, Stat, Stats},
+};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, Database, DatabaseConnection, EntityTrait, IntoActiveModel,
+    Schema,
+};
+
+async fn insert<M, A>(db: &DatabaseConnection, model: M)
+where
+    M: IntoActiveModel<A>,
+    A: ActiveModelTrait + sea_orm::ActiveModelBehavior + Send,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+{
+    model.into_active_model().insert(db).await.unwrap();
+}
+
+async fn create_table<E: EntityTrait>(db: &DatabaseConnection, entity: E) {
+    let backend = db.get_database_backend();
+    let stat = Schema::new(backend).create_table_from_entity(entity);
+    db.execute(backend.build(&stat)).await.unwrap();
+}
+
+fn item(id: u32, name: &str, search_category: Option<u32>, action: Option<u32>) -> items::Model {
+    items::Model {
+        id,
+        name: name.to_string(),
+        level: 100,
+        can_be_hq: true,
+        item_ui_category_id: None,
+        item_search_category_id: search_category,
+        item_action_id: action,
+        is_collectable: false,
+        always_collectable: false,
+    }
+}
+
+fn recipe(
+    id: u32,
+    craft_type_id: u32,
+    rlv: u32,
+    item: u32,
+    ingredients: &[(u32, u8)],
+) -> recipes::Model {
+    let ingredient = |i: usize| ingredients.get(i).copied().unwrap_or((0, 0));
+    recipes::Model {
+        id,
+        number: 0,
+        craft_type_id,
+        recipe_level_id: rlv,
+        item_result_id: item,
+        item_result_amount: 1,
+        material_quality_factor: 0,
+        difficulty_factor: 100,
+        quality_factor: 100,
+        durability_factor: 100,
+        required_quality: 0,
+        required_craftsmanship: 0,
+        required_control: 0,
+        can_hq: true,
+        is_expert: false,
+        collectables_metadata_key: 0,
+        collectables_metadata: None,
+        recipe_notebook_list: 0,
+        secret_recipe_book_id: None,
+        ingredient0: ingredient(0).0,
+        ingredient_amount0: ingredient(0).1,
+        ingredient1: ingredient(1).0,
+        ingredient_amount1: ingredient(1).1,
+        ingredient2: ingredient(2).0,
+        ingredient_amount2: ingredient(2).1,
+        ingredient3: ingredient(3).0,
+        ingredient_amount3: ingredient(3).1,
+        ingredient4: ingredient(4).0,
+        ingredient_amount4: ingredient(4).1,
+        ingredient5: ingredient(5).0,
+        ingredient_amount5: ingredient(5).1,
+        ingredient6: ingredient(6).0,
+        ingredient_amount6: ingredient(6).1,
+        ingredient7: ingredient(7).0,
+        ingredient_amount7: ingredient(7).1,
+    }
+}
+
+fn recipe_level(id: u32, class_job_level: u8) -> recipe_level_tables::Model {
+    recipe_level_tables::Model {
+        id,
+        class_job_level,
+        suggested_craftsmanship: 0,
+        difficulty: 1000,
+        quality: 2000,
+        progress_divider: 100,
+        quality_divider: 100,
+        progress_modifier: 100,
+        quality_modifier: 100,
+        durability: 80,
+        conditions_flag: 15,
+    }
+}
+
+fn item_action(id: u32, r#type: u16, item_food_id: u16) -> item_action::Model {
+    item_action::Model {
+        id,
+        r#type,
+        data1: 0,
+        data2: item_food_id,
+        data3: 1800,
+        data4: 0,
+        data5: 0,
+        data6: 0,
+        data7: 0,
+        data8: 0,
+        data9: 0,
+        data_hq1: 0,
+        data_hq2: 0,
+        data_hq3: 0,
+        data_hq4: 0,
+        data_hq5: 0,
+        data_hq6: 0,
+        data_hq7: 0,
+        data_hq8: 0,
+        data_hq9: 0,
+    }
+}
+
+fn item_food_effect(
+    id: u32,
+    item_food_id: u32,
+    base_param: u8,
+    (value, max): (i8, i16),
+    (value_hq, max_hq): (i8, i16),
+) -> item_food_effect::Model {
+    item_food_effect::Model {
+        id,
+        base_param,
+        value,
+        max,
+        value_hq,
+        max_hq,
+        item_food_id,
+    }
+}
+
+/// 建立一个内存中的SQLite数据库，包含两个职业、三个配方、一个宇宙探索任务和一种食物
+async fn fixture() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    create_table(&db, app_db::prelude::ItemUiCategories).await;
+    create_table(&db, app_db::prelude::ItemSearchCategories).await;
+    create_table(&db, app_db::prelude::CraftTypes).await;
+    create_table(&db, app_db::prelude::ItemAction).await;
+    create_table(&db, app_db::prelude::Items).await;
+    create_table(&db, app_db::prelude::RecipeLevelTables).await;
+    create_table(&db, app_db::prelude::ItemFood).await;
+    create_table(&db, app_db::prelude::ItemFoodEffect).await;
+    create_table(&db, app_db::prelude::CollectablesShopRefine).await;
+    create_table(&db, app_db::prelude::NotebookDivisionCategories).await;
+    create_table(&db, app_db::prelude::NotebookDivisions).await;
+    create_table(&db, app_db::prelude::RecipeNotebookLists).await;
+    create_table(&db, app_db::prelude::SecretRecipeBooks).await;
+    create_table(&db, app_db::prelude::Recipes).await;
+    create_table(&db, app_db::prelude::WksMissionToDo).await;
+    create_table(&db, app_db::prelude::WksMissionRecipe).await;
+    create_table(&db, app_db::prelude::WksMissionUnit).await;
+    create_table(&db, app_db::prelude::RecipeSearch).await;
+    create_table(&db, app_db::prelude::ItemTranslations).await;
+    create_table(&db, app_db::prelude::CraftTypeTranslations).await;
+    create_table(&db, app_db::prelude::WksMissionUnitTranslations).await;
+    create_table(&db, app_db::prelude::NotebookDivisionCategoryTranslations).await;
+    create_table(&db, app_db::prelude::NotebookDivisionTranslations).await;
+    create_table(&db, app_db::prelude::SecretRecipeBookTranslations).await;
+    create_table(&db, app_db::prelude::GilShopItems).await;
+    create_table(&db, app_db::prelude::SpecialShopItems).await;
+    create_table(&db, app_db::prelude::GatheringItems).await;
+    create_table(&db, app_db::prelude::GatheringPointBases).await;
+    create_table(&db, app_db::prelude::GatheringPoints).await;
+    create_table(&db, app_db::prelude::GilShopTranslations).await;
+    create_table(&db, app_db::prelude::SpecialShopTranslations).await;
+    create_table(&db, app_db::prelude::PlaceNameTranslations).await;
+    create_table(&db, app_db::prelude::ClassJobCategories).await;
+    create_table(&db, app_db::prelude::Gear).await;
+    create_table(&db, app_db::prelude::GearParams).await;
+    create_table(&db, app_db::prelude::Materia).await;
+
+    for (id, name) in [(0, "Carpenter"), (1, "Blacksmith")] {
+        let name = name.to_string();
+        insert(&db, craft_types::Model { id, name }).await;
+    }
+    for (id, name) in [
+        (queries::MEDICINE_SEARCH_ID, "Medicine"),
+        (queries::MEALS_SEARCH_ID, "Meals"),
+    ] {
+        let name = name.to_string();
+        insert(&db, item_search_categories::Model { id, name }).await;
+    }
+    insert(&db, item_action(1, 845, 10)).await;
+    insert(&db, item(1, "Iron Ingot", None, None)).await;
+    insert(&db, item(2, "Maple Lumber", None, None)).await;
+    insert(&db, item(3, "Iron Sword", None, None)).await;
+    insert(&db, item(4, "Maple Bow", None, None)).await;
+    insert(&db, item(5, "Mission Sword", None, None)).await;
+    insert(
+        &db,
+        item(6, "Crafter's Stew", Some(queries::MEALS_SEARCH_ID), Some(1)),
+    )
+    .await;
+    insert(&db, recipe_level(1, 50)).await;
+    insert(&db, recipe_level(2, 90)).await;
+    let notebook_list = recipe_notebook_lists::Model {
+        id: 0,
+        notebook_division_id: None,
+    };
+    insert(&db, notebook_list).await;
+    insert(&db, recipe(30, 1, 2, 5, &[(1, 2)])).await;
+    insert(&db, recipe(20, 0, 1, 4, &[(2, 3)])).await;
+    insert(&db, recipe(10, 1, 1, 3, &[(1, 2), (2, 1), (1, 3)])).await;
+
+    insert(&db, item_food::Model { id: 10 }).await;
+    insert(&db, item_food_effect(1, 10, 70, (4, 50), (5, 60))).await;
+    insert(&db, item_food_effect(2, 10, 11, (6, 20), (8, 25))).await;
+    // 非制作属性的加成不应出现在结果中
+    insert(&db, item_food_effect(3, 10, 12, (10, 100), (12, 120))).await;
+
+    insert(
+        &db,
+        wks_mission_to_do::Model {
+            id: 1,
+            temporary_action: 100,
+            temporary_action_count: 3,
+        },
+    )
+    .await;
+    insert(
+        &db,
+        wks_mission_recipe::Model {
+            id: 1,
+            recipe0_id: Some(30),
+            recipe1_id: None,
+            recipe2_id: None,
+            recipe3_id: None,
+            recipe4_id: None,
+            is_expert: false,
+        },
+    )
+    .await;
+    insert(
+        &db,
+        wks_mission_unit::Model {
+            id: 1,
+            name: "Cosmic Mission".to_string(),
+            recipe_id: Some(1),
+            to_do0_id: Some(1),
+            to_do1_id: None,
+            to_do2_id: None,
+        },
+    )
+    .await;
+    db
+}
+
+#[tokio::test]
+async fn recipe_table_is_sorted_by_id() {
+    let db = fixture().await;
+    let filter = queries::RecipeFilter::default();
+    let (data, p) = queries::recipe_table(&db, 0, "%", &filter, None)
+        .await
+        .unwrap();
+    assert_eq!(p, 1);
+    let ids: Vec<_> = data.iter().map(|r| r.id).collect();
+    assert_eq!(ids, [10, 20, 30]);
+    assert_eq!(data[0].item_name, "Iron Sword");
+    assert_eq!(data[0].job, "Blacksmith");
+}
+
+#[tokio::test]
+async fn recipe_table_filters() {
+    let db = fixture().await;
+    let filter = queries::RecipeFilter {
+        craft_type_id: Some(1),
+        job_level_max: Some(50),
+        ..Default::default()
+    };
+    let (data, _) = queries::recipe_table(&db, 0, "%Sword%", &filter, None)
+        .await
+        .unwrap();
+    let ids: Vec<_> = data.iter().map(|r| r.id).collect();
+    assert_eq!(ids, [10]);
+
+    let filter = queries::RecipeFilter {
+        recipe_level: Some(2),
+        ..Default::default()
+    };
+    let (data, _) = queries::recipe_table(&db, 0, "%", &filter, None)
+        .await
+        .unwrap();
+    let ids: Vec<_> = data.iter().map(|r| r.id).collect();
+    assert_eq!(ids, [30]);
+}
+
+#[tokio::test]
+async fn recipe_table_matches_mission_name() {
+    let db = fixture().await;
+    let filter = queries::RecipeFilter::default();
+    let (data, _) = queries::recipe_table(&db, 0, "%Cosmic%", &filter, None)
+        .await
+        .unwrap();
+    let ids: Vec<_> = data.iter().map(|r| r.id).collect();
+    assert_eq!(ids, [30]);
+}
+
+#[tokio::test]
+async fn recipe_info() {
+    let db = fixture().await;
+    let info = queries::recipe_info(&db, 20, None).await.unwrap().unwrap();
+    assert_eq!(info.item_id, 4);
+    assert_eq!(info.job, "Carpenter");
+    assert_eq!(info.rlv, 1);
+    assert!(
+        queries::recipe_info(&db, 404, None)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+/// 一本秘籍与一页制作笔记，配方40需要阅读秘籍
+async fn insert_notebooks(db: &DatabaseConnection) {
+    let category = notebook_division_categories::Model {
+        id: 1,
+        name: "Dawntrail".to_string(),
+    };
+    insert(db, category).await;
+    let division = notebook_divisions::Model {
+        id: 5,
+        name: "Lv. 91-95".to_string(),
+        notebook_division_category_id: Some(1),
+        craft_opening_level: 91,
+    };
+    insert(db, division).await;
+    let notebook_list = recipe_notebook_lists::Model {
+        id: 45,
+        notebook_division_id: Some(5),
+    };
+    insert(db, notebook_list).await;
+    let book = secret_recipe_books::Model {
+        id: 7,
+        item_id: 1,
+        name: "Master Blacksmith I".to_string(),
+    };
+    insert(db, book).await;
+    let mut model = recipe(40, 1, 2, 3, &[(1, 1)]);
+    model.recipe_notebook_list = 45;
+    model.secret_recipe_book_id = Some(7);
+    insert(db, model).await;
+}
+
+#[tokio::test]
+async fn recipe_notebooks() {
+    let db = fixture().await;
+    insert_notebooks(&db).await;
+    let info = queries::recipe_info(&db, 40, None).await.unwrap().unwrap();
+    assert_eq!(info.notebook_division_id, Some(5));
+    assert_eq!(info.notebook_division.as_deref(), Some("Lv. 91-95"));
+    assert_eq!(info.secret_recipe_book_id, Some(7));
+    assert_eq!(
+        info.secret_recipe_book.as_deref(),
+        Some("Master Blacksmith I")
+    );
+    let info = queries::recipe_info(&db, 10, None).await.unwrap().unwrap();
+    assert_eq!(info.notebook_division, None);
+    assert_eq!(info.secret_recipe_book, None);
+
+    for filter in [
+        queries::RecipeFilter {
+            expansion: Some(1),
+            ..Default::default()
+        },
+        queries::RecipeFilter {
+            notebook_division_id: Some(5),
+            ..Default::default()
+        },
+        queries::RecipeFilter {
+            secret_recipe_book_id: Some(7),
+            ..Default::default()
+        },
+    ] {
+        let (data, _) = queries::recipe_table(&db, 0, "%Sword%", &filter, None)
+            .await
+            .unwrap();
+        let ids: Vec<_> = data.iter().map(|r| r.id).collect();
+        assert_eq!(ids, [40]);
+    }
+
+    let model = secret_recipe_book_translations::Model {
+        id: 7,
+        lang: "ja".to_string(),
+        name: "上級鍛冶秘伝書:第1巻".to_string(),
+    };
+    insert(&db, model).await;
+    let model = notebook_division_translations::Model {
+        id: 5,
+        lang: "ja".to_string(),
+        name: "Lv91～95".to_string(),
+    };
+    insert(&db, model).await;
+    let info = queries::recipe_info(&db, 40, Some("ja"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(info.notebook_division.as_deref(), Some("Lv91～95"));
+    assert_eq!(
+        info.secret_recipe_book.as_deref(),
+        Some("上級鍛冶秘伝書:第1巻")
+    );
+
+    let notebooks = queries::recipe_notebooks(&db, Some("ja")).await.unwrap();
+    let names: Vec<_> = notebooks
+        .categories
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(names, ["Dawntrail"]);
+    assert_eq!(notebooks.divisions[0].name, "Lv91～95");
+    assert_eq!(
+        notebooks.divisions[0].notebook_division_category_id,
+        Some(1)
+    );
+    assert_eq!(notebooks.secret_recipe_books[0].item_id, 1);
+}
+
+#[tokio::test]
+async fn recipe_ingredients_are_merged() {
+    let db = fixture().await;
+    let ingredients = queries::recipe_ingredients(&db, 10).await.unwrap().unwrap();
+    assert_eq!(ingredients, [(1, 5), (2, 1)]);
+    assert!(
+        queries::recipe_ingredients(&db, 404)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn recipe_level_table_by_job_level() {
+    let db = fixture().await;
+    let rlt = queries::recipe_level_table_by_job_level(&db, 90)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(rlt.id, 2);
+    assert!(
+        queries::recipe_level_table_by_job_level(&db, 1)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn craft_types_are_sorted() {
+    let db = fixture().await;
+    let names: Vec<_> = queries::craft_types(&db, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, ["Carpenter", "Blacksmith"]);
+}
+
+#[tokio::test]
+async fn meals_table() {
+    let db = fixture().await;
+    let meals = queries::meals_table(&db, None).await.unwrap();
+    assert_eq!(
+        meals,
+        [
+            Enhancer {
+                name: "Crafter's Stew".to_string(),
+                level: 100,
+                is_hq: false,
+                cm: Some(4),
+                cm_max: Some(50),
+                cp: Some(6),
+                cp_max: Some(20),
+                ..Default::default()
+            },
+            Enhancer {
+                name: "Crafter's Stew".to_string(),
+                level: 100,
+                is_hq: true,
+                cm: Some(5),
+                cm_max: Some(60),
+                cp: Some(8),
+                cp_max: Some(25),
+                ..Default::default()
+            },
+        ]
+    );
+    assert!(queries::medicine_table(&db, None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn temporary_action_info() {
+    let db = fixture().await;
+    let info = queries::temporary_action_info(&db, 30).await.unwrap();
+    assert_eq!(
+        info,
+        Some(queries::TemporaryActionInfo {
+            action: 100,
+            count: 3
+        })
+    );
+    assert!(
+        queries::temporary_action_info(&db, 10)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+async fn search_ids(
+    db: &DatabaseConnection,
+    query: &str,
+    filter: &queries::RecipeFilter,
+) -> Vec<u32> {
+    let (data, _) = queries::search_recipes(db, 0, query, filter, None)
+        .await
+        .unwrap();
+    data.iter().map(|r| r.id).collect()
+}
+
+#[tokio::test]
+async fn search_recipes_by_prefix() {
+    let db = fixture().await;
+    search::rebuild_index(&db, "en", |_, _| Vec::new())
+        .await
+        .unwrap();
+    let filter = queries::RecipeFilter::default();
+    assert_eq!(search_ids(&db, "iron sw", &filter).await, [10]);
+    assert_eq!(search_ids(&db, "ＩＲＯＮ", &filter).await, [10]);
+    assert_eq!(search_ids(&db, "cosm", &filter).await, [30]);
+    // 较短的名称相关度更高
+    assert_eq!(search_ids(&db, "sword", &filter).await, [10, 30]);
+    assert_eq!(search_ids(&db, "", &filter).await, [10, 20, 30]);
+
+    let filter = queries::RecipeFilter {
+        craft_type_id: Some(1),
+        job_level_max: Some(50),
+        ..Default::default()
+    };
+    assert_eq!(search_ids(&db, "sword", &filter).await, [10]);
+}
+
+#[tokio::test]
+async fn search_recipes_fuzzy_fallback() {
+    let db = fixture().await;
+    search::rebuild_index(&db, "en", |_, _| Vec::new())
+        .await
+        .unwrap();
+    let filter = queries::RecipeFilter::default();
+    assert_eq!(search_ids(&db, "word", &filter).await, [10, 30]);
+    assert!(search_ids(&db, "axe", &filter).await.is_empty());
+}
+
+#[tokio::test]
+async fn search_recipes_kana_and_extra_keys() {
+    let db = fixture().await;
+    insert(&db, item(7, "ミスリルインゴット", None, None)).await;
+    insert(&db, recipe(40, 1, 1, 7, &[(1, 1)])).await;
+    insert_translations(&db).await;
+    search::rebuild_index(&db, "en", |name, lang| match (name, lang) {
+        ("Maple Bow", "en") => vec!["feng mu gong".to_string(), "fmg".to_string()],
+        ("アイアンソード", "ja") => vec!["tetsuken".to_string()],
+        _ => Vec::new(),
+    })
+    .await
+    .unwrap();
+    let filter = queries::RecipeFilter::default();
+    assert_eq!(search_ids(&db, "みすりる", &filter).await, [40]);
+    assert_eq!(search_ids(&db, "ｲﾝｺﾞｯﾄ", &filter).await, [40]);
+    assert_eq!(search_ids(&db, "feng mu", &filter).await, [20]);
+    assert_eq!(search_ids(&db, "fmg", &filter).await, [20]);
+    // 翻译的名称以其自身的语言代码请求额外搜索词
+    assert_eq!(search_ids(&db, "tetsuken", &filter).await, [10]);
+}
+
+async fn insert_translations(db: &DatabaseConnection) {
+    for (id, lang, name) in [(3, "ja", "アイアンソード"), (3, "de", "Eisenschwert")] {
+        let model = item_translations::Model {
+            id,
+            lang: lang.to_string(),
+            name: name.to_string(),
+        };
+        insert(db, model).await;
+    }
+    let model = craft_type_translations::Model {
+        id: 1,
+        lang: "ja".to_string(),
+        name: "鍛冶師".to_string(),
+    };
+    insert(db, model).await;
+    let model = wks_mission_unit_translations::Model {
+        id: 1,
+        lang: "ja".to_string(),
+        name: "コスモミッション".to_string(),
+    };
+    insert(db, model).await;
+}
+
+#[tokio::test]
+async fn translated_names() {
+    let db = fixture().await;
+    insert_translations(&db).await;
+    let filter = queries::RecipeFilter::default();
+    let (data, _) = queries::recipe_table(&db, 0, "%", &filter, Some("ja"))
+        .await
+        .unwrap();
+    let names: Vec<_> = data
+        .iter()
+        .map(|r| (r.item_name.as_str(), r.job.as_str()))
+        .collect();
+    // 没有翻译的名称使用默认语言
+    assert_eq!(
+        names,
+        [
+            ("アイアンソード", "鍛冶師"),
+            ("Maple Bow", "Carpenter"),
+            ("Mission Sword", "鍛冶師")
+        ]
+    );
+
+    let (data, _) = queries::recipe_table(&db, 0, "%コスモ%", &filter, Some("ja"))
+        .await
+        .unwrap();
+    let ids: Vec<_> = data.iter().map(|r| r.id).collect();
+    assert_eq!(ids, [30]);
+
+    let item = queries::item_info(&db, 3, Some("de"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.name, "Eisenschwert");
+    assert_eq!(item.level, 100);
+    let item = queries::item_info(&db, 3, None).await.unwrap().unwrap();
+    assert_eq!(item.name, "Iron Sword");
+
+    let names: Vec<_> = queries::craft_types(&db, Some("ja"))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, ["Carpenter", "鍛冶師"]);
+}
+
+#[tokio::test]
+async fn search_recipes_in_any_language() {
+    let db = fixture().await;
+    insert_translations(&db).await;
+    search::rebuild_index(&db, "en", |_, _| Vec::new())
+        .await
+        .unwrap();
+    let filter = queries::RecipeFilter::default();
+    assert_eq!(search_ids(&db, "eisen", &filter).await, [10]);
+    assert_eq!(search_ids(&db, "そーど", &filter).await, [10]);
+    assert_eq!(search_ids(&db, "こすも", &filter).await, [30]);
+}
+
+/// 铁矿石 -> 铁锭（每次产出5个） -> 铁剑、任务剑
+async fn insert_ore_recipe(db: &DatabaseConnection) {
+    insert(db, item(7, "Iron Ore", None, None)).await;
+    let mut ingot = recipe(40, 1, 1, 1, &[(7, 3)]);
+    ingot.item_result_amount = 5;
+    insert(db, ingot).await;
+}
+
+#[tokio::test]
+async fn resolve_bom() {
+    let db = fixture().await;
+    insert_ore_recipe(&db).await;
+    let request = bom::BomRequest {
+        targets: vec![(3, 2), (5, 1)],
+        holdings: [(2, 1)].into(),
+        include_crystals: true,
+        ..Default::default()
+    };
+    let bom = bom::resolve_bom(&db, &request, None).await.unwrap();
+
+    let summary: Vec<_> = bom
+        .items
+        .iter()
+        .map(|i| {
+            (
+                i.item_id, i.required, i.holding, i.crafts, i.leftover, i.depth,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (3, 2, 0, 2, 0, 0),
+            (5, 1, 0, 1, 0, 0),
+            (2, 2, 1, 0, 0, 1),
+            (1, 12, 0, 3, 3, 1),
+            (7, 9, 0, 0, 0, 2),
+        ]
+    );
+    assert_eq!(bom.items[3].required_by, [(3, 10), (5, 2)]);
+    assert_eq!(bom.items[3].recipe_id, Some(40));
+
+    let shopping: Vec<_> = bom
+        .shopping_list
+        .iter()
+        .map(|i| (i.item_name.as_str(), i.amount))
+        .collect();
+    assert_eq!(shopping, [("Maple Lumber", 1), ("Iron Ore", 9)]);
+
+    // 素材树不考虑持有的物品
+    let sword = &bom.tree[0];
+    assert_eq!((sword.item_id, sword.amount), (3, 2));
+    let children: Vec<_> = sword
+        .children
+        .iter()
+        .map(|c| (c.item_id, c.amount))
+        .collect();
+    assert_eq!(children, [(1, 10), (2, 2)]);
+    assert_eq!(sword.children[0].children[0].amount, 6);
+}
+
+#[tokio::test]
+async fn resolve_bom_errors() {
+    let db = fixture().await;
+    insert_ore_recipe(&db).await;
+    let request = bom::BomRequest {
+        targets: vec![(3, 1)],
+        recipes: [(3, 20)].into(),
+        include_crystals: true,
+        ..Default::default()
+    };
+    let result = bom::resolve_bom(&db, &request, None).await;
+    assert!(matches!(
+        result,
+        Err(bom::BomError::InvalidRecipe {
+            item_id: 3,
+            recipe_id: 20
+        })
+    ));
+
+    insert(&db, recipe(50, 1, 1, 7, &[(1, 1)])).await;
+    let request = bom::BomRequest {
+        targets: vec![(3, 1)],
+        include_crystals: true,
+        ..Default::default()
+    };
+    let result = bom::resolve_bom(&db, &request, None).await;
+    assert!(matches!(result, Err(bom::BomError::Cycle(items)) if items == [1, 7]));
+}
+
+#[tokio::test]
+async fn resolve_bom_overflow() {
+    let db = fixture().await;
+    insert_ore_recipe(&db).await;
+    // 合并同一物品的目标数量时溢出
+    let request = bom::BomRequest {
+        targets: vec![(5, u32::MAX), (5, 1)],
+        include_crystals: true,
+        ..Default::default()
+    };
+    let result = bom::resolve_bom(&db, &request, None).await;
+    assert!(matches!(result, Err(bom::BomError::Overflow(5))));
+
+    // 制作次数乘以素材数量时溢出
+    let request = bom::BomRequest {
+        targets: vec![(3, u32::MAX)],
+        include_crystals: true,
+        ..Default::default()
+    };
+    let result = bom::resolve_bom(&db, &request, None).await;
+    assert!(matches!(result, Err(bom::BomError::Overflow(_))));
+    let result = plan::crafting_plan(&db, &request, None).await;
+    assert!(matches!(result, Err(bom::BomError::Overflow(_))));
+}
+
+#[tokio::test]
+async fn resolve_bom_skips_crystals() {
+    let db = fixture().await;
+    let request = bom::BomRequest {
+        targets: vec![(3, 1)],
+        ..Default::default()
+    };
+    let bom = bom::resolve_bom(&db, &request, None).await.unwrap();
+    assert!(bom.tree[0].children.is_empty());
+    assert!(bom.shopping_list.is_empty());
+}
+
+#[tokio::test]
+async fn crafting_plan() {
+    let db = fixture().await;
+    insert_ore_recipe(&db).await;
+    let request = bom::BomRequest {
+        targets: vec![(3, 2), (5, 1), (4, 1)],
+        holdings: [(2, 1)].into(),
+        include_crystals: true,
+        ..Default::default()
+    };
+    let plan = plan::crafting_plan(&db, &request, None).await.unwrap();
+
+    let groups: Vec<_> = plan
+        .groups
+        .iter()
+        .map(|g| {
+            let steps: Vec<_> = g
+                .steps
+                .iter()
+                .map(|s| (s.item_id, s.crafts, s.amount, s.leftover))
+                .collect();
+            (g.stage, g.craft_type_name.as_str(), steps)
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (0, "Carpenter", vec![(4, 1, 1, 0)]),
+            (0, "Blacksmith", vec![(1, 3, 15, 3)]),
+            (1, "Blacksmith", vec![(5, 1, 1, 0), (3, 2, 2, 0)]),
+        ]
+    );
+    assert_eq!(plan.groups[2].steps[1].ingredients, [(1, 10), (2, 2)]);
+
+    let jobs: Vec<_> = plan
+        .jobs
+        .iter()
+        .map(|j| (j.craft_type_id, j.recipes, j.crafts))
+        .collect();
+    assert_eq!(jobs, [(0, 1, 1), (1, 3, 6)]);
+    let shopping: Vec<_> = plan
+        .shopping_list
+        .iter()
+        .map(|i| (i.item_id, i.amount))
+        .collect();
+    assert_eq!(shopping, [(2, 4), (7, 9)]);
+}
+
+#[tokio::test]
+async fn item_sources() {
+    let db = fixture().await;
+    insert_ore_recipe(&db).await;
+    for (shop_id, subrow_id, item_id) in [(100, 0, 7), (101, 3, 7), (101, 4, 2)] {
+        let model = gil_shop_items::Model {
+            shop_id,
+            subrow_id,
+            shop_name: format!("Shop {shop_id}"),
+            item_id,
+            price: 18,
+        };
+        insert(&db, model).await;
+    }
+    insert(
+        &db,
+        special_shop_items::Model {
+            shop_id: 200,
+            entry: 5,
+            slot: 0,
+            shop_name: "Scrip Exchange".to_string(),
+            item_id: 7,
+            count: 10,
+            cost_item0_id: Some(28),
+            cost_count0: 50,
+            cost_item1_id: None,
+            cost_count1: 0,
+            cost_item2_id: None,
+            cost_count2: 0,
+        },
+    )
+    .await;
+    let gathering_item = gathering_items::Model {
+        id: 30,
+        item_id: 7,
+        gathering_item_level: 10,
+    };
+    insert(&db, gathering_item).await;
+    insert(
+        &db,
+        gathering_point_bases::Model {
+            id: 40,
+            gathering_type: 0,
+            gathering_level: 10,
+            is_limited: false,
+            item0_id: Some(31),
+            item1_id: Some(30),
+            item2_id: None,
+            item3_id: None,
+            item4_id: None,
+            item5_id: None,
+            item6_id: None,
+            item7_id: None,
+        },
+    )
+    .await;
+    for (id, place_name_id, place_name) in [
+        (1, 50, "Spineless Basin"),
+        (2, 51, "Cedarwood"),
+        (3, 51, "Cedarwood"),
+    ] {
+        let point = gathering_points::Model {
+            id,
+            gathering_point_base_id: 40,
+            territory_type_id: 134,
+            place_name_id,
+            place_name: place_name.to_string(),
+        };
+        insert(&db, point).await;
+    }
+
+    let sources = queries::item_sources(&db, &[7, 1, 8], None).await.unwrap();
+    let ids: Vec<_> = sources.iter().map(|s| s.item_id).collect();
+    assert_eq!(ids, [7, 1, 8]);
+
+    let ore = &sources[0];
+    assert!(ore.recipes.is_empty());
+    let shops: Vec<_> = ore.gil_shops.iter().map(|s| (s.shop_id, s.price)).collect();
+    assert_eq!(shops, [(100, 18), (101, 18)]);
+    assert_eq!(ore.special_shops[0].costs, [(28, 50)]);
+    assert_eq!(ore.gathering.len(), 1);
+    assert_eq!(ore.gathering[0].gathering_item_level, 10);
+    assert_eq!(ore.gathering[0].places, ["Cedarwood", "Spineless Basin"]);
+
+    let ingot = &sources[1];
+    assert_eq!(ingot.recipes, [40]);
+    assert!(ingot.gil_shops.is_empty() && ingot.gathering.is_empty());
+    assert_eq!(
+        sources[2],
+        queries::ItemSources {
+            item_id: 8,
+            ..Default::default()
+        }
+    );
+
+    let model = gil_shop_translations::Model {
+        id: 100,
+        lang: "ja".to_string(),
+        name: "よろず屋".to_string(),
+    };
+    insert(&db, model).await;
+    let model = special_shop_translations::Model {
+        id: 200,
+        lang: "ja".to_string(),
+        name: "スクリップ取引".to_string(),
+    };
+    insert(&db, model).await;
+    let model = place_name_translations::Model {
+        id: 51,
+        lang: "ja".to_string(),
+        name: "シダーウッド".to_string(),
+    };
+    insert(&db, model).await;
+    let sources = queries::item_sources(&db, &[7], Some("ja")).await.unwrap();
+    let ore = &sources[0];
+    let shops: Vec<_> = ore.gil_shops.iter().map(|s| s.shop_name.as_str()).collect();
+    // 没有翻译的商店使用默认语言的名称
+    assert_eq!(shops, ["よろず屋", "Shop 101"]);
+    assert_eq!(ore.special_shops[0].shop_name, "スクリップ取引");
+    assert_eq!(ore.gathering[0].places, ["Spineless Basin", "シダーウッド"]);
+}
+
+#[tokio::test]
+async fn recipe_material_quality() {
+    let db = fixture().await;
+    let mut sword = recipe(50, 1, 1, 3, &[(1, 2), (2, 1), (20, 1)]);
+    sword.material_quality_factor = 75;
+    sword.quality_factor = 80;
+    insert(&db, sword).await;
+    insert(&db, item(20, "Wind Crystal", None, None)).await;
+
+    let info = queries::recipe_material_quality(&db, 50)
+        .await
+        .unwrap()
+        .unwrap();
+    // 配方等级1的品质为2000
+    assert_eq!((info.quality, info.material_quality_factor), (1600, 75));
+    let ingredients: Vec<_> = info
+        .ingredients
+        .iter()
+        .map(|i| (i.item_id, i.level, i.amount))
+        .collect();
+    assert_eq!(ingredients, [(1, 100, 2), (2, 100, 1), (20, 100, 1)]);
+    // 超出需要数量的HQ素材被忽略
+    let hq_amounts = BTreeMap::from([(1, 2)]);
+    assert_eq!(info.initial_quality(&hq_amounts), 600);
+    let hq_amounts = BTreeMap::from([(1, 5), (2, 1), (20, 1)]);
+    let quality = queries::initial_quality(&db, 50, &hq_amounts)
+        .await
+        .unwrap();
+    assert_eq!(quality, Some(1200));
+    assert!(
+        queries::recipe_material_quality(&db, 99)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn schema_version_check() {
+    let db = fixture().await;
+    assert_eq!(version::schema_version(&db).await.unwrap(), None);
+    assert!(matches!(
+        version::check_schema(&db).await,
+        Err(version::VersionError::Unversioned)
+    ));
+
+    create_table(&db, metadata::Entity).await;
+    version::set_metadata(&db, version::SCHEMA_VERSION_KEY, "0")
+        .await
+        .unwrap();
+    assert!(matches!(
+        version::check_schema(&db).await,
+        Err(version::VersionError::Incompatible { found: 0, .. })
+    ));
+
+    let current = version::SCHEMA_VERSION.to_string();
+    version::set_metadata(&db, version::SCHEMA_VERSION_KEY, &current)
+        .await
+        .unwrap();
+    version::check_schema(&db).await.unwrap();
+}
+
+#[tokio::test]
+async fn data_info() {
+    let db = fixture().await;
+    assert_eq!(
+        version::data_info(&db).await.unwrap(),
+        version::DataInfo::default()
+    );
+
+    create_table(&db, metadata::Entity).await;
+    for (key, value) in [
+        (version::SCHEMA_VERSION_KEY, "1"),
+        (version::GAME_VERSION_KEY, "2024.11.06.0000.0000"),
+        (version::LANGUAGE_KEY, "zh-CN"),
+        (version::IMPORTED_AT_KEY, "1760000000"),
+    ] {
+        version::set_metadata(&db, key, value).await.unwrap();
+    }
+    assert_eq!(
+        version::data_info(&db).await.unwrap(),
+        version::DataInfo {
+            schema_version: Some(1),
+            game_version: Some("2024.11.06.0000.0000".into()),
+            language: Some("zh-CN".into()),
+            imported_at: Some(1760000000),
+            tool_version: None,
+        }
+    );
+}
+
+async fn insert_gear(db: &DatabaseConnection) {
+    let category = |id: u32, carpenter: bool| class_job_categories::Model {
+        id,
+        name: format!("Category {id}"),
+        carpenter,
+        blacksmith: true,
+        armorer: true,
+        goldsmith: true,
+        leatherworker: true,
+        weaver: true,
+        alchemist: true,
+        culinarian: true,
+    };
+    insert(db, category(1, true)).await;
+    insert(db, category(2, false)).await;
+    for (id, name) in [
+        (500, "Saw"),
+        (501, "Hat"),
+        (600, "Craftsman's Materia"),
+        (601, "Craftsman's Competence Materia"),
+        (602, "Gatherer's Guerdon Materia"),
+    ] {
+        insert(db, item(id, name, None, None)).await;
+    }
+    let gear = |id: u32, category: u32, slots: u8, advanced: bool| gear::Model {
+        id,
+        equip_slot_category: 1,
+        level_equip: 100,
+        class_job_category_id: category,
+        materia_slot_count: slots,
+        is_advanced_melding_permitted: advanced,
+        craftsmanship_max: 150,
+        control_max: 120,
+        craft_points_max: 20,
+    };
+    insert(db, gear(500, 1, 2, true)).await;
+    insert(db, gear(501, 2, 1, false)).await;
+    for (item_id, base_param, value, value_hq) in [
+        (500, gearset::CRAFTSMANSHIP, 100, 110),
+        (500, gearset::CONTROL, 50, 55),
+        (501, gearset::CRAFT_POINTS, 5, 7),
+    ] {
+        let model = gear_params::Model {
+            item_id,
+            base_param,
+            value,
+            value_hq,
+        };
+        insert(db, model).await;
+    }
+    for (item_id, base_param, value) in [
+        (600, gearset::CRAFTSMANSHIP, 36),
+        (601, gearset::CRAFT_POINTS, 9),
+        (602, 10, 9),
+    ] {
+        let model = materia::Model {
+            item_id,
+            materia_id: item_id - 600,
+            grade: 11,
+            base_param,
+            value,
+        };
+        insert(db, model).await;
+    }
+}
+
+fn equipped(item_id: u32, hq: bool, materia: &[u32]) -> gearset::EquippedGear {
+    gearset::EquippedGear {
+        item_id,
+        hq,
+        materia: materia.to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn gear_attributes() {
+    let db = fixture().await;
+    insert_gear(&db).await;
+    // 110 + 36 * 2 超过上限150，7 + 9 未超过上限20
+    let result = gearset::gear_attributes(
+        &db,
+        &[
+            equipped(500, true, &[600, 600, 601]),
+            equipped(501, true, &[601]),
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        result,
+        gearset::GearAttributes {
+            craftsmanship: 150,
+            control: 55,
+            craft_points: 9 + 16,
+            compatible_jobs: [
+                "blacksmith",
+                "armorer",
+                "goldsmith",
+                "leatherworker",
+                "weaver",
+                "alchemist",
+                "culinarian"
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    );
+
+    let result = gearset::gear_attributes(&db, &[equipped(501, false, &[601, 601])]).await;
+    assert!(matches!(
+        result,
+        Err(gearset::GearsetError::TooManyMateria {
+            item_id: 501,
+            count: 2,
+            max: 1
+        })
+    ));
+    let result = gearset::gear_attributes(&db, &[equipped(600, false, &[])]).await;
+    assert!(matches!(result, Err(gearset::GearsetError::NotGear(600))));
+}
+
+#[tokio::test]
+async fn meld_inputs() {
+    let db = fixture().await;
+    insert_gear(&db).await;
+
+    // 500有2个普通孔且允许禁断镶嵌，已镶嵌3个时其中1个是禁断孔
+    let pieces = gearset::gear_pieces(
+        &db,
+        &[
+            equipped(500, true, &[600, 600, 601]),
+            equipped(500, false, &[600]),
+            equipped(501, false, &[]),
+        ],
+    )
+    .await
+    .unwrap();
+    let summary: Vec<_> = pieces
+        .iter()
+        .map(|p| (p.stats, p.free_slots, p.free_overmeld_slots, p.overmelded))
+        .collect();
+    let stats = |craftsmanship, control, craft_points| Stats {
+        craftsmanship,
+        control,
+        craft_points,
+    };
+    assert_eq!(
+        summary,
+        [
+            (stats(150, 55, 9), 0, 2, 1),
+            (stats(136, 50, 0), 1, 3, 0),
+            (stats(0, 0, 5), 1, 0, 0),
+        ]
+    );
+    assert_eq!(pieces[0].caps, stats(150, 120, 20));
+
+    let offer = |item_id: u32, cost: u32| gearset::MateriaOffer {
+        item_id,
+        cost,
+        overmeld_rates: vec![17, 10, 7, 5],
+    };
+    let materia = gearset::materia_grades(&db, &[offer(601, 300), offer(600, 200)])
+        .await
+        .unwrap();
+    let summary: Vec<_> = materia
+        .iter()
+        .map(|m| (m.item_id, m.stat, m.value, m.cost))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (601, Stat::CraftPoints, 9, 300),
+            (600, Stat::Craftsmanship, 36, 200)
+        ]
+    );
+    assert_eq!(materia[0].overmeld_rates, [17, 10, 7, 5]);
+
+    let result = gearset::materia_grades(&db, &[offer(602, 100)]).await;
+    assert!(matches!(
+        result,
+        Err(gearset::GearsetError::NotCrafterMateria(602))
+    ));
+    let result = gearset::materia_grades(&db, &[offer(500, 100)]).await;
+    assert!(matches!(
+        result,
+        Err(gearset::GearsetError::NotMateria(500))
+    ));
+
+    // 镶嵌后的属性与装备上限共同决定能否达到目标
+    let pieces = gearset::gear_pieces(&db, &[equipped(501, true, &[])])
+        .await
+        .unwrap();
+    let current = stats(0, 0, 7);
+    let plan = melding::optimize(current, stats(0, 0, 16), &pieces, &materia).unwrap();
+    assert_eq!(plan.melds, [vec![601]]);
+    assert_eq!(plan.stats, stats(0, 0, 16));
+    assert!(melding::optimize(current, stats(0, 0, 30), &pieces, &materia).is_none());
+}
+
+#[tokio::test]
+async fn shared_rotations_table() {
+    let db = Database::connect("sqlite::memory:").await.unwrap();
+    shared_rotations::create_table(&db).await.unwrap();
+    let rotation = shared_rotations::Model {
+        id: "a1B2c3D4".to_string(),
+        recipe_id: 35000,
+        attributes: sea_orm::JsonValue::Null,
+        actions: sea_orm::JsonValue::from(vec!["basic_synthesis"]),
+        notes: String::new(),
+        created_at: 1_700_000_000,
+    };
+    insert(&db, rotation.clone()).await;
+
+    // 服务端每次启动都会调用，表已存在时不能清空已有的分享
+    shared_rotations::create_table(&db).await.unwrap();
+    let found = shared_rotations::Entity::find_by_id("a1B2c3D4")
+        .one(&db)
+        .await
+        .unwrap();
+    assert_eq!(found, Some(rotation));
+}
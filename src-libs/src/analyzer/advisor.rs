@@ -0,0 +1,182 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::cmp::Ordering;
+
+use crate::{simulate_one_step, solver::Solver};
+use ffxiv_crafting::{Actions, Status};
+use rand::{Rng, rng};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct Advice {
+    pub action: Actions,
+    // 是否为求解器在当前状态下直接给出的技能
+    pub recommended: bool,
+    // 释放该技能后，求解器给出的后续技能序列
+    pub follow_up: Vec<Actions>,
+    // 模拟结束时的期望品质
+    pub expected_quality: f64,
+    // 期望HQ率（百分比），进展未推满的模拟按0计算
+    pub high_quality_rate: f64,
+    // 进展未推满或未完成制作的模拟占比
+    pub fail_rate: f64,
+}
+
+/// 制作过程中可能被推荐的技能，包含依赖球色的技能
+const CANDIDATES: [Actions; 36] = [
+    Actions::BasicSynthesis,
+    Actions::BasicTouch,
+    Actions::MastersMend,
+    Actions::HastyTouch,
+    Actions::RapidSynthesis,
+    Actions::Observe,
+    Actions::TricksOfTheTrade,
+    Actions::WasteNot,
+    Actions::Veneration,
+    Actions::StandardTouch,
+    Actions::GreatStrides,
+    Actions::Innovation,
+    Actions::FinalAppraisal,
+    Actions::WasteNotII,
+    Actions::ByregotsBlessing,
+    Actions::PreciseTouch,
+    Actions::MuscleMemory,
+    Actions::CarefulSynthesis,
+    Actions::Manipulation,
+    Actions::PrudentTouch,
+    Actions::Reflect,
+    Actions::PreparatoryTouch,
+    Actions::Groundwork,
+    Actions::DelicateSynthesis,
+    Actions::IntensiveSynthesis,
+    Actions::TrainedEye,
+    Actions::AdvancedTouch,
+    Actions::PrudentSynthesis,
+    Actions::TrainedFinesse,
+    Actions::HeartAndSoul,
+    Actions::RefinedTouch,
+    Actions::DaringTouch,
+    Actions::ImmaculateMend,
+    Actions::QuickInnovation,
+    Actions::TrainedPerfection,
+    Actions::StellarSteadyHand,
+];
+
+/// 每个候选技能随机模拟次数的上限，更大的`budget`会被截断
+pub const MAX_BUDGET: usize = 10000;
+
+/// 一个当前可释放的技能，以及释放后求解器给出的后续技能序列
+pub struct Candidate {
+    action: Actions,
+    recommended: bool,
+    follow_up: Vec<Actions>,
+}
+
+/// 根据实际制作中的状态（包含当前球色），给出“下一步按什么”的建议。
+///
+/// 对每个当前可释放的技能，先释放该技能，再按求解器给出的后续技能序列进行`budget`次随机模拟，
+/// 统计期望品质与HQ率。返回结果按期望HQ率、期望品质从高到低排序。
+pub fn advise(solver: &(impl Solver + ?Sized), status: &Status, budget: usize) -> Vec<Advice> {
+    evaluate(status, candidates(solver, status), budget)
+}
+
+/// [`advise`]中需要求解器的部分：列出可释放的技能并读取各自的后续技能序列
+pub fn candidates(solver: &(impl Solver + ?Sized), status: &Status) -> Vec<Candidate> {
+    if status.is_finished() {
+        return Vec::new();
+    }
+    let recommended = solver.read(status);
+    CANDIDATES
+        .into_iter()
+        .filter(|&action| status.is_action_allowed(action).is_ok())
+        .map(|action| {
+            let mut next = status.clone();
+            next.cast_action(action);
+            Candidate {
+                action,
+                recommended: recommended == Some(action),
+                follow_up: solver.read_all(&next),
+            }
+        })
+        .collect()
+}
+
+/// [`advise`]中的随机模拟，不需要求解器，`budget`不超过[`MAX_BUDGET`]
+pub fn evaluate(status: &Status, candidates: Vec<Candidate>, budget: usize) -> Vec<Advice> {
+    let budget = budget.clamp(1, MAX_BUDGET);
+    let mut rng = rng();
+    let mut result: Vec<Advice> = candidates
+        .into_iter()
+        .map(|candidate| {
+            let (expected_quality, high_quality_rate, fail_rate) = rollout(
+                &mut rng,
+                status,
+                candidate.action,
+                &candidate.follow_up,
+                budget,
+            );
+            Advice {
+                action: candidate.action,
+                recommended: candidate.recommended,
+                follow_up: candidate.follow_up,
+                expected_quality,
+                high_quality_rate,
+                fail_rate,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.high_quality_rate
+            .partial_cmp(&a.high_quality_rate)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| {
+                b.expected_quality
+                    .partial_cmp(&a.expected_quality)
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then_with(|| b.recommended.cmp(&a.recommended))
+    });
+    result
+}
+
+fn rollout(
+    rng: &mut impl Rng,
+    status: &Status,
+    action: Actions,
+    follow_up: &[Actions],
+    n: usize,
+) -> (f64, f64, f64) {
+    let (mut quality, mut high_quality, mut fails) = (0.0, 0.0, 0);
+    for _ in 0..n {
+        let mut s = status.clone();
+        for &sk in std::iter::once(&action).chain(follow_up) {
+            if s.is_finished() {
+                break;
+            }
+            // 随机球色下后续技能可能无法释放，跳过即可
+            let _ = simulate_one_step(&mut s, sk, false, rng);
+        }
+        quality += s.quality as f64;
+        if s.is_finished() && s.progress >= s.recipe.difficulty {
+            high_quality += s.high_quality_probability().unwrap_or(0) as f64;
+        } else {
+            fails += 1;
+        }
+    }
+    let n = n as f64;
+    (quality / n, high_quality / n, fails as f64 / n)
+}
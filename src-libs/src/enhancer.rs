@@ -0,0 +1,232 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use ffxiv_crafting::{Actions, Attributes, Status};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    new_status, simulate,
+    solver::{AtomicFlag, CrafterProfile, raphael},
+};
+
+/// 食物或药水，数值为百分比加成及其上限，与`medicine_table`和`meals_table`返回的结构一致
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Enhancer {
+    pub name: String,
+    pub level: u32,
+    pub is_hq: bool,
+
+    pub cm: Option<i8>,
+    pub cm_max: Option<i16>,
+    pub ct: Option<i8>,
+    pub ct_max: Option<i16>,
+    pub cp: Option<i8>,
+    pub cp_max: Option<i16>,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize)]
+pub struct AttributesAddon {
+    pub cm: i32,
+    pub ct: i32,
+    pub cp: i32,
+}
+
+fn bonus(base: i32, rate: Option<i8>, max: Option<i16>) -> i32 {
+    match (rate, max) {
+        (Some(rate), Some(max)) if rate != 0 && max != 0 => {
+            (base * rate as i32 / 100).min(max as i32)
+        }
+        _ => 0,
+    }
+}
+
+impl Enhancer {
+    /// 是否不提供任何制作相关的加成
+    fn is_empty(&self) -> bool {
+        [self.cm, self.ct, self.cp]
+            .into_iter()
+            .all(|rate| rate.unwrap_or(0) == 0)
+    }
+
+    /// 计算单个食物或药水带来的属性加成
+    pub fn addon(&self, attrs: &Attributes) -> AttributesAddon {
+        AttributesAddon {
+            cm: bonus(attrs.craftsmanship, self.cm, self.cm_max),
+            ct: bonus(attrs.control, self.ct, self.ct_max),
+            cp: bonus(attrs.craft_points, self.cp, self.cp_max),
+        }
+    }
+}
+
+/// 计算多个食物和药水的属性加成之和，每一项都以未加成的属性为基数
+pub fn attributes_addon<'a>(
+    attrs: &Attributes,
+    enhancers: impl IntoIterator<Item = &'a Enhancer>,
+) -> AttributesAddon {
+    enhancers
+        .into_iter()
+        .map(|enh| enh.addon(attrs))
+        .fold(AttributesAddon::default(), |acc, x| AttributesAddon {
+            cm: acc.cm + x.cm,
+            ct: acc.ct + x.ct,
+            cp: acc.cp + x.cp,
+        })
+}
+
+/// 计算使用食物和药水后的玩家属性
+pub fn enhanced_attributes<'a>(
+    attrs: Attributes,
+    enhancers: impl IntoIterator<Item = &'a Enhancer>,
+) -> Attributes {
+    let addon = attributes_addon(&attrs, enhancers);
+    Attributes {
+        craftsmanship: attrs.craftsmanship + addon.cm,
+        control: attrs.control + addon.ct,
+        craft_points: attrs.craft_points + addon.cp,
+        ..attrs
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum OptimizeTarget {
+    /// 品质达到配方最高品质
+    HighQuality,
+    /// 收藏价值达到指定值
+    Collectability(u32),
+}
+
+impl OptimizeTarget {
+    fn quality(&self, status: &Status) -> u32 {
+        match *self {
+            Self::HighQuality => status.recipe.quality,
+            Self::Collectability(c) => c.saturating_mul(10).min(status.recipe.quality),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EnhancerChoice {
+    pub meal: Option<Enhancer>,
+    pub medicine: Option<Enhancer>,
+    pub attributes: Attributes,
+    pub actions: Vec<Actions>,
+}
+
+/// 食物或药水的“价格”，等级越低越便宜，同等级下NQ比HQ便宜
+fn cost(enh: Option<&Enhancer>) -> (u32, u32) {
+    enh.map_or((0, 0), |e| (e.level, e.is_hq as u32))
+}
+
+/// 最多尝试的组合数量，每个组合都需要完整运行一次求解器
+pub const MAX_SOLVES: usize = 32;
+
+/// 为给定的初始状态挑选最便宜的食物和药水组合，使求解器得到的技能序列能够达到目标品质。
+///
+/// `status`为使用基础属性创建的初始状态，其中的初期品质和已充能的技能会被保留。
+/// 按价格从低到高依次尝试每个组合，若某组合的属性不高于一个已经失败的组合，则直接跳过。
+/// 所有组合都无法达到目标，或尝试了[`MAX_SOLVES`]个组合仍未成功时返回`None`。
+pub fn optimize(
+    status: Status,
+    meals: &[Enhancer],
+    medicines: &[Enhancer],
+    target: OptimizeTarget,
+    use_manipulation: bool,
+    profile: CrafterProfile,
+) -> Option<EnhancerChoice> {
+    optimize_interruptible(
+        status,
+        meals,
+        medicines,
+        target,
+        use_manipulation,
+        profile,
+        &AtomicFlag::new(),
+    )
+}
+
+/// 同[`optimize`]，`interrupt`被设置后尽快停止并返回`None`
+pub fn optimize_interruptible(
+    status: Status,
+    meals: &[Enhancer],
+    medicines: &[Enhancer],
+    target: OptimizeTarget,
+    use_manipulation: bool,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
+) -> Option<EnhancerChoice> {
+    let meals = std::iter::once(None).chain(meals.iter().filter(|e| !e.is_empty()).map(Some));
+    let medicines: Vec<_> = std::iter::once(None)
+        .chain(medicines.iter().filter(|e| !e.is_empty()).map(Some))
+        .collect();
+    let mut combinations: Vec<(Option<&Enhancer>, Option<&Enhancer>)> = meals
+        .flat_map(|meal| medicines.iter().map(move |&medicine| (meal, medicine)))
+        .collect();
+    combinations.sort_by_key(|&(meal, medicine)| {
+        let (meal_level, meal_hq) = cost(meal);
+        let (medicine_level, medicine_hq) = cost(medicine);
+        (meal_level + medicine_level, meal_hq + medicine_hq)
+    });
+
+    let target_quality = target.quality(&status);
+    let stellar_steady_hand = status.limits.stellar_steady_hand_charged;
+    let mut failed: Vec<Attributes> = Vec::new();
+    for (meal, medicine) in combinations {
+        if interrupt.is_set() || failed.len() >= MAX_SOLVES {
+            return None;
+        }
+        let attributes = enhanced_attributes(status.attributes, meal.into_iter().chain(medicine));
+        if failed.iter().any(|f| {
+            attributes.craftsmanship <= f.craftsmanship
+                && attributes.control <= f.control
+                && attributes.craft_points <= f.craft_points
+        }) {
+            continue;
+        }
+        let Ok(mut s) = new_status(attributes, status.recipe, stellar_steady_hand) else {
+            return None;
+        };
+        s.quality = status.quality;
+        let actions = raphael::solve_interruptible(
+            s.clone(),
+            Some(target_quality),
+            use_manipulation,
+            true,
+            false,
+            false,
+            profile,
+            interrupt,
+        );
+        // 被中断的求解器返回空序列，不能据此认为该组合失败
+        if interrupt.is_set() {
+            return None;
+        }
+        let final_status = simulate(s, actions.clone()).status;
+        if final_status.progress >= final_status.recipe.difficulty
+            && final_status.quality >= target_quality
+        {
+            return Some(EnhancerChoice {
+                meal: meal.cloned(),
+                medicine: medicine.cloned(),
+                attributes,
+                actions,
+            });
+        }
+        failed.push(attributes);
+    }
+    None
+}
@@ -15,6 +15,8 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 pub mod analyzer;
+pub mod enhancer;
+pub mod melding;
 pub mod solver;
 
 pub use ffxiv_crafting;
@@ -22,7 +24,7 @@ use ffxiv_crafting::{
     Actions, Attributes, CastActionError, Condition, ConditionIterator, Recipe, Status,
 };
 use rand::{Rng, RngExt, seq::IndexedRandom};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Serialize)]
 pub struct CastErrorPos {
@@ -51,6 +53,46 @@ pub fn new_status(
     }
 }
 
+/// 配方中的一种素材，用于计算初期品质
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QualityIngredient {
+    /// 素材的物品品级
+    pub level: u32,
+    pub can_be_hq: bool,
+    /// 配方需要的数量
+    pub amount: u32,
+    /// 其中使用HQ素材的数量，超出`amount`的部分会被忽略
+    pub hq_amount: u32,
+}
+
+/// 根据使用的HQ素材计算初期品质。
+///
+/// 初期品质的上限为配方最高品质的`material_quality_factor`%，
+/// 按HQ素材的品级之和占所有可以是HQ的素材品级之和的比例折算，结果向下取整。
+pub fn initial_quality(
+    recipe_quality: u32,
+    material_quality_factor: u8,
+    ingredients: &[QualityIngredient],
+) -> u32 {
+    let (mut total, mut hq) = (0u64, 0u64);
+    for i in ingredients.iter().filter(|i| i.can_be_hq) {
+        total += i.amount as u64 * i.level as u64;
+        hq += i.hq_amount.min(i.amount) as u64 * i.level as u64;
+    }
+    if total == 0 {
+        return 0;
+    }
+    (recipe_quality as u64 * material_quality_factor as u64 * hq / (100 * total)) as u32
+}
+
+/// 以指定的初期品质开始制作，超出配方最高品质的部分会被截断，未指定时保持原状态不变
+pub fn with_initial_quality(mut status: Status, initial_quality: Option<u32>) -> Status {
+    if let Some(quality) = initial_quality {
+        status.quality = quality.min(status.recipe.quality);
+    }
+    status
+}
+
 /// 模拟以指定初始状态按顺序执行一个技能序列后的结果，
 /// 返回值`SimulateResult`包含了最终状态以及模拟过程中每个技能失败的位置及原因
 pub fn simulate(status: Status, actions: Vec<Actions>) -> SimulateResult {
@@ -0,0 +1,372 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 魔晶石镶嵌：为装备挑选总价最低、能使属性达到目标的魔晶石
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+/// 一件装备最多能镶嵌的魔晶石数量，包括禁断镶嵌
+pub const MAX_MATERIA_SLOTS: u8 = 5;
+
+/// 作业精度、加工精度与制作力
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub craftsmanship: u32,
+    pub control: u32,
+    pub craft_points: u32,
+}
+
+impl Stats {
+    fn to_array(self) -> [u32; 3] {
+        [self.craftsmanship, self.control, self.craft_points]
+    }
+
+    fn from_array([craftsmanship, control, craft_points]: [u32; 3]) -> Self {
+        Self {
+            craftsmanship,
+            control,
+            craft_points,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stat {
+    Craftsmanship,
+    Control,
+    CraftPoints,
+}
+
+impl Stat {
+    fn index(self) -> usize {
+        match self {
+            Self::Craftsmanship => 0,
+            Self::Control => 1,
+            Self::CraftPoints => 2,
+        }
+    }
+}
+
+/// 一件装备，镶嵌后各项属性不能超过`caps`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GearPiece {
+    /// 装备当前的属性，包括已经镶嵌的魔晶石
+    pub stats: Stats,
+    pub caps: Stats,
+    /// 空闲的普通孔数量
+    pub free_slots: u8,
+    /// 还可以禁断镶嵌的孔数量，不允许禁断镶嵌时为0
+    #[serde(default)]
+    pub free_overmeld_slots: u8,
+    /// 已经禁断镶嵌的孔数量，决定之后每个禁断孔的成功率
+    #[serde(default)]
+    pub overmelded: u8,
+}
+
+/// 一种可用的魔晶石
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MateriaGrade {
+    pub item_id: u32,
+    pub stat: Stat,
+    pub value: u32,
+    /// 单个魔晶石的价格
+    pub cost: u32,
+    /// 在第1、2、3……个禁断孔中镶嵌的成功率（百分比），不能用于禁断镶嵌时为空
+    #[serde(default)]
+    pub overmeld_rates: Vec<u8>,
+}
+
+impl MateriaGrade {
+    /// 在第`overmeld`个禁断孔（从0开始）中镶嵌一次成功所需的预期花费，不能镶嵌时为`None`
+    fn overmeld_cost(&self, overmeld: usize) -> Option<u64> {
+        let rate = *self.overmeld_rates.get(overmeld)? as u64;
+        (rate > 0).then(|| (self.cost as u64 * 100).div_ceil(rate))
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MeldPlan {
+    /// 每件装备按孔的顺序镶嵌的魔晶石物品id，与输入的装备顺序一致
+    pub melds: Vec<Vec<u32>>,
+    /// 预期的总花费，禁断镶嵌按成功率折算
+    pub cost: u64,
+    /// 镶嵌后的属性
+    pub stats: Stats,
+}
+
+/// 一件装备的一种镶嵌方式
+struct MeldOption {
+    materia: Vec<usize>,
+    /// 镶嵌后各项属性的增加量，已按上限与所需的量截断
+    gains: [u32; 3],
+    cost: u64,
+}
+
+/// 列举一件装备所有的镶嵌方式，只保留同样增益下花费最低、且不被其他方式完全优于的方式。
+///
+/// 普通孔的成功率为100%，与顺序无关，因此只列举组合；禁断孔的成功率依次降低，需要列举排列。
+/// 前一个孔空着时之后的孔也不能镶嵌。孔的总数不超过[`MAX_MATERIA_SLOTS`]。
+fn meld_options(gear: &GearPiece, materia: &[MateriaGrade], needed: [u32; 3]) -> Vec<MeldOption> {
+    struct Search<'a> {
+        gear: &'a GearPiece,
+        materia: &'a [MateriaGrade],
+        needed: [u32; 3],
+        free_slots: usize,
+        overmeld_slots: usize,
+        best: HashMap<[u32; 3], MeldOption>,
+    }
+
+    impl Search<'_> {
+        fn visit(&mut self, melds: &mut Vec<usize>, raw: [u32; 3], cost: u64) {
+            let stats = self.gear.stats.to_array();
+            let caps = self.gear.caps.to_array();
+            let gains: [u32; 3] = std::array::from_fn(|i| {
+                let cap = caps[i].max(stats[i]);
+                (stats[i].saturating_add(raw[i]).min(cap) - stats[i]).min(self.needed[i])
+            });
+            match self.best.get(&gains) {
+                Some(option) if option.cost <= cost => {}
+                _ => {
+                    let option = MeldOption {
+                        materia: melds.clone(),
+                        gains,
+                        cost,
+                    };
+                    self.best.insert(gains, option);
+                }
+            }
+
+            let slot = melds.len();
+            let free_slots = self.free_slots;
+            if slot >= free_slots + self.overmeld_slots {
+                return;
+            }
+            for (i, m) in self.materia.iter().enumerate() {
+                let cost = if slot < free_slots {
+                    // 普通孔只按非递减顺序列举
+                    if melds.last().is_some_and(|&last| i < last) {
+                        continue;
+                    }
+                    m.cost as u64
+                } else {
+                    let overmeld = self.gear.overmelded as usize + slot - free_slots;
+                    match m.overmeld_cost(overmeld) {
+                        Some(cost) => cost,
+                        None => continue,
+                    }
+                } + cost;
+                let mut raw = raw;
+                raw[m.stat.index()] = raw[m.stat.index()].saturating_add(m.value);
+                melds.push(i);
+                self.visit(melds, raw, cost);
+                melds.pop();
+            }
+        }
+    }
+
+    let free_slots = gear.free_slots.min(MAX_MATERIA_SLOTS);
+    let overmeld_slots = gear
+        .free_overmeld_slots
+        .min(MAX_MATERIA_SLOTS.saturating_sub(free_slots + gear.overmelded.min(MAX_MATERIA_SLOTS)));
+    let mut search = Search {
+        gear,
+        materia,
+        needed,
+        free_slots: free_slots as usize,
+        overmeld_slots: overmeld_slots as usize,
+        best: HashMap::new(),
+    };
+    search.visit(&mut Vec::new(), [0; 3], 0);
+    pareto(search.best.into_values().collect(), |o| (o.gains, o.cost))
+}
+
+/// 按花费从低到高加入的增益，用于判断新的增益是否被某个更便宜的增益完全覆盖。
+///
+/// `levels[c]`记录制作力增益不低于`craft_points[c]`的所有增益中，作业精度与加工精度的天际线：
+/// 作业精度递增时加工精度递减。层数只与出现过的制作力增益的种类有关，与目标的大小无关。
+struct Frontier {
+    craft_points: Vec<u32>,
+    levels: Vec<BTreeMap<u32, u32>>,
+}
+
+impl Frontier {
+    fn new(mut craft_points: Vec<u32>) -> Self {
+        craft_points.sort_unstable();
+        craft_points.dedup();
+        Self {
+            levels: vec![BTreeMap::new(); craft_points.len()],
+            craft_points,
+        }
+    }
+
+    fn level(&self, cp: u32) -> usize {
+        self.craft_points.partition_point(|&c| c < cp)
+    }
+
+    fn covers(&self, [cm, ct, cp]: [u32; 3]) -> bool {
+        let skyline = &self.levels[self.level(cp)];
+        skyline.range(cm..).next().is_some_and(|(_, &y)| y >= ct)
+    }
+
+    fn insert(&mut self, [cm, ct, cp]: [u32; 3]) {
+        // 制作力要求越低的层包含的增益越多，某一层已被覆盖时更低的层也一定被覆盖
+        let level = self.level(cp);
+        for skyline in self.levels[..=level].iter_mut().rev() {
+            if skyline.range(cm..).next().is_some_and(|(_, &y)| y >= ct) {
+                break;
+            }
+            let dominated: Vec<u32> = skyline
+                .range(..=cm)
+                .rev()
+                .take_while(|&(_, &y)| y <= ct)
+                .map(|(&x, _)| x)
+                .collect();
+            for x in dominated {
+                skyline.remove(&x);
+            }
+            skyline.insert(cm, ct);
+        }
+    }
+}
+
+/// 去掉增益不高于另一项、花费却不低于它的项
+fn pareto<T>(mut items: Vec<T>, key: impl Fn(&T) -> ([u32; 3], u64)) -> Vec<T> {
+    items.sort_by_key(|item| {
+        let (gains, cost) = key(item);
+        (
+            cost,
+            std::cmp::Reverse(gains.iter().map(|&g| g as u64).sum::<u64>()),
+        )
+    });
+    let mut frontier = Frontier::new(items.iter().map(|item| key(item).0[2]).collect());
+    let mut kept = Vec::new();
+    for item in items {
+        let (gains, _) = key(&item);
+        if !frontier.covers(gains) {
+            frontier.insert(gains);
+            kept.push(item);
+        }
+    }
+    kept
+}
+
+/// 挑选总花费最低的镶嵌方案，使属性从`current`提高到不低于`target`。
+///
+/// `current`为镶嵌前的完整属性，`gear`中每件装备的增益不会超过其属性上限。
+/// 逐件装备做动态规划，状态为各项属性已获得的增益（截断到所需的量）。
+/// 无法达到目标时返回`None`，所需的量超过全部装备的属性上限时不做搜索。
+pub fn optimize(
+    current: Stats,
+    target: Stats,
+    gear: &[GearPiece],
+    materia: &[MateriaGrade],
+) -> Option<MeldPlan> {
+    let current_array = current.to_array();
+    let target_array = target.to_array();
+    let needed: [u32; 3] =
+        std::array::from_fn(|i| target_array[i].saturating_sub(current_array[i]));
+
+    let mut headroom = [0u32; 3];
+    for piece in gear {
+        let stats = piece.stats.to_array();
+        let caps = piece.caps.to_array();
+        for i in 0..3 {
+            headroom[i] = headroom[i].saturating_add(caps[i].saturating_sub(stats[i]));
+        }
+    }
+    if (0..3).any(|i| needed[i] > headroom[i]) {
+        return None;
+    }
+
+    struct State {
+        gains: [u32; 3],
+        cost: u64,
+        /// 上一层状态的下标与本件装备使用的镶嵌方式
+        prev: usize,
+        option: usize,
+    }
+
+    let options: Vec<Vec<MeldOption>> = gear
+        .iter()
+        .map(|g| meld_options(g, materia, needed))
+        .collect();
+    let mut layers: Vec<Vec<State>> = vec![vec![State {
+        gains: [0; 3],
+        cost: 0,
+        prev: 0,
+        option: 0,
+    }]];
+    for piece_options in &options {
+        let last = layers.last().unwrap();
+        let mut best: HashMap<[u32; 3], State> = HashMap::new();
+        for (prev, state) in last.iter().enumerate() {
+            for (option, o) in piece_options.iter().enumerate() {
+                let gains: [u32; 3] = std::array::from_fn(|i| {
+                    state.gains[i].saturating_add(o.gains[i]).min(needed[i])
+                });
+                let cost = state.cost + o.cost;
+                if best.get(&gains).is_some_and(|s| s.cost <= cost) {
+                    continue;
+                }
+                let state = State {
+                    gains,
+                    cost,
+                    prev,
+                    option,
+                };
+                best.insert(gains, state);
+            }
+        }
+        layers.push(pareto(best.into_values().collect(), |s| (s.gains, s.cost)));
+    }
+
+    let (mut index, _) = layers
+        .last()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.gains == needed)
+        .min_by_key(|(_, s)| s.cost)?;
+    let cost = layers.last().unwrap()[index].cost;
+    let mut melds = vec![Vec::new(); gear.len()];
+    let mut stats = current_array;
+    for piece in (0..gear.len()).rev() {
+        let state = &layers[piece + 1][index];
+        let option = &options[piece][state.option];
+        // 增益在搜索中被截断，这里按实际镶嵌的魔晶石重新计算属性
+        let base = gear[piece].stats.to_array();
+        let caps = gear[piece].caps.to_array();
+        let mut raw = [0u32; 3];
+        for &i in &option.materia {
+            let stat = materia[i].stat.index();
+            raw[stat] = raw[stat].saturating_add(materia[i].value);
+        }
+        for i in 0..3 {
+            let gain = base[i].saturating_add(raw[i]).min(caps[i].max(base[i])) - base[i];
+            stats[i] = stats[i].saturating_add(gain);
+        }
+        melds[piece] = option.materia.iter().map(|&i| materia[i].item_id).collect();
+        index = state.prev;
+    }
+    Some(MeldPlan {
+        melds,
+        cost,
+        stats: Stats::from_array(stats),
+    })
+}
@@ -16,21 +16,32 @@
 
 use ffxiv_crafting::{Actions, Status};
 
-use crate::solver::Score;
+use crate::solver::{AtomicFlag, CrafterProfile, Score};
 
 /// 进行一次深度优先搜索（DFS）
 ///
 /// status为开始制作时的初始状态
 /// maximum_depth为限制最深搜索深度
+pub fn solve(status: Status, maximum_depth: usize, profile: CrafterProfile) -> Vec<Actions> {
+    solve_interruptible(status, maximum_depth, profile, &AtomicFlag::new())
+}
+
+/// 同[`solve`]，`interrupt`被设置后停止搜索并返回目前找到的最好结果
 #[cfg(not(target_family = "wasm"))]
-pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Actions> {
+pub fn solve_interruptible(
+    status: Status,
+    maximum_depth: usize,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
+) -> Vec<Actions> {
     use std::sync::atomic::{AtomicUsize, Ordering};
     fn search(
         status: Status,
         actions: Vec<Actions>,
         aval_worker_num: std::sync::Arc<std::sync::atomic::AtomicUsize>,
         maximum_depth: usize,
-        specialist: bool,
+        profile: CrafterProfile,
+        interrupt: AtomicFlag,
     ) -> (Score, Vec<Actions>) {
         let mut threads = Vec::new();
         let mut best_actions = actions.clone();
@@ -41,6 +52,9 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
         stack.push((status.clone(), ACTION_LIST.into_iter()));
         stack_seq.push(Actions::BasicSynthesis);
         while let Some((status, action_iter)) = stack.last_mut() {
+            if interrupt.is_set() {
+                break;
+            }
             let Some(next_action) = action_iter.next() else {
                 stack.pop().unwrap();
                 stack_seq.pop().unwrap();
@@ -49,7 +63,7 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
             *stack_seq.last_mut().unwrap() = next_action;
 
             if !matches!(next_action, Actions::FinalAppraisal if status.buffs.final_appraisal == 0)
-                && (!matches!(next_action, Actions::HeartAndSoul) || specialist)
+                && profile.allows(next_action)
                 && stack_seq.len() <= maximum_depth
                 && status.is_action_allowed(next_action).is_ok()
             {
@@ -70,10 +84,21 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
                                 )
                                 .is_ok()
                         {
-                            let (status, actions, aval_worker_num) =
-                                (new_s.clone(), stack_seq.clone(), aval_worker_num.clone());
+                            let (status, actions, aval_worker_num, interrupt) = (
+                                new_s.clone(),
+                                stack_seq.clone(),
+                                aval_worker_num.clone(),
+                                interrupt.clone(),
+                            );
                             threads.push(std::thread::spawn(move || {
-                                search(status, actions, aval_worker_num, maximum_depth, specialist)
+                                search(
+                                    status,
+                                    actions,
+                                    aval_worker_num,
+                                    maximum_depth,
+                                    profile,
+                                    interrupt,
+                                )
                             }));
                         } else {
                             stack.push((new_s, ACTION_LIST.into_iter()));
@@ -107,13 +132,19 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
         Vec::new(),
         aval_worker_num,
         maximum_depth,
-        specialist,
+        profile,
+        interrupt.clone(),
     );
     best_actions
 }
 
 #[cfg(target_family = "wasm")]
-pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Actions> {
+pub fn solve_interruptible(
+    status: Status,
+    maximum_depth: usize,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
+) -> Vec<Actions> {
     let mut stack_seq: Vec<Actions> = Vec::new();
     let mut best_actions: Vec<Actions> = Vec::new();
     let mut best_score = Score::from(&status);
@@ -123,11 +154,15 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
         maximum_depth: usize,
         best_score: &mut Score,
         best_actions: &mut Vec<Actions>,
-        specialist: bool,
+        profile: CrafterProfile,
+        interrupt: &AtomicFlag,
     ) {
         for next_action in ACTION_LIST {
+            if interrupt.is_set() {
+                return;
+            }
             if !matches!(next_action, Actions::FinalAppraisal if status.buffs.final_appraisal == 0)
-                && (!matches!(next_action, Actions::HeartAndSoul) || specialist)
+                && profile.allows(next_action)
                 && stack_seq.len() <= maximum_depth
                 && status.is_action_allowed(next_action).is_ok()
             {
@@ -150,7 +185,8 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
                         maximum_depth,
                         best_score,
                         best_actions,
-                        specialist,
+                        profile,
+                        interrupt,
                     );
                 }
 
@@ -164,7 +200,8 @@ pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Acti
         maximum_depth,
         &mut best_score,
         &mut best_actions,
-        specialist,
+        profile,
+        interrupt,
     );
     best_actions
 }
@@ -16,12 +16,17 @@
 
 pub mod depth_first_search_solver;
 pub mod normal_progress_solver;
+pub mod pareto_solver;
 pub mod raphael;
 pub mod reflect_solver;
 
 use std::cmp::Ordering;
 
 use ffxiv_crafting::{Actions, Attributes, Recipe, Status};
+use serde::{Deserialize, Serialize};
+
+/// 用于从其它线程中途取消求解的共享标志，与raphael求解器使用同一类型
+pub use raphael_solvers::AtomicFlag;
 
 #[derive(Hash, Eq, PartialEq, Clone)]
 pub struct SolverHash {
@@ -29,6 +34,52 @@ pub struct SolverHash {
     pub recipe: Recipe,
 }
 
+/// 制作者配置，由所有求解器共用，保证各求解器对专家技能等限次技能的处理一致。
+///
+/// 等级取自`Status`中的玩家属性，本结构只描述属性之外的限制。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrafterProfile {
+    /// 是否装备了专家之证
+    pub specialist: bool,
+    /// 是否允许使用专心致志，需要专家之证
+    pub heart_and_soul: bool,
+    /// 是否允许使用快速改革，需要专家之证
+    pub quick_innovation: bool,
+    /// 可以使用的天星稳手次数
+    pub stellar_steady_hand_charges: u8,
+}
+
+impl CrafterProfile {
+    /// 专家可以使用全部专家技能，非专家则都不能使用
+    pub fn new(specialist: bool) -> Self {
+        Self {
+            specialist,
+            heart_and_soul: specialist,
+            quick_innovation: specialist,
+            stellar_steady_hand_charges: 0,
+        }
+    }
+
+    pub fn heart_and_soul(&self) -> bool {
+        self.specialist && self.heart_and_soul
+    }
+
+    pub fn quick_innovation(&self) -> bool {
+        self.specialist && self.quick_innovation
+    }
+
+    /// 判断该配置是否允许求解器使用某技能，不检查当前状态能否释放
+    pub fn allows(&self, action: Actions) -> bool {
+        match action {
+            Actions::HeartAndSoul => self.heart_and_soul(),
+            Actions::QuickInnovation => self.quick_innovation(),
+            Actions::StellarSteadyHand => self.stellar_steady_hand_charges > 0,
+            _ => true,
+        }
+    }
+}
+
 pub trait Solver {
     fn init(&mut self);
     fn read(&self, s: &Status) -> Option<Actions>;
@@ -49,6 +100,8 @@ pub trait Solver {
     }
 }
 
+/// 按进展、品质、步数的字典序比较结果，只能得到单个最优解。
+/// 需要在多个目标之间权衡时参见[`pareto_solver::Objectives`]。
 #[derive(PartialEq, Eq)]
 pub struct Score {
     pub quality: u32,
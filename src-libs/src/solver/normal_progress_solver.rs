@@ -16,28 +16,44 @@
 
 use ffxiv_crafting::{Actions, Status};
 
-pub fn solve(status: Status, maximum_depth: usize, specialist: bool) -> Vec<Actions> {
-    let (_, mut actions) = search(status, maximum_depth, specialist);
+use crate::solver::{AtomicFlag, CrafterProfile};
+
+pub fn solve(status: Status, maximum_depth: usize, profile: CrafterProfile) -> Vec<Actions> {
+    solve_interruptible(status, maximum_depth, profile, &AtomicFlag::new())
+}
+
+/// 同[`solve`]，`interrupt`被设置后停止搜索，此时返回的结果不一定最优
+pub fn solve_interruptible(
+    status: Status,
+    maximum_depth: usize,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
+) -> Vec<Actions> {
+    let (_, mut actions) = search(status, maximum_depth, profile, interrupt);
     actions.reverse();
     actions
 }
 
-fn search(status: Status, maximum_depth: usize, specialist: bool) -> (i32, Vec<Actions>) {
+fn search(
+    status: Status,
+    maximum_depth: usize,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
+) -> (i32, Vec<Actions>) {
     if status.progress == status.recipe.difficulty {
         return (status.step, Vec::new());
-    } else if status.durability <= 0 || maximum_depth == 0 {
+    } else if status.durability <= 0 || maximum_depth == 0 || interrupt.is_set() {
         return (0, Vec::new());
     }
     SKILL_LIST
         .into_iter()
         .filter(|&next_action| {
-            (!matches!(next_action, Actions::HeartAndSoul) || specialist)
-                && status.is_action_allowed(next_action).is_ok()
+            profile.allows(next_action) && status.is_action_allowed(next_action).is_ok()
         })
         .filter_map(|x| {
             let mut new_s = status.clone();
             new_s.cast_action(x);
-            let (steps, mut actions) = search(new_s, maximum_depth - 1, specialist);
+            let (steps, mut actions) = search(new_s, maximum_depth - 1, profile, interrupt);
             if steps == 0 {
                 None
             } else {
@@ -0,0 +1,154 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use ffxiv_crafting::{Actions, Status};
+use serde::Serialize;
+
+use crate::{
+    simulate,
+    solver::{AtomicFlag, CrafterProfile, raphael},
+};
+
+/// 一个技能序列在各个目标上的表现
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Objectives {
+    /// 最终品质，越高越好
+    pub quality: u32,
+    /// 步数，越少越好
+    pub steps: u16,
+    /// 消耗的制作力，越少越好
+    pub craft_points_used: i32,
+    /// 剩余耐久，越多越好
+    pub durability_left: u16,
+}
+
+impl Objectives {
+    fn new(init_status: &Status, final_status: &Status, steps: usize) -> Self {
+        Self {
+            quality: final_status.quality,
+            steps: steps as u16,
+            craft_points_used: init_status.craft_points - final_status.craft_points,
+            durability_left: final_status.durability,
+        }
+    }
+
+    /// 判断`self`是否支配`other`：所有目标都不差于`other`，且至少有一个目标更好
+    pub fn dominates(&self, other: &Self) -> bool {
+        let not_worse = self.quality >= other.quality
+            && self.steps <= other.steps
+            && self.craft_points_used <= other.craft_points_used
+            && self.durability_left >= other.durability_left;
+        not_worse && self != other
+    }
+}
+
+/// 目标品质采样数的上限，每个采样点需要调用两次raphael求解
+pub const MAX_SAMPLES: usize = 32;
+
+#[derive(Clone, Serialize)]
+pub struct ParetoRotation {
+    pub actions: Vec<Actions>,
+    pub objectives: Objectives,
+}
+
+/// 求解品质、步数、制作力消耗和剩余耐久之间的帕累托前沿。
+///
+/// 在当前品质与配方最高品质之间均匀取`samples`个目标品质（至少包含两端，至多[`MAX_SAMPLES`]个），
+/// 对每个目标品质分别以正常和后置进展两种方式调用raphael求解，
+/// 去掉无法完成制作以及被其它结果支配的技能序列后，按品质从高到低返回。
+pub fn solve(
+    status: Status,
+    samples: usize,
+    use_manipulation: bool,
+    use_trained_eye: bool,
+    profile: CrafterProfile,
+) -> Vec<ParetoRotation> {
+    solve_interruptible(
+        status,
+        samples,
+        use_manipulation,
+        use_trained_eye,
+        profile,
+        &AtomicFlag::new(),
+    )
+}
+
+/// 同[`solve`]，`interrupt`被设置后不再求解剩余的采样点，只返回已得到的结果
+pub fn solve_interruptible(
+    status: Status,
+    samples: usize,
+    use_manipulation: bool,
+    use_trained_eye: bool,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
+) -> Vec<ParetoRotation> {
+    let low = status.quality.min(status.recipe.quality);
+    let high = status.recipe.quality;
+    let samples = samples.clamp(2, MAX_SAMPLES) as u64;
+    let mut targets: Vec<u32> = (0..samples)
+        .map(|i| low + ((high - low) as u64 * i / (samples - 1)) as u32)
+        .collect();
+    targets.dedup();
+
+    let mut frontier: Vec<ParetoRotation> = Vec::new();
+    for target in targets {
+        for backload_progress in [false, true] {
+            if interrupt.is_set() {
+                break;
+            }
+            let actions = raphael::solve_interruptible(
+                status.clone(),
+                Some(target),
+                use_manipulation,
+                use_trained_eye,
+                backload_progress,
+                false,
+                profile,
+                interrupt,
+            );
+            let final_status = simulate(status.clone(), actions.clone()).status;
+            if final_status.progress < final_status.recipe.difficulty {
+                continue;
+            }
+            let objectives = Objectives::new(&status, &final_status, actions.len());
+            insert(
+                &mut frontier,
+                ParetoRotation {
+                    actions,
+                    objectives,
+                },
+            );
+        }
+    }
+    frontier.sort_by(|a, b| {
+        b.objectives
+            .quality
+            .cmp(&a.objectives.quality)
+            .then_with(|| a.objectives.steps.cmp(&b.objectives.steps))
+    });
+    frontier
+}
+
+/// 将结果加入前沿，并移除被其支配的结果；若其被前沿中的结果支配或与之相同则丢弃
+fn insert(frontier: &mut Vec<ParetoRotation>, rotation: ParetoRotation) {
+    if frontier.iter().any(|r| {
+        r.objectives.dominates(&rotation.objectives) || r.objectives == rotation.objectives
+    }) {
+        return;
+    }
+    frontier.retain(|r| !rotation.objectives.dominates(&r.objectives));
+    frontier.push(rotation);
+}
@@ -14,27 +14,53 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use ffxiv_crafting::{Actions, Status};
+use ffxiv_crafting::{Actions, ComboStates, Status};
 
-use raphael_simulator::{Action, ActionMask, Settings};
+use raphael_simulator::{Action, ActionMask, Combo, Settings, SimulationState};
 use raphael_solvers::{AtomicFlag, MacroSolver, SolverSettings};
 
+use crate::solver::{CrafterProfile, Solver, reflect_solver::ProgressSolver};
+
 pub fn solve(
     status: Status,
     target_quality: Option<u32>,
     use_manipultaion: bool,
-    use_heart_and_soul: bool,
-    use_quick_innovation: bool,
     use_trained_eye: bool,
     backload_progress: bool,
     adversarial: bool,
-    stellar_steady_hand_charges: u8,
+    profile: CrafterProfile,
+) -> Vec<Actions> {
+    solve_interruptible(
+        status,
+        target_quality,
+        use_manipultaion,
+        use_trained_eye,
+        backload_progress,
+        adversarial,
+        profile,
+        &AtomicFlag::new(),
+    )
+}
+
+/// 同[`solve`]，`interrupt`被设置后求解器会尽快停止并返回空序列。
+///
+/// 当前品质已经达到目标时只需要推满进展，改用[`ProgressSolver`]求解；制作已经结束时返回空序列。
+#[allow(clippy::too_many_arguments)]
+pub fn solve_interruptible(
+    status: Status,
+    target_quality: Option<u32>,
+    use_manipultaion: bool,
+    use_trained_eye: bool,
+    backload_progress: bool,
+    adversarial: bool,
+    profile: CrafterProfile,
+    interrupt: &AtomicFlag,
 ) -> Vec<Actions> {
     let mut allowed_actions = ActionMask::all();
-    if !use_heart_and_soul {
+    if !profile.heart_and_soul() || status.is_action_allowed(Actions::HeartAndSoul).is_err() {
         allowed_actions = allowed_actions.remove(Action::HeartAndSoul)
     }
-    if !use_quick_innovation {
+    if !profile.quick_innovation() || status.limits.quick_innovation_used > 0 {
         allowed_actions = allowed_actions.remove(Action::QuickInnovation)
     }
     if !use_manipultaion {
@@ -43,20 +69,35 @@ pub fn solve(
     if !use_trained_eye || status.is_action_allowed(Actions::TrainedEye).is_err() {
         allowed_actions = allowed_actions.remove(Action::TrainedEye);
     }
-    let target_quality = target_quality.unwrap_or(status.recipe.quality) as u16;
+    if status.is_finished() {
+        return Vec::new();
+    }
+    let target_quality = target_quality.unwrap_or(status.recipe.quality);
+    let target_quality = u16::try_from(target_quality).unwrap_or(u16::MAX);
+    let max_quality =
+        target_quality.saturating_sub(u16::try_from(status.quality).unwrap_or(u16::MAX));
+    if max_quality == 0 {
+        // 只推进展时不再使用掌握，表的大小约为完整求解表的1/9
+        let manipulation = status.buffs.manipulation > 0;
+        return ProgressSolver::new(status.clone(), manipulation, 8, false, profile)
+            .read_all(&status);
+    }
     let simulator_settings = Settings {
         max_cp: status.attributes.craft_points as u16,
         max_durability: status.recipe.durability,
         max_progress: status.recipe.difficulty as u16,
-        max_quality: target_quality - status.quality as u16,
+        max_quality,
         base_progress: status.caches.base_synth as u16,
         base_quality: status.caches.base_touch as u16,
         job_level: status.attributes.level,
         allowed_actions,
         adversarial,
         backload_progress,
-        stellar_steady_hand_charges,
+        stellar_steady_hand_charges: profile
+            .stellar_steady_hand_charges
+            .min(status.limits.stellar_steady_hand_charged),
     };
+    let initial_state = initial_state(&status, &simulator_settings);
     let solver_settings = SolverSettings {
         simulator_settings,
         allow_non_max_quality_solutions: true,
@@ -65,16 +106,58 @@ pub fn solve(
         solver_settings,
         Box::new(|_| {}),
         Box::new(|_| {}),
-        AtomicFlag::new(),
+        interrupt.clone(),
     );
     solver
-        .solve()
+        .solve_from(initial_state)
         .into_iter()
         .flatten()
         .map(map_action)
         .collect()
 }
 
+/// 将制作中途的状态转换为raphael的初始状态，使求解器可以从任意一步继续求解。
+///
+/// 品质已在`Settings::max_quality`中扣除，所以这里不再设置品质。
+fn initial_state(status: &Status, settings: &Settings) -> SimulationState {
+    let mut state = SimulationState::new(settings);
+    state.cp = status.craft_points as u16;
+    state.durability = status.durability;
+    state.progress = status.progress as u32;
+    state.effects = state
+        .effects
+        .with_inner_quiet(status.buffs.inner_quiet)
+        .with_innovation(status.buffs.innovation)
+        .with_veneration(status.buffs.veneration)
+        .with_great_strides(status.buffs.great_strides)
+        .with_muscle_memory(status.buffs.muscle_memory)
+        .with_waste_not(status.buffs.wast_not.max(status.buffs.wast_not_ii))
+        .with_manipulation(status.buffs.manipulation)
+        .with_trained_perfection_available(status.limits.trained_perfection_used == 0)
+        .with_trained_perfection_active(status.buffs.trained_perfection > 0)
+        .with_heart_and_soul_available(
+            settings.allowed_actions.has(Action::HeartAndSoul)
+                && status.limits.heart_and_soul_used == 0,
+        )
+        .with_heart_and_soul_active(status.buffs.heart_and_soul > 0)
+        .with_quick_innovation_available(
+            settings.allowed_actions.has(Action::QuickInnovation)
+                && status.limits.quick_innovation_used == 0,
+        )
+        .with_combo(map_combo(status));
+    state
+}
+
+fn map_combo(status: &Status) -> Combo {
+    match status.combo {
+        _ if status.step == 0 => Combo::SynthesisBegin,
+        Some(ComboStates::BasicTouched) => Combo::BasicTouch,
+        // raphael中观察与加工都通过StandardTouch连击解锁上级加工
+        Some(ComboStates::StandardTouched | ComboStates::Observed) => Combo::StandardTouch,
+        None => Combo::None,
+    }
+}
+
 fn map_action(action: Action) -> Actions {
     match action {
         Action::BasicSynthesis => Actions::BasicSynthesis,
@@ -16,7 +16,7 @@
 
 use crate::{
     SimulateResult, simulate,
-    solver::{Score, Solver},
+    solver::{CrafterProfile, Score, Solver},
 };
 use ffxiv_crafting::{Actions, Buffs, ComboStates, Status};
 use micro_ndarray::Array;
@@ -27,12 +27,14 @@ pub fn solve(
     use_manipulation: bool,
     use_waste_not: usize,
     use_observe: bool,
+    profile: CrafterProfile,
 ) -> Vec<Actions> {
     let solver = QualitySolver::new(
         status.clone(),
         use_manipulation || status.buffs.manipulation > 0,
         (status.buffs.wast_not.max(status.buffs.wast_not_ii) as usize + 1).max(use_waste_not + 1),
         use_observe,
+        profile,
     );
     let result1 = solver.read_all(&status);
     let SimulateResult { status: s1, .. } = simulate(status.clone(), result1.clone());
@@ -62,7 +64,7 @@ struct SolverSlot<T> {
     is_some: bool,
 }
 
-const SYNTH_SKILLS: [Actions; 12] = [
+const SYNTH_SKILLS: [Actions; 13] = [
     Actions::BasicSynthesis,
     Actions::WasteNot,
     Actions::Veneration,
@@ -75,9 +77,10 @@ const SYNTH_SKILLS: [Actions; 12] = [
     Actions::Observe,
     Actions::TrainedPerfection,
     Actions::ImmaculateMend,
+    Actions::HeartAndSoul,
 ];
 
-const TOUCH_SKILLS: [Actions; 17] = [
+const TOUCH_SKILLS: [Actions; 20] = [
     Actions::BasicTouch,
     Actions::RefinedTouch,
     Actions::MastersMend,
@@ -85,7 +88,7 @@ const TOUCH_SKILLS: [Actions; 17] = [
     Actions::StandardTouch,
     Actions::GreatStrides,
     Actions::Innovation,
-    // Actions::QuickInnovation,
+    Actions::QuickInnovation,
     Actions::WasteNotII,
     Actions::ByregotsBlessing,
     Actions::PrudentTouch,
@@ -96,34 +99,127 @@ const TOUCH_SKILLS: [Actions; 17] = [
     Actions::Observe,
     Actions::TrainedPerfection,
     Actions::ImmaculateMend,
+    Actions::HeartAndSoul,
+    Actions::PreciseTouch,
 ];
 
+/// 快速改革是否已使用，配置不允许使用时不区分
+fn quick_innovation_index(profile: &CrafterProfile, s: &Status) -> usize {
+    if profile.quick_innovation() {
+        (s.limits.quick_innovation_used > 0) as usize
+    } else {
+        0
+    }
+}
+
+/// 一次性技能的状态：0为可以使用，1为生效中，2为已经用完
+fn once_action_index(active: u8, used: u8) -> usize {
+    if active > 0 {
+        1
+    } else if used > 0 {
+        2
+    } else {
+        0
+    }
+}
+
+/// 专心致志的状态，见[`once_action_index`]，配置不允许使用时不区分
+fn heart_and_soul_index(profile: &CrafterProfile, s: &Status) -> usize {
+    if profile.heart_and_soul() {
+        once_action_index(s.buffs.heart_and_soul, s.limits.heart_and_soul_used)
+    } else {
+        0
+    }
+}
+
+/// 工匠的绝技的状态，见[`once_action_index`]
+fn trained_perfection_index(s: &Status) -> usize {
+    once_action_index(s.buffs.trained_perfection, s.limits.trained_perfection_used)
+}
+
 pub struct QualitySolver {
     progress_solver: ProgressSolver,
     mn: bool,
     wn: usize,
     obz: bool,
-    // results [combo][iq][iv][gs][mn][wn][tp][qi][d][cp]
-    results: Array<Cell<SolverSlot<u32>>, 9>,
+    profile: CrafterProfile,
+    // results [combo][iq][iv][gs][mn][wn][tp][qi][hs][d][cp]
+    results: Array<Cell<SolverSlot<u32>>, 11>,
+}
+
+/// 求解表各维度的大小。允许快速改革与专心致志时，表的大小分别变为原来的2倍与3倍，最多6倍
+fn quality_table_size(
+    init_status: &Status,
+    mn: bool,
+    wn: usize,
+    profile: &CrafterProfile,
+) -> [usize; 11] {
+    let cp = init_status.attributes.craft_points as usize;
+    let du = init_status.recipe.durability as usize;
+    [
+        4, // None, Observed, BasicTouched, StdTouched
+        11,
+        5,
+        4,
+        mn as usize * 8 + 1,
+        wn + 1,
+        3,
+        profile.quick_innovation() as usize + 1,
+        profile.heart_and_soul() as usize * 2 + 1,
+        du / 5 + 1,
+        cp + 1,
+    ]
+}
+
+fn progress_table_size(
+    init_status: &Status,
+    mn: bool,
+    wn: usize,
+    obz: bool,
+    profile: &CrafterProfile,
+) -> [usize; 8] {
+    let cp = init_status.attributes.craft_points as usize;
+    let du = init_status.recipe.durability as usize;
+    [
+        obz as usize + 1,
+        5,
+        mn as usize * 8 + 1,
+        wn + 1,
+        3,
+        profile.heart_and_soul() as usize * 2 + 1,
+        du / 5 + 1,
+        cp + 1,
+    ]
+}
+
+/// 求解器预先分配的内存大小（字节），用于在创建求解器之前拒绝过大的请求
+pub fn table_bytes(
+    init_status: &Status,
+    mn: bool,
+    wn: usize,
+    obz: bool,
+    profile: &CrafterProfile,
+) -> usize {
+    let quality: usize = quality_table_size(init_status, mn, wn, profile)
+        .iter()
+        .product();
+    let progress: usize = progress_table_size(init_status, mn, wn, obz, profile)
+        .iter()
+        .product();
+    quality * size_of::<Cell<SolverSlot<u32>>>() + progress * size_of::<Cell<SolverSlot<u16>>>()
 }
 
 impl QualitySolver {
-    pub fn new(mut init_status: Status, mn: bool, wn: usize, obz: bool) -> Self {
+    pub fn new(
+        mut init_status: Status,
+        mn: bool,
+        wn: usize,
+        obz: bool,
+        profile: CrafterProfile,
+    ) -> Self {
         init_status.progress = 0;
-        let cp = init_status.attributes.craft_points as usize;
-        let du = init_status.recipe.durability as usize;
-        let progress_solver = ProgressSolver::new(init_status, mn, wn, obz);
-        let size = [
-            4, // None, Observed, BasicTouched, StdTouched
-            11,
-            5,
-            4,
-            mn as usize * 8 + 1,
-            wn + 1,
-            3,
-            du / 5 + 1,
-            cp + 1,
-        ];
+        let size = quality_table_size(&init_status, mn, wn, &profile);
+        let progress_solver = ProgressSolver::new(init_status, mn, wn, obz, profile);
         // let results = Array::new(size);
         let results = unsafe {
             use std::alloc::{Layout, alloc_zeroed};
@@ -139,6 +235,7 @@ impl QualitySolver {
             wn,
             mn,
             obz,
+            profile,
             results,
         }
     }
@@ -151,7 +248,9 @@ impl QualitySolver {
             s.buffs.great_strides as usize,
             s.buffs.manipulation as usize,
             s.buffs.wast_not.max(s.buffs.wast_not_ii) as usize,
-            s.buffs.trained_perfection as usize,
+            trained_perfection_index(s),
+            quick_innovation_index(&self.profile, s),
+            heart_and_soul_index(&self.profile, s),
             s.durability as usize / 5,
             s.craft_points as usize,
         ];
@@ -188,7 +287,8 @@ impl QualitySolver {
             is_some: true,
         };
         for sk in TOUCH_SKILLS {
-            if (matches!(sk, Actions::Manipulation) && !self.mn)
+            if !self.profile.allows(sk)
+                || (matches!(sk, Actions::Manipulation) && !self.mn)
                 || (matches!(sk, Actions::WasteNotII) && self.wn < 8)
                 || (matches!(sk, Actions::WasteNot) && self.wn < 4)
                 || (matches!(sk, Actions::Observe) && !self.obz)
@@ -246,6 +346,7 @@ impl Solver for QualitySolver {
             wast_not: s.buffs.wast_not,
             wast_not_ii: s.buffs.wast_not_ii,
             trained_perfection: s.buffs.trained_perfection,
+            heart_and_soul: s.buffs.heart_and_soul,
             ..Buffs::default()
         };
         let max_addon = max_quality - s.quality;
@@ -285,23 +386,20 @@ pub struct ProgressSolver {
     mn: bool,
     wn: usize,
     obz: bool,
-    // [combo][ve][mn][wn][tp][d][cp]
-    results: Array<Cell<SolverSlot<u16>>, 7>,
+    profile: CrafterProfile,
+    // [combo][ve][mn][wn][tp][hs][d][cp]
+    results: Array<Cell<SolverSlot<u16>>, 8>,
 }
 
 impl ProgressSolver {
-    pub fn new(init_status: Status, mn: bool, wn: usize, obz: bool) -> Self {
-        let cp = init_status.attributes.craft_points as usize;
-        let du = init_status.recipe.durability as usize;
-        let size = [
-            obz as usize + 1,
-            5,
-            mn as usize * 8 + 1,
-            wn + 1,
-            3,
-            du / 5 + 1,
-            cp + 1,
-        ];
+    pub fn new(
+        init_status: Status,
+        mn: bool,
+        wn: usize,
+        obz: bool,
+        profile: CrafterProfile,
+    ) -> Self {
+        let size = progress_table_size(&init_status, mn, wn, obz, &profile);
         let results = unsafe {
             use std::alloc::{Layout, alloc_zeroed};
 
@@ -315,6 +413,7 @@ impl ProgressSolver {
             mn,
             wn,
             obz,
+            profile,
             results,
         }
     }
@@ -325,7 +424,8 @@ impl ProgressSolver {
             s.buffs.veneration as usize,
             s.buffs.manipulation as usize,
             s.buffs.wast_not.max(s.buffs.wast_not_ii) as usize,
-            s.buffs.trained_perfection as usize,
+            trained_perfection_index(s),
+            heart_and_soul_index(&self.profile, s),
             (s.durability as usize).div_ceil(5),
             s.craft_points as usize,
         ];
@@ -338,6 +438,12 @@ impl ProgressSolver {
     }
 
     fn inner_read(&self, s: &Status) -> SolverSlot<u16> {
+        // 表中不记录当前进展，已有的进展会使技能被判定为不可用，因此从0开始计算
+        if s.progress > 0 {
+            let mut s = s.clone();
+            s.progress = 0;
+            return self.inner_read(&s);
+        }
         let slot = self.get(s);
         {
             let result = slot.get();
@@ -362,7 +468,8 @@ impl ProgressSolver {
             is_some: true,
         };
         for sk in SYNTH_SKILLS {
-            if (matches!(sk, Actions::Manipulation) && !self.mn)
+            if !self.profile.allows(sk)
+                || (matches!(sk, Actions::Manipulation) && !self.mn)
                 || (matches!(sk, Actions::WasteNotII) && self.wn < 8)
                 || (matches!(sk, Actions::WasteNot) && self.wn < 4)
                 || (matches!(sk, Actions::Observe) && !self.obz)
@@ -425,6 +532,7 @@ impl Solver for ProgressSolver {
             wast_not: s.buffs.wast_not,
             wast_not_ii: s.buffs.wast_not_ii,
             trained_perfection: s.buffs.trained_perfection,
+            heart_and_soul: s.buffs.heart_and_soul,
             ..Buffs::default()
         };
         for cp in 0..=s.craft_points {
@@ -0,0 +1,68 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use app_libs::{
+    enhancer::{Enhancer, OptimizeTarget, optimize_interruptible},
+    solver::{AtomicFlag, CrafterProfile},
+};
+use ffxiv_crafting::{Attributes, Recipe, RecipeLevel, Status};
+
+fn status() -> Status {
+    let rlv = RecipeLevel {
+        id: 690,
+        class_job_level: 100,
+        stars: 0,
+        suggested_craftsmanship: 0,
+        difficulty: 600,
+        quality: 2000,
+        progress_divider: 130,
+        quality_divider: 115,
+        progress_modifier: 80,
+        quality_modifier: 70,
+        durability: 40,
+        conditions_flag: 15,
+    };
+    let attributes = Attributes {
+        level: 100,
+        craftsmanship: 4900,
+        control: 4800,
+        craft_points: 600,
+    };
+    Status::new(attributes, Recipe::new(rlv, 100, 100, 100))
+}
+
+#[test]
+fn interrupted_before_solving() {
+    let meal = Enhancer {
+        name: "meal".to_string(),
+        level: 100,
+        cp: Some(10),
+        cp_max: Some(80),
+        ..Default::default()
+    };
+    let interrupt = AtomicFlag::new();
+    interrupt.set();
+    let result = optimize_interruptible(
+        status(),
+        &[meal],
+        &[],
+        OptimizeTarget::HighQuality,
+        true,
+        CrafterProfile::new(false),
+        &interrupt,
+    );
+    assert!(result.is_none());
+}
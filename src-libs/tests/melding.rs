@@ -0,0 +1,99 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use app_libs::melding::{GearPiece, MAX_MATERIA_SLOTS, MateriaGrade, Stat, Stats, optimize};
+
+fn stats(craftsmanship: u32, control: u32, craft_points: u32) -> Stats {
+    Stats {
+        craftsmanship,
+        control,
+        craft_points,
+    }
+}
+
+fn piece(current: Stats, caps: Stats, free_slots: u8, free_overmeld_slots: u8) -> GearPiece {
+    GearPiece {
+        stats: current,
+        caps,
+        free_slots,
+        free_overmeld_slots,
+        overmelded: 0,
+    }
+}
+
+fn materia(item_id: u32, stat: Stat, value: u32, cost: u32, overmeld_rates: &[u8]) -> MateriaGrade {
+    MateriaGrade {
+        item_id,
+        stat,
+        value,
+        cost,
+        overmeld_rates: overmeld_rates.to_vec(),
+    }
+}
+
+#[test]
+fn cheapest_materia_within_caps() {
+    let materia = [
+        materia(1, Stat::Craftsmanship, 10, 50, &[]),
+        materia(2, Stat::Control, 10, 1, &[]),
+        materia(3, Stat::Craftsmanship, 10, 5, &[]),
+    ];
+    // 每件装备只能再提高10或15点作业精度，超过上限的魔晶石不会带来增益
+    let gear = [
+        piece(stats(0, 0, 0), stats(10, 100, 0), 2, 0),
+        piece(stats(0, 0, 0), stats(15, 100, 0), 2, 0),
+    ];
+    let plan = optimize(stats(0, 0, 0), stats(20, 0, 0), &gear, &materia).unwrap();
+    assert_eq!(plan.melds, [vec![3], vec![3]]);
+    assert_eq!(plan.cost, 10);
+    assert_eq!(plan.stats, stats(20, 0, 0));
+}
+
+#[test]
+fn overmeld_order() {
+    // 禁断孔的成功率依次降低，成功率下降多的魔晶石应当先镶嵌
+    let materia = [
+        materia(1, Stat::Craftsmanship, 12, 100, &[100, 20]),
+        materia(2, Stat::Craftsmanship, 9, 10, &[100, 50]),
+    ];
+    let gear = [piece(stats(0, 0, 0), stats(100, 0, 0), 0, 2)];
+    let plan = optimize(stats(0, 0, 0), stats(20, 0, 0), &gear, &materia).unwrap();
+    assert_eq!(plan.melds, [vec![1, 2]]);
+    assert_eq!(plan.cost, 100 + 20);
+    assert_eq!(plan.stats, stats(21, 0, 0));
+}
+
+#[test]
+fn unreachable_target() {
+    let materia = [materia(1, Stat::CraftPoints, 9, 10, &[])];
+    let gear = [piece(stats(0, 0, 5), stats(0, 0, 50), 1, 0)];
+    // 孔不够
+    assert!(optimize(stats(0, 0, 5), stats(0, 0, 20), &gear, &materia).is_none());
+    // 超过装备的上限
+    assert!(optimize(stats(0, 0, 5), stats(0, 0, u32::MAX), &gear, &materia).is_none());
+    // 没有提供所需属性的魔晶石
+    assert!(optimize(stats(0, 0, 5), stats(1, 0, 5), &gear, &materia).is_none());
+}
+
+#[test]
+fn slots_are_limited() {
+    let materia = [materia(1, Stat::Control, 1, 1, &[100; 8])];
+    let gear = [piece(stats(0, 0, 0), stats(0, 100, 0), u8::MAX, u8::MAX)];
+    let max = MAX_MATERIA_SLOTS as u32;
+    let plan = optimize(stats(0, 0, 0), stats(0, max, 0), &gear, &materia).unwrap();
+    assert_eq!(plan.melds[0].len(), MAX_MATERIA_SLOTS as usize);
+    assert!(optimize(stats(0, 0, 0), stats(0, max + 1, 0), &gear, &materia).is_none());
+}
@@ -0,0 +1,82 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use app_libs::{
+    simulate,
+    solver::{CrafterProfile, raphael},
+};
+use ffxiv_crafting::{Actions, Attributes, Recipe, RecipeLevel, Status};
+
+fn status() -> Status {
+    let rlv = RecipeLevel {
+        id: 690,
+        class_job_level: 100,
+        stars: 0,
+        suggested_craftsmanship: 0,
+        difficulty: 600,
+        quality: 2000,
+        progress_divider: 130,
+        quality_divider: 115,
+        progress_modifier: 80,
+        quality_modifier: 70,
+        durability: 40,
+        conditions_flag: 15,
+    };
+    let attributes = Attributes {
+        level: 100,
+        craftsmanship: 4900,
+        control: 4800,
+        craft_points: 100,
+    };
+    Status::new(attributes, Recipe::new(rlv, 100, 100, 100))
+}
+
+#[test]
+fn resume_above_target_quality() {
+    let mut status = status();
+    status.cast_action(Actions::BasicTouch);
+    status.cast_action(Actions::StandardTouch);
+    let target = status.quality / 2;
+    let actions = raphael::solve(
+        status.clone(),
+        Some(target),
+        true,
+        false,
+        false,
+        false,
+        CrafterProfile::new(false),
+    );
+    let result = simulate(status.clone(), actions);
+    assert!(result.errors.is_empty());
+    assert!(result.status.progress >= status.recipe.difficulty);
+    assert!(result.status.quality >= target);
+}
+
+#[test]
+fn resume_finished_craft() {
+    let mut status = status();
+    status.progress = status.recipe.difficulty;
+    let actions = raphael::solve(
+        status,
+        None,
+        true,
+        false,
+        false,
+        false,
+        CrafterProfile::new(false),
+    );
+    assert!(actions.is_empty());
+}
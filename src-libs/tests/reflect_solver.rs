@@ -0,0 +1,62 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use app_libs::{
+    simulate,
+    solver::{CrafterProfile, Solver, reflect_solver::QualitySolver},
+};
+use ffxiv_crafting::{Actions, Attributes, Recipe, RecipeLevel, Status};
+
+fn status() -> Status {
+    let rlv = RecipeLevel {
+        id: 690,
+        class_job_level: 100,
+        stars: 0,
+        suggested_craftsmanship: 0,
+        difficulty: 600,
+        quality: 2000,
+        progress_divider: 130,
+        quality_divider: 115,
+        progress_modifier: 80,
+        quality_modifier: 70,
+        durability: 20,
+        conditions_flag: 15,
+    };
+    let attributes = Attributes {
+        level: 100,
+        craftsmanship: 4900,
+        control: 4800,
+        craft_points: 60,
+    };
+    Status::new(attributes, Recipe::new(rlv, 100, 100, 100))
+}
+
+#[test]
+fn spent_heart_and_soul_is_not_cast_again() {
+    let init = status();
+    let solver = QualitySolver::new(init.clone(), false, 0, false, CrafterProfile::new(true));
+    // 先从初始状态读取，使表中填入专心致志尚可使用时的结果
+    let actions = solver.read_all(&init);
+    assert!(actions.contains(&Actions::HeartAndSoul), "{actions:?}");
+
+    let mut spent = init.clone();
+    spent.limits.heart_and_soul_used = 1;
+    let actions = solver.read_all(&spent);
+    assert!(!actions.contains(&Actions::HeartAndSoul), "{actions:?}");
+    let result = simulate(spent.clone(), actions);
+    assert!(result.errors.is_empty());
+    assert!(result.status.progress >= spent.recipe.difficulty);
+}
@@ -0,0 +1,327 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 异步求解任务：提交后立即返回任务id，通过轮询或SSE获取任务状态（排队、运行、结束）和结果
+//!
+//! 同时运行的求解器数量受`workers`限制，排队中的任务数量受`max_pending`限制。
+//! 相同的求解请求（求解器、参数与[`SolverHash`]均相同）会被合并为同一个任务。
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use app_libs::{
+    ffxiv_crafting::Status,
+    solver::{AtomicFlag, SolverHash},
+};
+use futures_util::stream;
+use salvo::prelude::*;
+use salvo::size_limiter::max_size;
+use salvo::sse::{SseEvent, SseKeepAlive};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{Semaphore, watch},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    AppState, Result,
+    solver::{
+        DfsSolveRequest, ParetoSolveRequest, RaphaelSolveRequest, ReflectSolveRequest,
+        SolverConfig, parse, spawn_until,
+    },
+};
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct JobsConfig {
+    /// 同时运行的求解任务数量
+    pub workers: usize,
+    /// 未完成任务（排队中与运行中）的最大数量，超出时拒绝提交
+    pub max_pending: usize,
+    /// 单个任务允许的最长计算时间（秒）
+    pub time_budget: u64,
+    /// 任务结束后结果的保留时间（秒）
+    pub retention: u64,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            workers: 2,
+            max_pending: 64,
+            time_budget: 600,
+            retention: 600,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "solver", content = "params", rename_all = "snake_case")]
+pub enum JobRequest {
+    Dfs(DfsSolveRequest),
+    Nq(DfsSolveRequest),
+    Reflect(ReflectSolveRequest),
+    Raphael(RaphaelSolveRequest),
+    Pareto(ParetoSolveRequest),
+}
+
+impl JobRequest {
+    fn status(&self) -> &Status {
+        match self {
+            Self::Dfs(r) | Self::Nq(r) => &r.status,
+            Self::Reflect(r) => &r.status,
+            Self::Raphael(r) => &r.status,
+            Self::Pareto(r) => &r.status,
+        }
+    }
+
+    fn check(&self, config: &SolverConfig) -> Result<()> {
+        match self {
+            Self::Dfs(r) | Self::Nq(r) => r.check(config),
+            Self::Reflect(r) => r.check(config),
+            Self::Raphael(r) => r.check(config),
+            Self::Pareto(r) => r.check(config),
+        }
+    }
+
+    fn run(self, interrupt: &AtomicFlag) -> serde_json::Result<serde_json::Value> {
+        match self {
+            Self::Dfs(r) => serde_json::to_value(r.dfs(interrupt)),
+            Self::Nq(r) => serde_json::to_value(r.nq(interrupt)),
+            Self::Reflect(r) => serde_json::to_value(r.solve(interrupt)),
+            Self::Raphael(r) => serde_json::to_value(r.solve(interrupt)),
+            Self::Pareto(r) => serde_json::to_value(r.solve(interrupt)),
+        }
+    }
+}
+
+/// 用于合并相同任务的键，`params`为序列化后的完整请求
+#[derive(Hash, Eq, PartialEq, Clone)]
+struct JobKey {
+    solver: SolverHash,
+    params: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Finished { result: serde_json::Value },
+    Failed { error: String },
+}
+
+impl JobState {
+    fn is_done(&self) -> bool {
+        matches!(self, Self::Finished { .. } | Self::Failed { .. })
+    }
+}
+
+struct Job {
+    key: JobKey,
+    state: watch::Sender<JobState>,
+}
+
+pub struct JobQueue {
+    config: JobsConfig,
+    workers: Arc<Semaphore>,
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, Arc<Job>>>,
+    dedup: Mutex<HashMap<JobKey, u64>>,
+}
+
+impl JobQueue {
+    pub fn new(config: JobsConfig) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(config.workers.max(1))),
+            config,
+            next_id: AtomicU64::new(1),
+            jobs: Mutex::new(HashMap::new()),
+            dedup: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 求解器的并发许可，同步求解接口也从这里取得许可
+    pub fn workers(&self) -> &Arc<Semaphore> {
+        &self.workers
+    }
+
+    /// 提交任务并返回任务id，若已存在相同的任务则直接返回其id
+    fn submit(self: &Arc<Self>, request: JobRequest) -> Result<u64> {
+        let status = request.status();
+        let key = JobKey {
+            solver: SolverHash {
+                attributes: status.attributes,
+                recipe: status.recipe,
+            },
+            params: serde_json::to_string(&request)
+                .map_err(|err| StatusError::bad_request().detail(err.to_string()))?,
+        };
+
+        let mut dedup = self.dedup.lock().unwrap();
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(&id) = dedup.get(&key)
+            && jobs.contains_key(&id)
+        {
+            return Ok(id);
+        }
+        let pending = jobs
+            .values()
+            .filter(|job| !job.state.borrow().is_done())
+            .count();
+        if pending >= self.config.max_pending {
+            return Err(StatusError::service_unavailable().detail("Too many pending jobs"));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (state, _) = watch::channel(JobState::Queued);
+        let job = Arc::new(Job {
+            key: key.clone(),
+            state,
+        });
+        jobs.insert(id, job.clone());
+        dedup.insert(key, id);
+        drop((jobs, dedup));
+
+        tokio::spawn(self.clone().run(id, job, request));
+        Ok(id)
+    }
+
+    async fn run(self: Arc<Self>, id: u64, job: Arc<Job>, request: JobRequest) {
+        let Ok(permit) = self.workers.clone().acquire_owned().await else {
+            return;
+        };
+        job.state.send_replace(JobState::Running);
+        // 许可随计算一同释放；超时后任务立即失败，仍在后台停止中的求解器继续占用许可，
+        // 所以重新提交的相同请求要等到它真正返回后才会开始计算
+        let deadline = Instant::now() + Duration::from_secs(self.config.time_budget);
+        let result = spawn_until(deadline, Some(permit), move |interrupt| {
+            request.run(interrupt)
+        })
+        .await;
+        let state = match result {
+            Ok(Ok(result)) => JobState::Finished { result },
+            Ok(Err(err)) => JobState::Failed {
+                error: err.to_string(),
+            },
+            Err(err) => JobState::Failed {
+                error: err.detail.unwrap_or_default(),
+            },
+        };
+        // 失败的任务不再参与合并，以便重新提交
+        if matches!(state, JobState::Failed { .. }) {
+            self.forget(id, &job.key);
+        }
+        job.state.send_replace(state);
+
+        tokio::time::sleep(Duration::from_secs(self.config.retention)).await;
+        self.forget(id, &job.key);
+        self.jobs.lock().unwrap().remove(&id);
+    }
+
+    fn forget(&self, id: u64, key: &JobKey) {
+        let mut dedup = self.dedup.lock().unwrap();
+        if dedup.get(key) == Some(&id) {
+            dedup.remove(key);
+        }
+    }
+
+    fn subscribe(&self, id: u64) -> Option<watch::Receiver<JobState>> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.get(&id).map(|job| job.state.subscribe())
+    }
+}
+
+pub fn router(config: &SolverConfig) -> Router {
+    Router::with_path("jobs")
+        .push(
+            Router::new()
+                .hoop(max_size(config.max_body_size))
+                .post(submit_job),
+        )
+        .push(
+            Router::with_path("{id}")
+                .get(job_state)
+                .push(Router::with_path("events").get(job_events)),
+        )
+}
+
+fn app_state(depot: &Depot) -> Result<&AppState> {
+    depot
+        .obtain::<AppState>()
+        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))
+}
+
+fn job_queue(depot: &Depot) -> Result<Arc<JobQueue>> {
+    Ok(app_state(depot)?.jobs.clone())
+}
+
+fn subscribe(req: &Request, depot: &Depot) -> Result<watch::Receiver<JobState>> {
+    let id = req
+        .param::<u64>("id")
+        .ok_or_else(|| StatusError::bad_request().detail("Invalid job id"))?;
+    job_queue(depot)?
+        .subscribe(id)
+        .ok_or_else(|| StatusError::not_found().detail("Job doesn't exist"))
+}
+
+#[derive(Serialize)]
+struct SubmitResponse {
+    id: u64,
+}
+
+#[handler]
+async fn submit_job(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = app_state(depot)?;
+    let request: JobRequest = parse(req).await?;
+    request.check(&state.solver)?;
+    let id = state.jobs.submit(request)?;
+    res.status_code(StatusCode::ACCEPTED);
+    res.render(Json(SubmitResponse { id }));
+    Ok(())
+}
+
+#[handler]
+async fn job_state(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = subscribe(req, depot)?.borrow().clone();
+    res.render(Json(state));
+    Ok(())
+}
+
+/// 以SSE推送任务状态，任务结束后关闭连接
+#[handler]
+async fn job_events(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let rx = subscribe(req, depot)?;
+    let events = stream::unfold((rx, true, false), |(mut rx, first, done)| async move {
+        if done || (!first && rx.changed().await.is_err()) {
+            return None;
+        }
+        let state = rx.borrow_and_update().clone();
+        let done = state.is_done();
+        let event = SseEvent::default()
+            .name("state")
+            .text(serde_json::to_string(&state).unwrap_or_default());
+        Some((Ok::<_, Infallible>(event), (rx, false, done)))
+    });
+    SseKeepAlive::new(events).stream(res);
+    Ok(())
+}
@@ -17,35 +17,57 @@
 use std::{
     collections::{BTreeMap, HashMap},
     env,
+    sync::Arc,
 };
 
 use salvo::cors;
 use salvo::cors::Cors;
 use salvo::hyper::Method;
 use salvo::prelude::*;
-use sea_orm::{Database, DatabaseConnection, FromQueryResult, entity::*, query::*};
+use salvo::size_limiter::max_size;
+use sea_orm::{Database, DatabaseConnection, DbErr};
 use serde::{Deserialize, Serialize};
 
-use app_db::{
-    craft_types, item_action, item_food, item_food_effect, items, prelude::*, recipe_level_tables,
-    recipes, wks_mission_recipe, wks_mission_to_do, wks_mission_unit,
-};
+use app_db::{bom, gearset, plan, queries, version};
+use app_libs::melding;
+
+mod jobs;
+mod rotations;
+mod solver;
 
 type Result<T> = std::result::Result<T, StatusError>;
 
 #[derive(Deserialize, Debug)]
 struct ServerConfig {
     lang: HashMap<String, LanguageConfig>,
+    #[serde(default)]
+    solver: solver::SolverConfig,
+    #[serde(default)]
+    jobs: jobs::JobsConfig,
+    rotations: Option<rotations::RotationsConfig>,
 }
 
 #[derive(Deserialize, Debug)]
 struct LanguageConfig {
     database: String,
+    /// 数据库包含多语言翻译表时，按路径中的语言代码读取翻译后的名称。
+    /// 此时多个语言可以使用同一个数据库
+    #[serde(default)]
+    translations: bool,
+}
+
+#[derive(Clone)]
+struct LanguageDatabase {
+    conn: DatabaseConnection,
+    translations: bool,
 }
 
 #[derive(Clone)]
 struct AppState {
-    connections: HashMap<String, DatabaseConnection>,
+    connections: HashMap<String, LanguageDatabase>,
+    solver: solver::SolverConfig,
+    jobs: Arc<jobs::JobQueue>,
+    rotations: Option<Arc<rotations::RotationStore>>,
 }
 
 #[tokio::main]
@@ -62,18 +84,48 @@ async fn main() {
     let port = env::var("PORT").expect("PORT is not set in .env file");
     let server_url = format!("{host}:{port}");
 
-    // create post table if not exists
+    // 使用相同数据库的语言共用一个连接
+    let mut databases: HashMap<String, DatabaseConnection> = HashMap::new();
     let mut connections = HashMap::new();
     for (lang, lang_cfg) in config.lang.into_iter() {
-        let conn = Database::connect(&lang_cfg.database).await.unwrap();
-        connections.insert(lang, conn);
+        let conn = match databases.get(&lang_cfg.database) {
+            Some(conn) => conn.clone(),
+            None => {
+                let conn = Database::connect(&lang_cfg.database).await.unwrap();
+                if let Err(err) = version::check_schema(&conn).await {
+                    panic!("Incompatible database {}: {err}", lang_cfg.database);
+                }
+                databases.insert(lang_cfg.database, conn.clone());
+                conn
+            }
+        };
+        let translations = lang_cfg.translations;
+        connections.insert(lang, LanguageDatabase { conn, translations });
     }
-    let state = AppState { connections };
+    let rotations = match config.rotations {
+        Some(rotations_cfg) => {
+            let store = rotations::RotationStore::open(rotations_cfg).await.unwrap();
+            Some(Arc::new(store))
+        }
+        None => None,
+    };
+    let solver_router = solver::router(&config.solver);
+    let jobs_router = jobs::router(&config.solver);
+    let max_body_size = config.solver.max_body_size;
+    let rotations_router = rotations::router(max_body_size);
+    let state = AppState {
+        connections,
+        solver: config.solver,
+        jobs: Arc::new(jobs::JobQueue::new(config.jobs)),
+        rotations,
+    };
 
     let cors = Cors::new()
         .allow_origin(cors::Any)
-        .allow_methods([Method::GET])
+        .allow_methods([Method::GET, Method::POST])
         .allow_headers(vec![
+            "Content-Type",
+            "Authorization",
             "Access-Control-Request-Method",
             "Access-Control-Allow-Origin",
             "Access-Control-Allow-Headers",
@@ -83,6 +135,9 @@ async fn main() {
 
     let router = Router::with_hoop(cors)
         .hoop(affix_state::inject(state))
+        .push(solver_router)
+        .push(jobs_router)
+        .push(rotations_router)
         .push(
             Router::with_path("{lang}")
                 .push(Router::with_path("recipe_level_table").get(recipe_level_table))
@@ -91,40 +146,54 @@ async fn main() {
                         .get(recipe_level_table_by_job_level),
                 )
                 .push(Router::with_path("recipe_table").get(recipe_table))
+                .push(Router::with_path("search_recipes").get(search_recipes))
                 .push(Router::with_path("recipe_info").get(recipe_info))
                 .push(Router::with_path("recipes_ingredientions").get(recipes_ingredientions))
+                .push(Router::with_path("item_sources").get(item_sources))
                 .push(Router::with_path("recipe_collectability").get(recipe_collectability))
                 .push(Router::with_path("item_info").get(item_info))
                 .push(Router::with_path("craft_type").get(craft_type))
+                .push(Router::with_path("recipe_notebooks").get(recipe_notebooks))
                 .push(Router::with_path("medicine_table").get(medicine_table))
                 .push(Router::with_path("meals_table").get(meals_table))
-                .push(Router::with_path("temporary_action_info").get(temporary_action_info)),
+                .push(Router::with_path("temporary_action_info").get(temporary_action_info))
+                .push(Router::with_path("data_info").get(data_info))
+                .push(
+                    Router::new()
+                        .hoop(max_size(max_body_size))
+                        .push(Router::with_path("bom").post(resolve_bom))
+                        .push(Router::with_path("crafting_plan").post(crafting_plan))
+                        .push(Router::with_path("gear_attributes").post(gear_attributes))
+                        .push(Router::with_path("optimize_melds").post(optimize_melds))
+                        .push(Router::with_path("initial_quality").post(initial_quality)),
+                ),
         );
     let listener = TcpListener::new(server_url);
     let acceptor = listener.bind().await;
     Server::new(acceptor).serve(router).await;
 }
 
-#[derive(FromQueryResult, Serialize)]
-struct RecipeInfo {
-    id: u32,
-    rlv: u32,
-    item_id: u32,
-    item_name: String,
-    item_amount: u8,
-    job: String,
-
-    difficulty_factor: u16,
-    quality_factor: u16,
-    durability_factor: u16,
-    material_quality_factor: u8,
-
-    required_craftsmanship: u16,
-    required_control: u16,
-
-    can_hq: bool,
-    is_expert: bool,
-    recipe_notebook_list: u32,
+/// 返回路径中语言对应的数据库连接，以及查询翻译表时使用的语言代码
+fn lang_conn<'a, 'b>(
+    req: &'b Request,
+    depot: &'a Depot,
+) -> Result<(&'a DatabaseConnection, Option<&'b str>)> {
+    let state = depot
+        .obtain::<AppState>()
+        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))?;
+    let lang = req.params().get::<str>("lang").unwrap();
+    let db = state
+        .connections
+        .get(lang)
+        .ok_or_else(|| StatusError::bad_request().detail("Unknown language"))?;
+    Ok((&db.conn, db.translations.then_some(lang.as_str())))
+}
+
+fn db_error(msg: &'static str) -> impl FnOnce(DbErr) -> StatusError {
+    move |err| {
+        println!("{msg}: {err}");
+        StatusError::internal_server_error().detail(msg)
+    }
 }
 
 // rlv: i32,
@@ -134,21 +203,13 @@ async fn recipe_level_table(
     depot: &mut Depot,
     res: &mut Response,
 ) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, _) = lang_conn(req, depot)?;
     let rlv = req
         .query::<u32>("rlv")
         .ok_or_else(|| StatusError::bad_request())?;
-    let Some(rt) = RecipeLevelTables::find_by_id(rlv)
-        .one(conn)
+    let Some(rt) = queries::recipe_level_table(conn, rlv)
         .await
-        .map_err(|_| StatusError::internal_server_error())?
+        .map_err(db_error("Failed to get recipe level table"))?
     else {
         return Err(StatusError::bad_gateway());
     };
@@ -163,23 +224,13 @@ async fn recipe_level_table_by_job_level(
     depot: &mut Depot,
     res: &mut Response,
 ) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, _) = lang_conn(req, depot)?;
     let job_level = req
         .query::<u32>("job_level")
         .ok_or_else(|| StatusError::bad_request())?;
-    let result = RecipeLevelTables::find()
-        .filter(recipe_level_tables::Column::ClassJobLevel.eq(job_level))
-        .order_by_asc(recipe_level_tables::Column::Id)
-        .one(conn)
+    let result = queries::recipe_level_table_by_job_level(conn, job_level)
         .await
-        .map_err(|_| StatusError::internal_server_error())?;
+        .map_err(db_error("Failed to get recipe level table"))?;
     res.render(Json(result));
     Ok(())
 }
@@ -188,135 +239,68 @@ async fn recipe_level_table_by_job_level(
 // search_name: String,
 #[handler]
 async fn recipe_table(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, lang) = lang_conn(req, depot)?;
     let page_id = req
         .query::<u64>("page_id")
         .ok_or_else(|| StatusError::bad_request().detail("Need 'page_id'"))?;
     let search_name = req
         .query::<String>("search_name")
         .ok_or_else(|| StatusError::bad_request().detail("Need 'search_name'"))?;
+    let (data, p) = queries::recipe_table(conn, page_id, &search_name, &recipe_filter(req), lang)
+        .await
+        .map_err(db_error("Failed to get recipe data"))?;
+    res.render(Json(RecipeTableResp { data, p }));
+    Ok(())
+}
 
-    let wks_ids = WksMissionRecipe::find()
-        .join(
-            JoinType::InnerJoin,
-            wks_mission_recipe::Relation::WksMissionUnit.def(),
-        )
-        .filter(wks_mission_unit::Column::Name.like(&search_name))
-        .all(conn)
+// page_id: u64,
+// query: String,
+#[handler]
+async fn search_recipes(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let (conn, lang) = lang_conn(req, depot)?;
+    let page_id = req.query::<u64>("page_id").unwrap_or(0);
+    let query = req.query::<String>("query").unwrap_or_default();
+    let (data, p) = queries::search_recipes(conn, page_id, &query, &recipe_filter(req), lang)
         .await
-        .map_err(|err| {
-            println!("Failed to search mission recipes: {err}");
-            StatusError::internal_server_error().detail("Failed to search mission recipes")
-        })?
-        .into_iter()
-        .flat_map(|r| {
-            [
-                r.recipe0_id,
-                r.recipe1_id,
-                r.recipe2_id,
-                r.recipe3_id,
-                r.recipe4_id,
-            ]
-        })
-        .flatten();
-
-    let mut query = Recipes::find()
-        .join(JoinType::InnerJoin, recipes::Relation::CraftTypes.def())
-        .join(JoinType::InnerJoin, recipes::Relation::ItemResultItem.def())
-        .join(
-            JoinType::InnerJoin,
-            recipes::Relation::RecipeLevelTables.def(),
-        )
-        .filter(
-            items::Column::Name
-                .like(&search_name)
-                .or(recipes::Column::Id.is_in(wks_ids)),
-        );
-    if let Some(rlv) = req.query::<u32>("rlv") {
-        query = query.filter(recipes::Column::RecipeLevelId.eq(rlv))
-    }
-    if let Some(craft_type_id) = req.query::<u32>("craft_type_id") {
-        query = query.filter(recipes::Column::CraftTypeId.eq(craft_type_id))
-    }
-    if let Some(job_level_min) = req.query::<u32>("job_level_min") {
-        query = query.filter(recipe_level_tables::Column::ClassJobLevel.gte(job_level_min))
-    }
-    if let Some(job_level_max) = req.query::<u32>("job_level_max") {
-        query = query.filter(recipe_level_tables::Column::ClassJobLevel.lte(job_level_max))
-    }
-    let query = query
-        .select_only()
-        .column_as(recipes::Column::Id, "id")
-        .column_as(recipes::Column::RecipeLevelId, "rlv")
-        .column_as(items::Column::Id, "item_id")
-        .column_as(items::Column::Name, "item_name")
-        .column_as(recipes::Column::ItemResultAmount, "item_amount")
-        .column_as(craft_types::Column::Name, "job")
-        .column_as(recipes::Column::DifficultyFactor, "difficulty_factor")
-        .column_as(recipes::Column::QualityFactor, "quality_factor")
-        .column_as(recipes::Column::DurabilityFactor, "durability_factor")
-        .column_as(
-            recipes::Column::MaterialQualityFactor,
-            "material_quality_factor",
-        )
-        .column_as(
-            recipes::Column::RequiredCraftsmanship,
-            "required_craftsmanship",
-        )
-        .column_as(recipes::Column::RequiredControl, "required_control")
-        .column_as(recipes::Column::CanHq, "can_hq")
-        .column_as(recipes::Column::IsExpert, "is_expert")
-        .column_as(recipes::Column::RecipeNotebookList, "recipe_notebook_list")
-        .order_by(recipes::Column::Id, Order::Asc);
-    let paginate = query.into_model::<RecipeInfo>().paginate(conn, 200);
-
-    let p = paginate.num_pages().await.map_err(|err| {
-        println!("Failed to get total page numbers: {err}");
-        StatusError::internal_server_error().detail("Failed to get total page numbers")
-    })?;
-    let data = paginate.fetch_page(page_id).await.map_err(|err| {
-        println!("Failed to get recipe data: {err}");
-        StatusError::internal_server_error().detail("Failed to get recipe data")
-    })?;
-
-    #[derive(Serialize)]
-    struct Resp {
-        data: Vec<RecipeInfo>,
-        p: u64,
-    }
-    res.render(Json(Resp { data, p }));
+        .map_err(db_error("Failed to search recipes"))?;
+    res.render(Json(RecipeTableResp { data, p }));
     Ok(())
 }
 
+fn recipe_filter(req: &Request) -> queries::RecipeFilter {
+    queries::RecipeFilter {
+        recipe_level: req.query::<u32>("rlv"),
+        craft_type_id: req.query::<u32>("craft_type_id"),
+        job_level_min: req.query::<u32>("job_level_min"),
+        job_level_max: req.query::<u32>("job_level_max"),
+        expansion: req.query::<u32>("expansion"),
+        notebook_division_id: req.query::<u32>("notebook_division_id"),
+        secret_recipe_book_id: req.query::<u32>("secret_recipe_book_id"),
+    }
+}
+
+#[derive(Serialize)]
+struct RecipeTableResp {
+    data: Vec<queries::RecipeInfo>,
+    p: u64,
+}
+
 #[handler]
 async fn craft_type(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
-    let query = CraftTypes::find()
-        .column_as(craft_types::Column::Id, "id")
-        .column_as(craft_types::Column::Name, "name")
-        .order_by(craft_types::Column::Id, Order::Asc);
-    let result = query
-        .into_model::<craft_types::Model>()
-        .all(conn)
+    let (conn, lang) = lang_conn(req, depot)?;
+    let result = queries::craft_types(conn, lang)
         .await
-        .map_err(|err| {
-            println!("Failed to get craft type list: {err}");
-            StatusError::internal_server_error().detail("Failed to get craft type list")
-        })?;
+        .map_err(db_error("Failed to get craft type list"))?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn recipe_notebooks(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let (conn, lang) = lang_conn(req, depot)?;
+    let result = queries::recipe_notebooks(conn, lang)
+        .await
+        .map_err(db_error("Failed to get recipe notebooks"))?;
     res.render(Json(result));
     Ok(())
 }
@@ -328,124 +312,93 @@ async fn recipes_ingredientions(
     depot: &mut Depot,
     res: &mut Response,
 ) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, _) = lang_conn(req, depot)?;
     let recipe_id = req
         .query::<u32>("recipe_id")
         .ok_or_else(|| StatusError::bad_request())?;
-
-    let recipe = Recipes::find_by_id(recipe_id)
-        .one(conn)
+    let result = queries::recipe_ingredients(conn, recipe_id)
         .await
-        .map_err(|_| StatusError::internal_server_error())?
+        .map_err(db_error("Failed to get recipe ingredients"))?
         .ok_or_else(|| StatusError::bad_request().detail("Recipe not found"))?;
-    let ingredients = [
-        (recipe.ingredient0, recipe.ingredient_amount0),
-        (recipe.ingredient1, recipe.ingredient_amount1),
-        (recipe.ingredient2, recipe.ingredient_amount2),
-        (recipe.ingredient3, recipe.ingredient_amount3),
-        (recipe.ingredient4, recipe.ingredient_amount4),
-        (recipe.ingredient5, recipe.ingredient_amount5),
-        (recipe.ingredient6, recipe.ingredient_amount6),
-        (recipe.ingredient7, recipe.ingredient_amount7),
-    ];
-    let mut needs = BTreeMap::new();
-    for (id, amount) in ingredients {
-        if id != 0 {
-            needs
-                .entry(id)
-                .and_modify(|e| *e += amount)
-                .or_insert(amount);
-        }
-    }
-    let result: Vec<_> = needs.into_iter().collect();
     res.render(Json(result));
     Ok(())
 }
 
 #[handler]
 async fn recipe_info(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, lang) = lang_conn(req, depot)?;
     let recipe_id = req
         .query::<u32>("recipe_id")
         .ok_or_else(|| StatusError::bad_request().detail("Need 'recipe_id'"))?;
-    let result = Recipes::find_by_id(recipe_id)
-        .join(JoinType::InnerJoin, recipes::Relation::CraftTypes.def())
-        .join(JoinType::InnerJoin, recipes::Relation::ItemResultItem.def())
-        .join(
-            JoinType::InnerJoin,
-            recipes::Relation::RecipeLevelTables.def(),
-        )
-        .select_only()
-        .column_as(recipes::Column::Id, "id")
-        .column_as(recipes::Column::RecipeLevelId, "rlv")
-        .column_as(items::Column::Id, "item_id")
-        .column_as(items::Column::Name, "item_name")
-        .column_as(recipes::Column::ItemResultAmount, "item_amount")
-        .column_as(craft_types::Column::Name, "job")
-        .column_as(recipes::Column::DifficultyFactor, "difficulty_factor")
-        .column_as(recipes::Column::QualityFactor, "quality_factor")
-        .column_as(recipes::Column::DurabilityFactor, "durability_factor")
-        .column_as(
-            recipes::Column::MaterialQualityFactor,
-            "material_quality_factor",
-        )
-        .column_as(
-            recipes::Column::RequiredCraftsmanship,
-            "required_craftsmanship",
-        )
-        .column_as(recipes::Column::RequiredControl, "required_control")
-        .column_as(recipes::Column::CanHq, "can_hq")
-        .column_as(recipes::Column::IsExpert, "is_expert")
-        .column_as(recipes::Column::RecipeNotebookList, "recipe_notebook_list")
-        .into_model::<RecipeInfo>()
-        .one(conn)
+    let result = queries::recipe_info(conn, recipe_id, lang)
         .await
-        .map_err(|e| {
-            println!("recipe_info error: {e:?}");
-            StatusError::internal_server_error()
-        })?
+        .map_err(db_error("Failed to get recipe info"))?
         .ok_or_else(|| StatusError::bad_request().detail("Recipe not found"))?;
     res.render(Json(result));
     Ok(())
 }
 
+#[derive(Deserialize)]
+struct InitialQualityRequest {
+    recipe_id: u32,
+    /// 物品id到HQ数量的映射
+    #[serde(default)]
+    hq_amounts: BTreeMap<u32, u32>,
+}
+
+#[handler]
+async fn initial_quality(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let request: InitialQualityRequest = solver::parse(req).await?;
+    let (conn, _) = lang_conn(req, depot)?;
+    let result = queries::initial_quality(conn, request.recipe_id, &request.hq_amounts)
+        .await
+        .map_err(db_error("Failed to get recipe material quality"))?
+        .ok_or_else(|| StatusError::bad_request().detail("Recipe not found"))?;
+    res.render(Json(result));
+    Ok(())
+}
+
+// item_ids: 以逗号分隔的物品id
+#[handler]
+async fn item_sources(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let (conn, lang) = lang_conn(req, depot)?;
+    let item_ids = req
+        .query::<String>("item_ids")
+        .ok_or_else(|| StatusError::bad_request().detail("Need 'item_ids'"))?
+        .split(',')
+        .map(|id| id.trim().parse::<u32>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|err| StatusError::bad_request().detail(err.to_string()))?;
+    let result = queries::item_sources(conn, &item_ids, lang)
+        .await
+        .map_err(db_error("Failed to get item sources"))?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn data_info(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let (conn, _) = lang_conn(req, depot)?;
+    let result = version::data_info(conn)
+        .await
+        .map_err(db_error("Failed to get data info"))?;
+    res.render(Json(result));
+    Ok(())
+}
+
 #[handler]
 async fn recipe_collectability(
     req: &mut Request,
     depot: &mut Depot,
     res: &mut Response,
 ) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, _) = lang_conn(req, depot)?;
     let recipe_id = req
         .query::<u32>("recipe_id")
         .ok_or_else(|| StatusError::bad_request().detail("Need 'recipe_id'"))?;
-    let result = CollectablesShopRefine::find()
-        .reverse_join(Recipes)
-        .filter(recipes::Column::Id.eq(recipe_id))
-        .one(conn)
+    let result = queries::recipe_collectability(conn, recipe_id)
         .await
-        .map_err(|_| StatusError::internal_server_error())?;
+        .map_err(db_error("Failed to get recipe collectability"))?;
     res.render(Json(result));
     Ok(())
 }
@@ -453,160 +406,36 @@ async fn recipe_collectability(
 // item_id: i32
 #[handler]
 async fn item_info(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, lang) = lang_conn(req, depot)?;
     let item_id = req
         .query::<u32>("item_id")
         .ok_or_else(|| StatusError::bad_request())?;
-    let result = Items::find_by_id(item_id)
-        .one(conn)
+    let result = queries::item_info(conn, item_id, lang)
         .await
-        .map_err(|_| StatusError::internal_server_error())?
+        .map_err(db_error("Failed to get item info"))?
         .ok_or_else(|| StatusError::bad_gateway())?;
     res.render(Json(result));
     Ok(())
 }
 
-#[derive(FromQueryResult, Debug)]
-struct ItemFoodAction {
-    name: String,
-    level: u32,
-    item_food_id: u16,
-    #[allow(dead_code)]
-    item_food_duration: u16,
-}
-
-#[derive(Default, Serialize)]
-struct Enhancer {
-    name: String,
-    level: u32,
-    is_hq: bool,
-
-    cm: Option<i8>,
-    cm_max: Option<i16>,
-    ct: Option<i8>,
-    ct_max: Option<i16>,
-    cp: Option<i8>,
-    cp_max: Option<i16>,
-}
-
-const MEDICINE_SEARCH_ID: u32 = 43;
-const MEALS_SEARCH_ID: u32 = 45;
-
 #[handler]
 async fn medicine_table(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
-    res.render(Json(query_enhancers(conn, MEDICINE_SEARCH_ID).await?));
+    let (conn, lang) = lang_conn(req, depot)?;
+    let result = queries::medicine_table(conn, lang)
+        .await
+        .map_err(db_error("Failed to get medicine table"))?;
+    res.render(Json(result));
     Ok(())
 }
 
 #[handler]
 async fn meals_table(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
-    res.render(Json(query_enhancers(conn, MEALS_SEARCH_ID).await?));
-    Ok(())
-}
-
-async fn query_enhancers(conn: &DatabaseConnection, search_id: u32) -> Result<Vec<Enhancer>> {
-    let crafting_item_food = ItemFood::find()
-        .join(
-            JoinType::InnerJoin,
-            item_food::Relation::ItemFoodEffect.def(),
-        )
-        .select_with(ItemFoodEffect)
-        .filter(item_food_effect::Column::BaseParam.is_in([11, 70, 71]))
-        .all(conn)
-        .await
-        .map_err(|err| StatusError::internal_server_error().detail(err.to_string()))?
-        .into_iter()
-        .map(|(item_food, effects)| (item_food.id, effects));
-    let crafting_item_food = BTreeMap::from_iter(crafting_item_food);
-    let result = Items::find()
-        .select_only()
-        .select_column_as(items::Column::Name, "name")
-        .select_column_as(items::Column::Level, "level")
-        .filter(items::Column::ItemSearchCategoryId.eq(search_id))
-        .join(JoinType::InnerJoin, items::Relation::ItemAction.def())
-        .select_column_as(item_action::Column::Data2, "item_food_id")
-        .select_column_as(item_action::Column::Data3, "item_food_duration")
-        .filter(item_action::Column::Type.between(844, 846))
-        .filter(item_action::Column::Data2.is_in(crafting_item_food.iter().map(|v| *v.0)))
-        .into_model::<ItemFoodAction>()
-        .all(conn)
+    let (conn, lang) = lang_conn(req, depot)?;
+    let result = queries::meals_table(conn, lang)
         .await
-        .map_err(|err| StatusError::internal_server_error().detail(err.to_string()))?;
-    let result = result
-        .into_iter()
-        .flat_map(|item| {
-            let mut enh = Enhancer {
-                name: item.name.clone(),
-                level: item.level,
-                is_hq: false,
-                ..Enhancer::default()
-            };
-            let mut enh_hq = Enhancer {
-                name: item.name.clone(),
-                level: item.level,
-                is_hq: true,
-                ..Enhancer::default()
-            };
-            for item_food in crafting_item_food
-                .get(&(item.item_food_id as u32))
-                .into_iter()
-                .flatten()
-            {
-                match item_food.base_param {
-                    11 => {
-                        enh.cp = Some(item_food.value);
-                        enh.cp_max = Some(item_food.max);
-                        enh_hq.cp = Some(item_food.value_hq);
-                        enh_hq.cp_max = Some(item_food.max_hq);
-                    }
-                    70 => {
-                        enh.cm = Some(item_food.value);
-                        enh.cm_max = Some(item_food.max);
-                        enh_hq.cm = Some(item_food.value_hq);
-                        enh_hq.cm_max = Some(item_food.max_hq);
-                    }
-                    71 => {
-                        enh.ct = Some(item_food.value);
-                        enh.ct_max = Some(item_food.max);
-                        enh_hq.ct = Some(item_food.value_hq);
-                        enh_hq.ct_max = Some(item_food.max_hq);
-                    }
-                    _ => {}
-                }
-            }
-            [enh, enh_hq].into_iter()
-        })
-        .collect();
-    Ok(result)
-}
-
-#[derive(Default, Serialize, FromQueryResult)]
-struct TemporaryActionInfo {
-    action: u32,
-    count: u32,
+        .map_err(db_error("Failed to get meals table"))?;
+    res.render(Json(result));
+    Ok(())
 }
 
 // recipe_id: i32
@@ -616,43 +445,84 @@ async fn temporary_action_info(
     depot: &mut Depot,
     res: &mut Response,
 ) -> Result<()> {
-    let state = depot
-        .obtain::<AppState>()
-        .map_err(|_| StatusError::internal_server_error())?;
-    let lang = req.params().get::<str>("lang").unwrap();
-    let conn = state
-        .connections
-        .get(lang)
-        .ok_or_else(|| StatusError::bad_request())?;
+    let (conn, _) = lang_conn(req, depot)?;
     let recipe_id = req
         .query::<u32>("recipe_id")
         .ok_or_else(|| StatusError::bad_request())?;
-    let query = WksMissionUnit::find()
-        .join(
-            JoinType::InnerJoin,
-            wks_mission_unit::Relation::WksMissionRecipe.def(),
-        )
-        .join(
-            JoinType::LeftJoin,
-            wks_mission_unit::Relation::WksMissionToDo3.def(),
-        )
-        .filter(
-            wks_mission_recipe::Column::Recipe0Id
-                .eq(recipe_id)
-                .or(wks_mission_recipe::Column::Recipe1Id.eq(recipe_id))
-                .or(wks_mission_recipe::Column::Recipe2Id.eq(recipe_id))
-                .or(wks_mission_recipe::Column::Recipe3Id.eq(recipe_id))
-                .or(wks_mission_recipe::Column::Recipe4Id.eq(recipe_id)),
-        )
-        .select_only()
-        .column_as(wks_mission_to_do::Column::TemporaryAction, "action")
-        .column_as(wks_mission_to_do::Column::TemporaryActionCount, "count");
-    let result = query
-        .into_model::<TemporaryActionInfo>()
-        .one(conn)
+    let result = queries::temporary_action_info(conn, recipe_id)
         .await
-        .map_err(|_| StatusError::internal_server_error())?
+        .map_err(db_error("Failed to get temporary action info"))?
         .ok_or_else(|| StatusError::not_found())?;
     res.render(Json(result));
     Ok(())
 }
+
+#[handler]
+async fn resolve_bom(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let request: bom::BomRequest = solver::parse(req).await?;
+    let (conn, lang) = lang_conn(req, depot)?;
+    let result = bom::resolve_bom(conn, &request, lang)
+        .await
+        .map_err(|err| match err {
+            bom::BomError::Db(err) => db_error("Failed to resolve bill of materials")(err),
+            err => StatusError::bad_request().detail(err.to_string()),
+        })?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn crafting_plan(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let request: bom::BomRequest = solver::parse(req).await?;
+    let (conn, lang) = lang_conn(req, depot)?;
+    let result = plan::crafting_plan(conn, &request, lang)
+        .await
+        .map_err(|err| match err {
+            bom::BomError::Db(err) => db_error("Failed to make crafting plan")(err),
+            err => StatusError::bad_request().detail(err.to_string()),
+        })?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn gear_attributes(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let gearset: Vec<gearset::EquippedGear> = solver::parse(req).await?;
+    let (conn, _) = lang_conn(req, depot)?;
+    let result = gearset::gear_attributes(conn, &gearset)
+        .await
+        .map_err(|err| match err {
+            gearset::GearsetError::Db(err) => db_error("Failed to compute gear attributes")(err),
+            err => StatusError::bad_request().detail(err.to_string()),
+        })?;
+    res.render(Json(result));
+    Ok(())
+}
+
+/// 根据装备与可购买的魔晶石挑选最便宜的镶嵌方案
+#[handler]
+async fn optimize_melds(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let request: gearset::MeldRequest = solver::parse(req).await?;
+    let (conn, _) = lang_conn(req, depot)?;
+    let gearset_error = |err: gearset::GearsetError| match err {
+        gearset::GearsetError::Db(err) => db_error("Failed to load gearset")(err),
+        err => StatusError::bad_request().detail(err.to_string()),
+    };
+    let gear = gearset::gear_pieces(conn, &request.gearset)
+        .await
+        .map_err(gearset_error)?;
+    let materia = gearset::materia_grades(conn, &request.materia)
+        .await
+        .map_err(gearset_error)?;
+    let time_budget = depot
+        .obtain::<AppState>()
+        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))?
+        .solver
+        .time_budget;
+    let result = solver::run_blocking(time_budget, move |_| {
+        melding::optimize(request.current, request.target, &gear, &materia)
+    })
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
@@ -0,0 +1,284 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 技能序列分享：保存后返回短id，可通过短id或配方id查询
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use app_libs::ffxiv_crafting::{Actions, Attributes};
+use rand::{RngExt, distr::Alphanumeric};
+use salvo::prelude::*;
+use salvo::size_limiter::max_size;
+use sea_orm::{Database, DatabaseConnection, DbErr, SqlErr, entity::*, query::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{AppState, Result, solver::parse};
+use app_db::shared_rotations::{self, Column, Entity as SharedRotations};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RotationsConfig {
+    /// 用于保存技能序列的数据库，需要可写
+    pub database: String,
+    /// 备注的最大长度（字符数）
+    #[serde(default = "default_max_notes_len")]
+    pub max_notes_len: usize,
+    /// 技能序列的最大长度
+    #[serde(default = "default_max_actions")]
+    pub max_actions: usize,
+    /// 每个客户端每小时最多保存的技能序列数量
+    #[serde(default = "default_max_saves_per_hour")]
+    pub max_saves_per_hour: usize,
+    /// 部署在反向代理之后时，从该请求头（如`X-Real-IP`）读取客户端地址
+    #[serde(default)]
+    pub real_ip_header: Option<String>,
+    /// 设置后，保存技能序列需要携带`Authorization: Bearer <write_token>`
+    #[serde(default)]
+    pub write_token: Option<String>,
+}
+
+fn default_max_notes_len() -> usize {
+    2000
+}
+
+fn default_max_actions() -> usize {
+    200
+}
+
+fn default_max_saves_per_hour() -> usize {
+    30
+}
+
+/// 短id的长度，62^8足以避免冲突
+const ID_LEN: usize = 8;
+
+/// 短id冲突时重新生成的最大次数
+const MAX_ID_ATTEMPTS: usize = 5;
+
+/// 每页返回的技能序列数量
+const PAGE_SIZE: u64 = 50;
+
+/// 限制保存频率的时间窗口
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(3600);
+
+pub struct RotationStore {
+    conn: DatabaseConnection,
+    config: RotationsConfig,
+    /// 每个客户端地址在当前时间窗口内的开始时间与保存次数
+    saves: Mutex<HashMap<String, (Instant, usize)>>,
+}
+
+impl RotationStore {
+    /// 连接数据库，并在表不存在时创建
+    pub async fn open(config: RotationsConfig) -> std::result::Result<Self, DbErr> {
+        let conn = Database::connect(&config.database).await?;
+        shared_rotations::create_table(&conn).await?;
+        Ok(Self {
+            conn,
+            config,
+            saves: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn check_token(&self, req: &Request) -> Result<()> {
+        let Some(token) = &self.config.write_token else {
+            return Ok(());
+        };
+        let authorized = req
+            .headers()
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| v == token);
+        if !authorized {
+            return Err(StatusError::unauthorized().detail("Invalid write token"));
+        }
+        Ok(())
+    }
+
+    fn client_addr(&self, req: &Request) -> String {
+        let header = self.config.real_ip_header.as_ref().and_then(|name| {
+            let value = req.headers().get(name)?.to_str().ok()?;
+            value.split(',').next().map(|v| v.trim().to_string())
+        });
+        header.unwrap_or_else(|| match req.remote_addr().clone().into_std() {
+            Some(addr) => addr.ip().to_string(),
+            None => String::new(),
+        })
+    }
+
+    /// 记录一次保存，超出频率限制时返回429
+    fn check_rate(&self, req: &Request) -> Result<()> {
+        let client = self.client_addr(req);
+        let now = Instant::now();
+        let mut saves = self.saves.lock().unwrap();
+        saves.retain(|_, (start, _)| now.duration_since(*start) < RATE_LIMIT_WINDOW);
+        let (_, count) = saves.entry(client).or_insert((now, 0));
+        if *count >= self.config.max_saves_per_hour {
+            return Err(StatusError::too_many_requests().detail("Too many rotations saved"));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+pub fn router(max_body_size: u64) -> Router {
+    Router::with_path("rotations")
+        .get(search_rotations)
+        .push(
+            Router::new()
+                .hoop(max_size(max_body_size))
+                .post(save_rotation),
+        )
+        .push(Router::with_path("{id}").get(rotation))
+}
+
+fn rotation_store(depot: &Depot) -> Result<&RotationStore> {
+    let state = depot
+        .obtain::<AppState>()
+        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))?;
+    state
+        .rotations
+        .as_deref()
+        .ok_or_else(|| StatusError::service_unavailable().detail("Rotation storage is disabled"))
+}
+
+#[derive(Deserialize)]
+struct SaveRequest {
+    recipe_id: u32,
+    attributes: Attributes,
+    actions: Vec<Actions>,
+    #[serde(default)]
+    notes: String,
+}
+
+#[derive(Serialize)]
+struct SaveResponse {
+    id: String,
+}
+
+#[handler]
+async fn save_rotation(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let store = rotation_store(depot)?;
+    store.check_token(req)?;
+    let request: SaveRequest = parse(req).await?;
+    let config = &store.config;
+    if request.actions.is_empty() || request.actions.len() > config.max_actions {
+        return Err(StatusError::bad_request().detail("Invalid number of actions"));
+    }
+    if request.notes.chars().count() > config.max_notes_len {
+        return Err(StatusError::bad_request().detail("Notes are too long"));
+    }
+    store.check_rate(req)?;
+    let attributes = serde_json::to_value(request.attributes)
+        .map_err(|err| StatusError::bad_request().detail(err.to_string()))?;
+    let actions = serde_json::to_value(&request.actions)
+        .map_err(|err| StatusError::bad_request().detail(err.to_string()))?;
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+
+    // 直接插入，短id冲突时重新生成，避免先查询后插入之间的竞争
+    for _ in 0..MAX_ID_ATTEMPTS {
+        let id = new_id();
+        let result = shared_rotations::ActiveModel {
+            id: Set(id.clone()),
+            recipe_id: Set(request.recipe_id),
+            attributes: Set(attributes.clone()),
+            actions: Set(actions.clone()),
+            notes: Set(request.notes.clone()),
+            created_at: Set(created_at),
+        }
+        .insert(&store.conn)
+        .await;
+        match result {
+            Ok(_) => {
+                res.status_code(StatusCode::CREATED);
+                res.render(Json(SaveResponse { id }));
+                return Ok(());
+            }
+            Err(err) if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                continue;
+            }
+            Err(err) => {
+                println!("Failed to save rotation: {err}");
+                return Err(StatusError::internal_server_error().detail("Failed to save rotation"));
+            }
+        }
+    }
+    Err(StatusError::internal_server_error().detail("Failed to allocate rotation id"))
+}
+
+fn new_id() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(ID_LEN)
+        .map(char::from)
+        .collect()
+}
+
+#[handler]
+async fn rotation(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let store = rotation_store(depot)?;
+    let id = req.params().get::<str>("id").unwrap();
+    let Some(model) = SharedRotations::find_by_id(id)
+        .one(&store.conn)
+        .await
+        .map_err(|err| {
+            println!("Failed to query rotation: {err}");
+            StatusError::internal_server_error().detail("Failed to query rotation")
+        })?
+    else {
+        return Err(StatusError::not_found().detail("Rotation doesn't exist"));
+    };
+    res.render(Json(model));
+    Ok(())
+}
+
+// recipe_id: u32,
+// page_id: u64,
+#[handler]
+async fn search_rotations(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let store = rotation_store(depot)?;
+    let recipe_id = req
+        .query::<u32>("recipe_id")
+        .ok_or_else(|| StatusError::bad_request().detail("Need 'recipe_id'"))?;
+    let page_id = req.query::<u64>("page_id").unwrap_or(0);
+
+    let paginate = SharedRotations::find()
+        .filter(Column::RecipeId.eq(recipe_id))
+        .order_by_desc(Column::CreatedAt)
+        .paginate(&store.conn, PAGE_SIZE);
+    let p = paginate.num_pages().await.map_err(|err| {
+        println!("Failed to get total page numbers: {err}");
+        StatusError::internal_server_error().detail("Failed to get total page numbers")
+    })?;
+    let data = paginate.fetch_page(page_id).await.map_err(|err| {
+        println!("Failed to search rotations: {err}");
+        StatusError::internal_server_error().detail("Failed to search rotations")
+    })?;
+
+    #[derive(Serialize)]
+    struct Resp {
+        data: Vec<shared_rotations::Model>,
+        p: u64,
+    }
+    res.render(Json(Resp { data, p }));
+    Ok(())
+}
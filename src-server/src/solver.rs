@@ -0,0 +1,486 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! 模拟与求解接口，供无法在浏览器中运行重型求解器的用户使用
+
+use std::sync::Arc;
+
+use app_libs::{
+    analyzer::{rand_simulations, scope_of_application},
+    ffxiv_crafting::{Actions, Status},
+    solver::{
+        AtomicFlag, CrafterProfile, depth_first_search_solver, normal_progress_solver,
+        pareto_solver::{self, ParetoRotation},
+        raphael, reflect_solver,
+    },
+    with_initial_quality,
+};
+use salvo::prelude::*;
+use salvo::size_limiter::max_size;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::{Duration, Instant},
+};
+
+use crate::{AppState, Result};
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SolverConfig {
+    /// 请求体的最大字节数
+    pub max_body_size: u64,
+    /// 单次请求允许的最长计算时间（秒）
+    pub time_budget: u64,
+    /// 随机模拟的最大次数
+    pub max_simulations: usize,
+    /// 深度优先与推进展求解器的最大搜索深度
+    pub max_depth: usize,
+    /// 玩家属性中制作力的上限，求解器的状态表大小与其成正比
+    pub max_craft_points: i32,
+    /// 配方耐久的上限
+    pub max_durability: u16,
+    /// 反射求解器状态表的最大字节数，由制作力、耐久与各`use_*`参数共同决定
+    pub max_table_bytes: usize,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            max_body_size: 64 * 1024,
+            time_budget: 30,
+            max_simulations: 10000,
+            max_depth: 8,
+            max_craft_points: 1000,
+            max_durability: 100,
+            max_table_bytes: 1 << 30,
+        }
+    }
+}
+
+impl SolverConfig {
+    /// 检查客户端提交的制作状态，避免构造过大的状态表或越界访问
+    pub fn check_status(&self, status: &Status) -> Result<()> {
+        let max_cp = status.attributes.craft_points;
+        if !(0..=self.max_craft_points).contains(&max_cp)
+            || !(0..=max_cp).contains(&status.craft_points)
+        {
+            return Err(StatusError::bad_request().detail("Invalid craft points"));
+        }
+        if status.recipe.durability > self.max_durability
+            || status.durability > status.recipe.durability
+        {
+            return Err(StatusError::bad_request().detail("Invalid durability"));
+        }
+        Ok(())
+    }
+
+    pub fn check_depth(&self, depth: usize) -> Result<()> {
+        if depth > self.max_depth {
+            return Err(StatusError::bad_request().detail("Depth too large"));
+        }
+        Ok(())
+    }
+}
+
+pub fn router(config: &SolverConfig) -> Router {
+    Router::new()
+        .hoop(max_size(config.max_body_size))
+        .push(Router::with_path("simulate").post(simulate))
+        .push(Router::with_path("simulate_detail").post(simulate_detail))
+        .push(Router::with_path("rand_simulation").post(rand_simulation))
+        .push(Router::with_path("calc_attributes_scope").post(calc_attributes_scope))
+        .push(Router::with_path("dfs_solve").post(dfs_solve))
+        .push(Router::with_path("nq_solve").post(nq_solve))
+        .push(Router::with_path("reflect_solve").post(reflect_solve))
+        .push(Router::with_path("raphael_solve").post(raphael_solve))
+        .push(Router::with_path("pareto_solve").post(pareto_solve))
+}
+
+fn app_state(depot: &Depot) -> Result<&AppState> {
+    depot
+        .obtain::<AppState>()
+        .map_err(|_| StatusError::internal_server_error().detail("Obtain AppState error"))
+}
+
+fn solver_config(depot: &Depot) -> Result<&SolverConfig> {
+    Ok(&app_state(depot)?.solver)
+}
+
+pub async fn parse<T: DeserializeOwned>(req: &mut Request) -> Result<T> {
+    req.parse_json::<T>()
+        .await
+        .map_err(|err| StatusError::bad_request().detail(err.to_string()))
+}
+
+/// 在阻塞线程池中执行计算，超出时间预算时设置中断标志并立即返回503。
+///
+/// 计算不会被强制终止，而是在后台运行到求解器响应中断为止。
+/// 只用于开销受请求大小限制的模拟接口，求解器使用[`run_solver`]
+pub async fn run_blocking<T: Send + 'static>(
+    time_budget: u64,
+    f: impl FnOnce(&AtomicFlag) -> T + Send + 'static,
+) -> Result<T> {
+    let deadline = Instant::now() + Duration::from_secs(time_budget);
+    spawn_until(deadline, None, f).await
+}
+
+/// 同[`run_blocking`]，但需要先从与任务队列共用的`workers`中取得许可。
+///
+/// 许可在计算真正结束后才释放，因此超时返回后仍在后台运行的求解器也计入并发数量
+pub async fn run_solver<T: Send + 'static>(
+    workers: &Arc<Semaphore>,
+    time_budget: u64,
+    f: impl FnOnce(&AtomicFlag) -> T + Send + 'static,
+) -> Result<T> {
+    let deadline = Instant::now() + Duration::from_secs(time_budget);
+    let permit = match tokio::time::timeout_at(deadline, workers.clone().acquire_owned()).await {
+        Ok(Ok(permit)) => permit,
+        Ok(Err(_)) => {
+            return Err(StatusError::internal_server_error().detail("Solver workers closed"));
+        }
+        Err(_) => return Err(StatusError::service_unavailable().detail("Too many running solvers")),
+    };
+    spawn_until(deadline, Some(permit), f).await
+}
+
+/// 在阻塞线程池中执行计算并持有`permit`直到计算结束，到达`deadline`时设置中断标志并返回503
+pub async fn spawn_until<T: Send + 'static>(
+    deadline: Instant,
+    permit: Option<OwnedSemaphorePermit>,
+    f: impl FnOnce(&AtomicFlag) -> T + Send + 'static,
+) -> Result<T> {
+    let interrupt = AtomicFlag::new();
+    let task = tokio::task::spawn_blocking({
+        let interrupt = interrupt.clone();
+        move || {
+            let _permit = permit;
+            f(&interrupt)
+        }
+    });
+    match tokio::time::timeout_at(deadline, task).await {
+        Ok(result) => result.map_err(task_failed),
+        Err(_) => {
+            interrupt.set();
+            Err(StatusError::service_unavailable().detail("Time budget exceeded"))
+        }
+    }
+}
+
+fn task_failed(err: tokio::task::JoinError) -> StatusError {
+    println!("Solver task failed: {err}");
+    StatusError::internal_server_error().detail("Solver task failed")
+}
+
+#[derive(Deserialize)]
+struct SimulateRequest {
+    status: Status,
+    actions: Vec<Actions>,
+}
+
+#[handler]
+async fn simulate(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let time_budget = solver_config(depot)?.time_budget;
+    let SimulateRequest { status, actions } = parse(req).await?;
+    let result = run_blocking(time_budget, move |_| app_libs::simulate(status, actions)).await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn simulate_detail(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let time_budget = solver_config(depot)?.time_budget;
+    let SimulateRequest { status, actions } = parse(req).await?;
+    let result = run_blocking(time_budget, move |_| {
+        app_libs::simulate_detail(status, actions)
+    })
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn calc_attributes_scope(
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+) -> Result<()> {
+    let time_budget = solver_config(depot)?.time_budget;
+    let SimulateRequest { status, actions } = parse(req).await?;
+    let result = run_blocking(time_budget, move |_| {
+        scope_of_application::calc_scope(status, &actions)
+    })
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct RandSimulationRequest {
+    status: Status,
+    actions: Vec<Actions>,
+    n: usize,
+    ignore_errors: bool,
+}
+
+#[handler]
+async fn rand_simulation(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let config = solver_config(depot)?;
+    let (time_budget, max_simulations) = (config.time_budget, config.max_simulations);
+    let RandSimulationRequest {
+        status,
+        actions,
+        n,
+        ignore_errors,
+    } = parse(req).await?;
+    if n > max_simulations {
+        return Err(StatusError::bad_request().detail("Too many simulations"));
+    }
+    let result = run_blocking(time_budget, move |_| {
+        rand_simulations::stat(status, &actions, n, ignore_errors)
+    })
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DfsSolveRequest {
+    pub status: Status,
+    depth: usize,
+    /// 初期品质，未指定时使用`status`中的品质
+    #[serde(default)]
+    initial_quality: Option<u32>,
+    #[serde(default)]
+    profile: CrafterProfile,
+}
+
+impl DfsSolveRequest {
+    pub fn check(&self, config: &SolverConfig) -> Result<()> {
+        config.check_status(&self.status)?;
+        config.check_depth(self.depth)
+    }
+
+    pub fn dfs(self, interrupt: &AtomicFlag) -> Vec<Actions> {
+        depth_first_search_solver::solve_interruptible(
+            with_initial_quality(self.status, self.initial_quality),
+            self.depth,
+            self.profile,
+            interrupt,
+        )
+    }
+
+    pub fn nq(self, interrupt: &AtomicFlag) -> Vec<Actions> {
+        normal_progress_solver::solve_interruptible(
+            with_initial_quality(self.status, self.initial_quality),
+            self.depth,
+            self.profile,
+            interrupt,
+        )
+    }
+}
+
+#[handler]
+async fn dfs_solve(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = app_state(depot)?;
+    let request: DfsSolveRequest = parse(req).await?;
+    request.check(&state.solver)?;
+    let result = run_solver(
+        state.jobs.workers(),
+        state.solver.time_budget,
+        move |interrupt| request.dfs(interrupt),
+    )
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[handler]
+async fn nq_solve(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = app_state(depot)?;
+    let request: DfsSolveRequest = parse(req).await?;
+    request.check(&state.solver)?;
+    let result = run_solver(
+        state.jobs.workers(),
+        state.solver.time_budget,
+        move |interrupt| request.nq(interrupt),
+    )
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReflectSolveRequest {
+    pub status: Status,
+    use_manipulation: bool,
+    use_waste_not: usize,
+    use_observe: bool,
+    #[serde(default)]
+    initial_quality: Option<u32>,
+    #[serde(default)]
+    profile: CrafterProfile,
+}
+
+impl ReflectSolveRequest {
+    pub fn check(&self, config: &SolverConfig) -> Result<()> {
+        config.check_status(&self.status)?;
+        let (mn, wn) = self.table_params();
+        let bytes =
+            reflect_solver::table_bytes(&self.status, mn, wn, self.use_observe, &self.profile);
+        if bytes > config.max_table_bytes {
+            return Err(StatusError::bad_request().detail("Solver table too large"));
+        }
+        Ok(())
+    }
+
+    /// 与[`reflect_solver::solve`]一致的掌握与俭约参数
+    fn table_params(&self) -> (bool, usize) {
+        let buffs = &self.status.buffs;
+        let mn = self.use_manipulation || buffs.manipulation > 0;
+        let wn = (buffs.wast_not.max(buffs.wast_not_ii) as usize).max(self.use_waste_not()) + 1;
+        (mn, wn)
+    }
+
+    /// 俭约的效果最多持续8步，更大的值只会增大状态表
+    fn use_waste_not(&self) -> usize {
+        self.use_waste_not.min(8)
+    }
+
+    /// 该求解器不支持中断，其耗时由[`Self::check`]限制的状态表大小决定；
+    /// 超时后请求会立即返回，求解器在后台结束后才释放所占的许可
+    pub fn solve(self, _interrupt: &AtomicFlag) -> Vec<Actions> {
+        let use_waste_not = self.use_waste_not();
+        reflect_solver::solve(
+            with_initial_quality(self.status, self.initial_quality),
+            self.use_manipulation,
+            use_waste_not,
+            self.use_observe,
+            self.profile,
+        )
+    }
+}
+
+#[handler]
+async fn reflect_solve(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = app_state(depot)?;
+    let request: ReflectSolveRequest = parse(req).await?;
+    request.check(&state.solver)?;
+    let result = run_solver(
+        state.jobs.workers(),
+        state.solver.time_budget,
+        move |interrupt| request.solve(interrupt),
+    )
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RaphaelSolveRequest {
+    pub status: Status,
+    target_quality: Option<u32>,
+    use_manipulation: bool,
+    use_trained_eye: bool,
+    backload_progress: bool,
+    adversarial: bool,
+    #[serde(default)]
+    initial_quality: Option<u32>,
+    #[serde(default)]
+    profile: CrafterProfile,
+}
+
+impl RaphaelSolveRequest {
+    pub fn check(&self, config: &SolverConfig) -> Result<()> {
+        config.check_status(&self.status)
+    }
+
+    pub fn solve(self, interrupt: &AtomicFlag) -> Vec<Actions> {
+        raphael::solve_interruptible(
+            with_initial_quality(self.status, self.initial_quality),
+            self.target_quality,
+            self.use_manipulation,
+            self.use_trained_eye,
+            self.backload_progress,
+            self.adversarial,
+            self.profile,
+            interrupt,
+        )
+    }
+}
+
+#[handler]
+async fn raphael_solve(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = app_state(depot)?;
+    let request: RaphaelSolveRequest = parse(req).await?;
+    request.check(&state.solver)?;
+    let result = run_solver(
+        state.jobs.workers(),
+        state.solver.time_budget,
+        move |interrupt| request.solve(interrupt),
+    )
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ParetoSolveRequest {
+    pub status: Status,
+    samples: usize,
+    use_manipulation: bool,
+    use_trained_eye: bool,
+    #[serde(default)]
+    initial_quality: Option<u32>,
+    #[serde(default)]
+    profile: CrafterProfile,
+}
+
+impl ParetoSolveRequest {
+    pub fn check(&self, config: &SolverConfig) -> Result<()> {
+        config.check_status(&self.status)?;
+        if self.samples > pareto_solver::MAX_SAMPLES {
+            return Err(StatusError::bad_request().detail("Too many samples"));
+        }
+        Ok(())
+    }
+
+    pub fn solve(self, interrupt: &AtomicFlag) -> Vec<ParetoRotation> {
+        pareto_solver::solve_interruptible(
+            with_initial_quality(self.status, self.initial_quality),
+            self.samples,
+            self.use_manipulation,
+            self.use_trained_eye,
+            self.profile,
+            interrupt,
+        )
+    }
+}
+
+#[handler]
+async fn pareto_solve(req: &mut Request, depot: &mut Depot, res: &mut Response) -> Result<()> {
+    let state = app_state(depot)?;
+    let request: ParetoSolveRequest = parse(req).await?;
+    request.check(&state.solver)?;
+    let result = run_solver(
+        state.jobs.workers(),
+        state.solver.time_budget,
+        move |interrupt| request.solve(interrupt),
+    )
+    .await?;
+    res.render(Json(result));
+    Ok(())
+}
@@ -26,24 +26,25 @@ use std::{
 
 use app_libs::{
     SimulateOneStepResult, SimulateResult,
-    analyzer::{rand_simulations, scope_of_application::Scope},
+    analyzer::{advisor, rand_simulations, scope_of_application::Scope},
+    enhancer::{self, Enhancer, EnhancerChoice, OptimizeTarget},
     ffxiv_crafting::{Actions, Attributes, CastActionError, Recipe, Status},
+    melding::{self, GearPiece, MateriaGrade, MeldPlan, Stats},
     solver::{
-        Solver, SolverHash, depth_first_search_solver, normal_progress_solver, raphael,
-        reflect_solver,
+        CrafterProfile, Solver, SolverHash, depth_first_search_solver, normal_progress_solver,
+        pareto_solver, raphael, reflect_solver,
     },
+    with_initial_quality,
 };
 use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
 use rand::rng;
-use sea_orm::{Database, DatabaseConnection, FromQueryResult, entity::*, query::*};
-use serde::Serialize;
+use sea_orm::{Database, DatabaseConnection};
 use tauri::{Manager, Theme, path::BaseDirectory, webview::WebviewWindow};
 use tokio::sync::{Mutex, OnceCell};
 
 use app_db::{
-    collectables_shop_refine, craft_types, item_action, item_food, item_food_effect, items,
-    prelude::*, recipe_level_tables, recipes, wks_mission_recipe, wks_mission_to_do,
-    wks_mission_unit,
+    bom, collectables_shop_refine, craft_types, gearset, items, plan, queries, recipe_level_tables,
+    version,
 };
 
 /// 创建新的Recipe对象，蕴含了模拟一次制作过程所必要的全部配方信息
@@ -54,14 +55,10 @@ async fn recipe_level_table(
     app_handle: tauri::AppHandle,
 ) -> Result<recipe_level_tables::Model, String> {
     let db = app_state.get_db(app_handle).await.map_err(err_to_string)?;
-    let Some(rt) = RecipeLevelTables::find_by_id(rlv)
-        .one(db)
+    queries::recipe_level_table(db, rlv)
         .await
         .map_err(err_to_string)?
-    else {
-        return Err(String::from("unknown-recipe-level"));
-    };
-    Ok(rt)
+        .ok_or_else(|| String::from("unknown-recipe-level"))
 }
 
 #[tauri::command(async)]
@@ -114,367 +111,312 @@ fn err_to_string<T: ToString>(v: T) -> String {
     v.to_string()
 }
 
-#[derive(FromQueryResult, Serialize)]
-struct RecipeInfo {
-    id: i32,
-    rlv: i32,
-    item_id: i32,
-    item_name: String,
-    item_amount: i32,
-    job: String,
-
-    difficulty_factor: u16,
-    quality_factor: u16,
-    durability_factor: u16,
-    material_quality_factor: u8,
-
-    required_craftsmanship: u16,
-    required_control: u16,
-
-    can_hq: bool,
-    is_expert: bool,
-    recipe_notebook_list: u32,
-}
-
 #[tauri::command(async)]
 async fn recipe_table(
     page_id: u64,
     search_name: String,
-    craft_type_id: Option<i32>,
-    recipe_level: Option<i32>,
-    job_level_min: Option<i32>,
-    job_level_max: Option<i32>,
+    craft_type_id: Option<u32>,
+    recipe_level: Option<u32>,
+    job_level_min: Option<u32>,
+    job_level_max: Option<u32>,
+    expansion: Option<u32>,
+    notebook_division_id: Option<u32>,
+    secret_recipe_book_id: Option<u32>,
+    lang: Option<String>,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
-) -> Result<(Vec<RecipeInfo>, u64), String> {
+) -> Result<(Vec<queries::RecipeInfo>, u64), String> {
     let db = app_state.get_db(app_handle).await.map_err(err_to_string)?;
+    let filter = queries::RecipeFilter {
+        recipe_level,
+        craft_type_id,
+        job_level_min,
+        job_level_max,
+        expansion,
+        notebook_division_id,
+        secret_recipe_book_id,
+    };
+    queries::recipe_table(db, page_id, &search_name, &filter, lang.as_deref())
+        .await
+        .map_err(err_to_string)
+}
 
-    let wks_ids = WksMissionRecipe::find()
-        .join(
-            JoinType::InnerJoin,
-            wks_mission_recipe::Relation::WksMissionUnit.def(),
-        )
-        .filter(wks_mission_unit::Column::Name.like(&search_name))
-        .all(db)
+#[tauri::command(async)]
+async fn search_recipes(
+    page_id: u64,
+    query: String,
+    craft_type_id: Option<u32>,
+    recipe_level: Option<u32>,
+    job_level_min: Option<u32>,
+    job_level_max: Option<u32>,
+    expansion: Option<u32>,
+    notebook_division_id: Option<u32>,
+    secret_recipe_book_id: Option<u32>,
+    lang: Option<String>,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<(Vec<queries::RecipeInfo>, u64), String> {
+    let db = app_state.get_db(app_handle).await.map_err(err_to_string)?;
+    let filter = queries::RecipeFilter {
+        recipe_level,
+        craft_type_id,
+        job_level_min,
+        job_level_max,
+        expansion,
+        notebook_division_id,
+        secret_recipe_book_id,
+    };
+    queries::search_recipes(db, page_id, &query, &filter, lang.as_deref())
         .await
-        .map_err(err_to_string)?
-        .into_iter()
-        .flat_map(|r| {
-            [
-                r.recipe0_id,
-                r.recipe1_id,
-                r.recipe2_id,
-                r.recipe3_id,
-                r.recipe4_id,
-            ]
-        })
-        .flatten();
-    let mut query = Recipes::find()
-        .join(JoinType::InnerJoin, recipes::Relation::CraftTypes.def())
-        .join(JoinType::InnerJoin, recipes::Relation::ItemResultItem.def())
-        .join(
-            JoinType::InnerJoin,
-            recipes::Relation::RecipeLevelTables.def(),
-        )
-        .filter(
-            items::Column::Name
-                .like(&search_name)
-                .or(recipes::Column::Id.is_in(wks_ids)),
-        );
-    if let Some(rlv) = recipe_level {
-        query = query.filter(recipes::Column::RecipeLevelId.eq(rlv))
-    }
-    if let Some(craft_type_id) = craft_type_id {
-        query = query.filter(recipes::Column::CraftTypeId.eq(craft_type_id))
-    }
-    if let Some(job_level_min) = job_level_min {
-        query = query.filter(recipe_level_tables::Column::ClassJobLevel.gte(job_level_min))
-    }
-    if let Some(job_level_max) = job_level_max {
-        query = query.filter(recipe_level_tables::Column::ClassJobLevel.lte(job_level_max))
-    }
-    let paginate = query
-        .column_as(recipes::Column::Id, "id")
-        .column_as(recipes::Column::RecipeLevelId, "rlv")
-        .column_as(items::Column::Id, "item_id")
-        .column_as(items::Column::Name, "item_name")
-        .column_as(recipes::Column::ItemResultAmount, "item_amount")
-        .column_as(craft_types::Column::Name, "job")
-        .column_as(recipes::Column::DifficultyFactor, "difficulty_factor")
-        .column_as(recipes::Column::QualityFactor, "quality_factor")
-        .column_as(recipes::Column::DurabilityFactor, "durability_factor")
-        .column_as(
-            recipes::Column::MaterialQualityFactor,
-            "material_quality_factor",
-        )
-        .column_as(
-            recipes::Column::RequiredCraftsmanship,
-            "required_craftsmanship",
-        )
-        .column_as(recipes::Column::RequiredControl, "required_control")
-        .column_as(recipes::Column::CanHq, "can_hq")
-        .column_as(recipes::Column::IsExpert, "is_expert")
-        .column_as(recipes::Column::RecipeNotebookList, "recipe_notebook_list")
-        .into_model::<RecipeInfo>()
-        .paginate(db, 200);
-    let p = paginate.num_pages().await.map_err(err_to_string)?;
-    let data = paginate.fetch_page(page_id).await.map_err(err_to_string)?;
-    Ok((data, p))
+        .map_err(err_to_string)
 }
 
 #[tauri::command(async)]
 async fn recipe_level_table_by_job_level(
-    job_level: i32,
+    job_level: u32,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<Option<recipe_level_tables::Model>, String> {
     let db = app_state.get_db(app_handle).await.map_err(err_to_string)?;
-    let result = RecipeLevelTables::find()
-        .filter(recipe_level_tables::Column::ClassJobLevel.eq(job_level))
-        .order_by_asc(recipe_level_tables::Column::Id)
-        .one(db)
+    queries::recipe_level_table_by_job_level(db, job_level)
         .await
-        .map_err(err_to_string)?;
-    Ok(result)
+        .map_err(err_to_string)
 }
 
 #[tauri::command(async)]
 async fn recipes_ingredientions(
-    recipe_id: i32,
+    recipe_id: u32,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<(u32, u8)>, String> {
     let db = app_state.get_db(app_handle).await?;
-    let recipe = Recipes::find_by_id(recipe_id as u32)
-        .one(db)
+    queries::recipe_ingredients(db, recipe_id)
         .await
         .map_err(err_to_string)?
-        .ok_or("Recipe not found".to_string())?;
-    Ok([
-        (recipe.ingredient0, recipe.ingredient_amount0),
-        (recipe.ingredient1, recipe.ingredient_amount1),
-        (recipe.ingredient2, recipe.ingredient_amount2),
-        (recipe.ingredient3, recipe.ingredient_amount3),
-        (recipe.ingredient4, recipe.ingredient_amount4),
-        (recipe.ingredient5, recipe.ingredient_amount5),
-        (recipe.ingredient6, recipe.ingredient_amount6),
-        (recipe.ingredient7, recipe.ingredient_amount7),
-    ]
-    .into_iter()
-    .filter(|(id, _)| *id != 0)
-    .collect())
+        .ok_or("Recipe not found".to_string())
+}
+
+/// 根据每种素材使用的HQ数量计算初期品质，`hq_amounts`为物品id到HQ数量的映射
+#[tauri::command(async)]
+async fn initial_quality(
+    recipe_id: u32,
+    hq_amounts: BTreeMap<u32, u32>,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<u32, String> {
+    let db = app_state.get_db(app_handle).await?;
+    queries::initial_quality(db, recipe_id, &hq_amounts)
+        .await
+        .map_err(err_to_string)?
+        .ok_or("Recipe not found".to_string())
+}
+
+#[tauri::command(async)]
+async fn item_sources(
+    item_ids: Vec<u32>,
+    lang: Option<String>,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<queries::ItemSources>, String> {
+    let db = app_state.get_db(app_handle).await?;
+    queries::item_sources(db, &item_ids, lang.as_deref())
+        .await
+        .map_err(err_to_string)
+}
+
+#[tauri::command(async)]
+async fn data_info(
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<version::DataInfo, String> {
+    let db = app_state.get_db(app_handle).await?;
+    version::data_info(db).await.map_err(err_to_string)
 }
 
 #[tauri::command(async)]
 async fn recipe_collectability(
-    recipe_id: i32,
+    recipe_id: u32,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<Option<collectables_shop_refine::Model>, String> {
     let db = app_state.get_db(app_handle).await?;
-    let result = CollectablesShopRefine::find()
-        .reverse_join(Recipes)
-        .filter(recipes::Column::Id.eq(recipe_id))
-        .one(db)
+    queries::recipe_collectability(db, recipe_id)
         .await
-        .map_err(err_to_string)?;
-    Ok(result)
+        .map_err(err_to_string)
 }
 
 #[tauri::command(async)]
 async fn item_info(
     item_id: u32,
+    lang: Option<String>,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<items::Model, String> {
     let db = app_state.get_db(app_handle).await?;
-    Items::find_by_id(item_id)
-        .one(db)
+    queries::item_info(db, item_id, lang.as_deref())
         .await
         .map_err(err_to_string)?
         .ok_or("Item not found".to_string())
 }
 
-#[derive(FromQueryResult, Debug)]
-struct ItemFoodAction {
-    name: String,
-    level: u32,
-    item_food_id: u16,
-    #[allow(dead_code)]
-    item_food_duration: u16,
-}
-
-#[derive(Default, Serialize)]
-struct Enhancer {
-    name: String,
-    level: u32,
-    is_hq: bool,
-
-    cm: Option<i8>,
-    cm_max: Option<i16>,
-    ct: Option<i8>,
-    ct_max: Option<i16>,
-    cp: Option<i8>,
-    cp_max: Option<i16>,
-}
-
-const MEDICINE_SEARCH_ID: u32 = 43;
-const MEALS_SEARCH_ID: u32 = 45;
-
 #[tauri::command(async)]
 async fn craft_type(
+    lang: Option<String>,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<craft_types::Model>, String> {
     let db = app_state.get_db(app_handle).await?;
-    let query = CraftTypes::find()
-        .column_as(craft_types::Column::Id, "id")
-        .column_as(craft_types::Column::Name, "name")
-        .order_by(craft_types::Column::Id, Order::Asc);
-    let result = query
-        .into_model::<craft_types::Model>()
-        .all(db)
+    queries::craft_types(db, lang.as_deref())
         .await
-        .map_err(err_to_string)?;
-    Ok(result)
+        .map_err(err_to_string)
+}
+
+#[tauri::command(async)]
+async fn recipe_notebooks(
+    lang: Option<String>,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<queries::RecipeNotebooks, String> {
+    let db = app_state.get_db(app_handle).await?;
+    queries::recipe_notebooks(db, lang.as_deref())
+        .await
+        .map_err(err_to_string)
 }
 
 #[tauri::command(async)]
 async fn medicine_table(
+    lang: Option<String>,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<Enhancer>, String> {
     let db = app_state.get_db(app_handle).await?;
-    query_enhancers(db, MEDICINE_SEARCH_ID).await
+    queries::medicine_table(db, lang.as_deref())
+        .await
+        .map_err(err_to_string)
 }
 
 #[tauri::command(async)]
 async fn meals_table(
+    lang: Option<String>,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
 ) -> Result<Vec<Enhancer>, String> {
     let db = app_state.get_db(app_handle).await?;
-    query_enhancers(db, MEALS_SEARCH_ID).await
+    queries::meals_table(db, lang.as_deref())
+        .await
+        .map_err(err_to_string)
 }
 
-async fn query_enhancers(
-    conn: &DatabaseConnection,
-    search_id: u32,
-) -> Result<Vec<Enhancer>, String> {
-    let crafting_item_food = ItemFood::find()
-        .join(
-            JoinType::InnerJoin,
-            item_food::Relation::ItemFoodEffect.def(),
-        )
-        .select_with(ItemFoodEffect)
-        .filter(item_food_effect::Column::BaseParam.is_in([11, 70, 71]))
-        .all(conn)
+#[tauri::command(async)]
+fn attributes_addon(attrs: Attributes, enhancers: Vec<Enhancer>) -> enhancer::AttributesAddon {
+    enhancer::attributes_addon(&attrs, &enhancers)
+}
+
+#[tauri::command(async)]
+fn enhanced_attributes(attrs: Attributes, enhancers: Vec<Enhancer>) -> Attributes {
+    enhancer::enhanced_attributes(attrs, &enhancers)
+}
+
+/// 挑选能够达到目标品质的最便宜的食物和药水组合
+#[tauri::command(async)]
+fn optimize_enhancers(
+    status: Status,
+    meals: Vec<Enhancer>,
+    medicines: Vec<Enhancer>,
+    target: OptimizeTarget,
+    use_manipulation: bool,
+    profile: CrafterProfile,
+) -> Option<EnhancerChoice> {
+    enhancer::optimize(
+        status,
+        &meals,
+        &medicines,
+        target,
+        use_manipulation,
+        profile,
+    )
+}
+
+/// 挑选能使属性达到目标的最便宜的魔晶石镶嵌方案
+#[tauri::command(async)]
+fn optimize_melds(
+    current: Stats,
+    target: Stats,
+    gear: Vec<GearPiece>,
+    materia: Vec<MateriaGrade>,
+) -> Option<MeldPlan> {
+    melding::optimize(current, target, &gear, &materia)
+}
+
+/// 根据数据库中的装备与魔晶石属性构建输入，挑选最便宜的魔晶石镶嵌方案
+#[tauri::command(async)]
+async fn optimize_gearset_melds(
+    request: gearset::MeldRequest,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<MeldPlan>, String> {
+    let db = app_state.get_db(app_handle).await?;
+    let gear = gearset::gear_pieces(db, &request.gearset)
         .await
-        .map_err(err_to_string)?
-        .into_iter()
-        .map(|(item_food, effects)| (item_food.id, effects));
-    let crafting_item_food = BTreeMap::from_iter(crafting_item_food);
-    let result = Items::find()
-        .select_only()
-        .select_column_as(items::Column::Name, "name")
-        .select_column_as(items::Column::Level, "level")
-        .filter(items::Column::ItemSearchCategoryId.eq(search_id))
-        .join(JoinType::InnerJoin, items::Relation::ItemAction.def())
-        .select_column_as(item_action::Column::Data2, "item_food_id")
-        .select_column_as(item_action::Column::Data3, "item_food_duration")
-        .filter(item_action::Column::Type.between(844, 846))
-        .filter(item_action::Column::Data2.is_in(crafting_item_food.iter().map(|v| *v.0)))
-        .into_model::<ItemFoodAction>()
-        .all(conn)
+        .map_err(err_to_string)?;
+    let materia = gearset::materia_grades(db, &request.materia)
         .await
         .map_err(err_to_string)?;
-    let result = result
-        .into_iter()
-        .flat_map(|item| {
-            let mut enh = Enhancer {
-                name: item.name.clone(),
-                level: item.level,
-                is_hq: false,
-                ..Enhancer::default()
-            };
-            let mut enh_hq = Enhancer {
-                name: item.name.clone(),
-                level: item.level,
-                is_hq: true,
-                ..Enhancer::default()
-            };
-            for item_food in crafting_item_food
-                .get(&(item.item_food_id as u32))
-                .into_iter()
-                .flatten()
-            {
-                match item_food.base_param {
-                    11 => {
-                        enh.cp = Some(item_food.value);
-                        enh.cp_max = Some(item_food.max);
-                        enh_hq.cp = Some(item_food.value_hq);
-                        enh_hq.cp_max = Some(item_food.max_hq);
-                    }
-                    70 => {
-                        enh.cm = Some(item_food.value);
-                        enh.cm_max = Some(item_food.max);
-                        enh_hq.cm = Some(item_food.value_hq);
-                        enh_hq.cm_max = Some(item_food.max_hq);
-                    }
-                    71 => {
-                        enh.ct = Some(item_food.value);
-                        enh.ct_max = Some(item_food.max);
-                        enh_hq.ct = Some(item_food.value_hq);
-                        enh_hq.ct_max = Some(item_food.max_hq);
-                    }
-                    _ => {}
-                }
-            }
-            [enh, enh_hq].into_iter()
-        })
-        .collect();
-    Ok(result)
+    tauri::async_runtime::spawn_blocking(move || {
+        melding::optimize(request.current, request.target, &gear, &materia)
+    })
+    .await
+    .map_err(err_to_string)
 }
 
-#[derive(Default, Serialize, FromQueryResult)]
-struct TemporaryActionInfo {
-    action: u32,
-    count: u32,
+#[tauri::command(async)]
+async fn temporary_action_info(
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+    recipe_id: u32,
+) -> Result<Option<queries::TemporaryActionInfo>, String> {
+    let db = app_state.get_db(app_handle).await?;
+    queries::temporary_action_info(db, recipe_id)
+        .await
+        .map_err(err_to_string)
 }
 
 #[tauri::command(async)]
-async fn temporary_action_info(
+async fn resolve_bom(
+    request: bom::BomRequest,
+    lang: Option<String>,
     app_state: tauri::State<'_, AppState>,
     app_handle: tauri::AppHandle,
-    recipe_id: i32,
-) -> Result<Option<TemporaryActionInfo>, String> {
+) -> Result<bom::Bom, String> {
     let db = app_state.get_db(app_handle).await?;
-    let query = WksMissionUnit::find()
-        .join(
-            JoinType::InnerJoin,
-            wks_mission_unit::Relation::WksMissionRecipe.def(),
-        )
-        .join(
-            JoinType::LeftJoin,
-            wks_mission_unit::Relation::WksMissionToDo3.def(),
-        )
-        .filter(
-            wks_mission_recipe::Column::Recipe0Id
-                .eq(recipe_id)
-                .or(wks_mission_recipe::Column::Recipe1Id.eq(recipe_id))
-                .or(wks_mission_recipe::Column::Recipe2Id.eq(recipe_id))
-                .or(wks_mission_recipe::Column::Recipe3Id.eq(recipe_id))
-                .or(wks_mission_recipe::Column::Recipe4Id.eq(recipe_id)),
-        )
-        .select_only()
-        .column_as(wks_mission_to_do::Column::TemporaryAction, "action")
-        .column_as(wks_mission_to_do::Column::TemporaryActionCount, "count");
-    let result = query
-        .into_model::<TemporaryActionInfo>()
-        .one(db)
+    bom::resolve_bom(db, &request, lang.as_deref())
         .await
-        .map_err(err_to_string)?;
-    Ok(result)
+        .map_err(err_to_string)
+}
+
+#[tauri::command(async)]
+async fn crafting_plan(
+    request: bom::BomRequest,
+    lang: Option<String>,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<plan::CraftingPlan, String> {
+    let db = app_state.get_db(app_handle).await?;
+    plan::crafting_plan(db, &request, lang.as_deref())
+        .await
+        .map_err(err_to_string)
+}
+
+#[tauri::command(async)]
+async fn gear_attributes(
+    gearset: Vec<gearset::EquippedGear>,
+    app_state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<gearset::GearAttributes, String> {
+    let db = app_state.get_db(app_handle).await?;
+    gearset::gear_attributes(db, &gearset)
+        .await
+        .map_err(err_to_string)
 }
 
 type SolverInstance = Arc<Mutex<Option<Box<dyn Solver + Send>>>>;
@@ -499,9 +441,14 @@ impl AppState {
             .map_err(|e| format!("database file not found: {e}"))?;
         let path = utf8_percent_encode(&path.to_string_lossy(), ESCAPE_SET).to_string();
         self.db
-            .get_or_try_init(|| Database::connect(format!("sqlite:{}?mode=ro", path)))
+            .get_or_try_init(|| async {
+                let db = Database::connect(format!("sqlite:{}?mode=ro", path))
+                    .await
+                    .map_err(err_to_string)?;
+                version::check_schema(&db).await.map_err(err_to_string)?;
+                Ok(db)
+            })
             .await
-            .map_err(err_to_string)
     }
 }
 
@@ -510,6 +457,7 @@ async fn create_solver(
     status: Status,
     use_manipulation: bool,
     use_observe: bool,
+    profile: CrafterProfile,
     app_state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
     let key = SolverHash {
@@ -534,6 +482,7 @@ async fn create_solver(
         use_manipulation,
         8 + 1,
         use_observe,
+        profile,
     ));
     *solver_slot.lock().await = Some(solver);
     Ok(())
@@ -563,14 +512,62 @@ async fn read_solver(
     Ok(result)
 }
 
+/// 使用已创建的求解器，为实际制作中的状态给出下一步技能建议，
+/// `budget`不超过[`advisor::MAX_BUDGET`]
+#[tauri::command(async)]
+async fn advise(
+    status: Status,
+    budget: usize,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<Vec<advisor::Advice>, String> {
+    let key = SolverHash {
+        attributes: status.attributes,
+        recipe: status.recipe,
+    };
+    let solver = app_state
+        .solver_list
+        .lock()
+        .await
+        .get(&key)
+        .ok_or_else(|| "solver-doesn-t-exist".to_string())?
+        .clone();
+    let solver = solver.lock_owned().await;
+    tauri::async_runtime::spawn_blocking(move || {
+        let candidates = advisor::candidates(
+            solver
+                .as_ref()
+                .ok_or_else(|| "solver-isn-t-prepared".to_string())?
+                .as_ref(),
+            &status,
+        );
+        // 随机模拟不需要求解器，先释放以免阻塞其它求解请求
+        drop(solver);
+        Ok(advisor::evaluate(&status, candidates, budget))
+    })
+    .await
+    .map_err(err_to_string)?
+}
+
 #[tauri::command(async)]
-fn dfs_solve(status: Status, depth: usize, specialist: bool) -> Vec<Actions> {
-    depth_first_search_solver::solve(status, depth, specialist)
+fn dfs_solve(
+    status: Status,
+    depth: usize,
+    profile: CrafterProfile,
+    initial_quality: Option<u32>,
+) -> Vec<Actions> {
+    let status = with_initial_quality(status, initial_quality);
+    depth_first_search_solver::solve(status, depth, profile)
 }
 
 #[tauri::command(async)]
-fn nq_solve(status: Status, depth: usize, specialist: bool) -> Vec<Actions> {
-    normal_progress_solver::solve(status, depth, specialist)
+fn nq_solve(
+    status: Status,
+    depth: usize,
+    profile: CrafterProfile,
+    initial_quality: Option<u32>,
+) -> Vec<Actions> {
+    let status = with_initial_quality(status, initial_quality);
+    normal_progress_solver::solve(status, depth, profile)
 }
 
 #[tauri::command(async)]
@@ -579,8 +576,16 @@ fn reflect_solve(
     use_manipulation: bool,
     use_waste_not: usize,
     use_observe: bool,
+    profile: CrafterProfile,
+    initial_quality: Option<u32>,
 ) -> Vec<Actions> {
-    reflect_solver::solve(status.clone(), use_manipulation, use_waste_not, use_observe)
+    reflect_solver::solve(
+        with_initial_quality(status, initial_quality),
+        use_manipulation,
+        use_waste_not,
+        use_observe,
+        profile,
+    )
 }
 
 #[tauri::command(async)]
@@ -588,26 +593,37 @@ fn raphael_solve(
     status: Status,
     target_quality: Option<u32>,
     use_manipulation: bool,
-    use_heart_and_soul: bool,
-    use_quick_innovation: bool,
     use_trained_eye: bool,
     backload_progress: bool,
     adversarial: bool,
-    stellar_steady_hand_charges: u8,
+    profile: CrafterProfile,
+    initial_quality: Option<u32>,
 ) -> Vec<Actions> {
     raphael::solve(
-        status.clone(),
+        with_initial_quality(status, initial_quality),
         target_quality,
         use_manipulation,
-        use_heart_and_soul,
-        use_quick_innovation,
         use_trained_eye,
         backload_progress,
         adversarial,
-        stellar_steady_hand_charges,
+        profile,
     )
 }
 
+/// 求解品质、步数、制作力和耐久之间的帕累托前沿，返回多个互不支配的技能序列
+#[tauri::command(async)]
+fn pareto_solve(
+    status: Status,
+    samples: usize,
+    use_manipulation: bool,
+    use_trained_eye: bool,
+    profile: CrafterProfile,
+    initial_quality: Option<u32>,
+) -> Vec<pareto_solver::ParetoRotation> {
+    let status = with_initial_quality(status, initial_quality);
+    pareto_solver::solve(status, samples, use_manipulation, use_trained_eye, profile)
+}
+
 /// 释放求解器
 #[tauri::command(async)]
 async fn destroy_solver(
@@ -703,20 +719,35 @@ fn main() {
             allowed_list,
             craftpoints_list,
             recipe_table,
+            search_recipes,
             recipes_ingredientions,
+            item_sources,
+            initial_quality,
             recipe_collectability,
             item_info,
             craft_type,
+            recipe_notebooks,
             medicine_table,
             meals_table,
+            attributes_addon,
+            enhanced_attributes,
+            optimize_enhancers,
+            optimize_melds,
+            optimize_gearset_melds,
             temporary_action_info,
+            resolve_bom,
+            crafting_plan,
+            gear_attributes,
+            data_info,
             create_solver,
             read_solver,
             destroy_solver,
+            advise,
             dfs_solve,
             nq_solve,
             reflect_solve,
             raphael_solve,
+            pareto_solve,
             set_theme,
             rand_simulation,
             rand_collectables_simulation,
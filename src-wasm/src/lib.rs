@@ -17,6 +17,8 @@
 use app_libs::{
     SimulateOneStepResult,
     ffxiv_crafting::{Actions, Attributes, Recipe, Status},
+    solver::{CrafterProfile, SolverHash, reflect_solver::QualitySolver},
+    with_initial_quality,
 };
 
 use rand::rng;
@@ -90,26 +92,44 @@ pub fn high_quality_probability(status: JsValue) -> Result<JsValue, JsValue> {
 }
 
 #[wasm_bindgen]
-pub fn dfs_solve(status: JsValue, depth: usize, specialist: bool) -> Result<JsValue, JsValue> {
+pub fn dfs_solve(
+    status: JsValue,
+    depth: usize,
+    profile: JsValue,
+    initial_quality: Option<u32>,
+) -> Result<JsValue, JsValue> {
     use app_libs::solver::depth_first_search_solver::solve;
-    let status: Status = from_value(status)?;
-    let result: Vec<Actions> = solve(status, depth, specialist);
+    let status = with_initial_quality(from_value(status)?, initial_quality);
+    let profile: CrafterProfile = from_value(profile)?;
+    let result: Vec<Actions> = solve(status, depth, profile);
     Ok(to_value(&result)?)
 }
 
 #[wasm_bindgen]
-pub fn nq_solve(status: JsValue, depth: usize, specialist: bool) -> Result<JsValue, JsValue> {
+pub fn nq_solve(
+    status: JsValue,
+    depth: usize,
+    profile: JsValue,
+    initial_quality: Option<u32>,
+) -> Result<JsValue, JsValue> {
     use app_libs::solver::normal_progress_solver::solve;
-    let status: Status = from_value(status)?;
-    let result: Vec<Actions> = solve(status, depth, specialist);
+    let status = with_initial_quality(from_value(status)?, initial_quality);
+    let profile: CrafterProfile = from_value(profile)?;
+    let result: Vec<Actions> = solve(status, depth, profile);
     Ok(to_value(&result)?)
 }
 
 #[wasm_bindgen]
-pub fn reflect_solve(status: JsValue, use_observe: bool) -> Result<JsValue, JsValue> {
+pub fn reflect_solve(
+    status: JsValue,
+    use_observe: bool,
+    profile: JsValue,
+    initial_quality: Option<u32>,
+) -> Result<JsValue, JsValue> {
     use app_libs::solver::reflect_solver::solve;
-    let status: Status = from_value(status)?;
-    let result: Vec<Actions> = solve(status.clone(), false, 0, use_observe);
+    let status = with_initial_quality(from_value(status)?, initial_quality);
+    let profile: CrafterProfile = from_value(profile)?;
+    let result: Vec<Actions> = solve(status, false, 0, use_observe, profile);
     Ok(to_value(&result)?)
 }
 
@@ -118,29 +138,92 @@ pub fn raphael_solve(
     status: JsValue,
     target_quality: Option<u32>,
     use_manipultaion: bool,
-    use_heart_and_soul: bool,
-    use_quick_innovation: bool,
     use_trained_eye: bool,
     backload_progress: bool,
     adversarial: bool,
-    stellar_steady_hand_charges: u8,
+    profile: JsValue,
+    initial_quality: Option<u32>,
 ) -> Result<JsValue, JsValue> {
     use app_libs::solver::raphael::solve;
-    let status: Status = from_value(status)?;
+    let status = with_initial_quality(from_value(status)?, initial_quality);
+    let profile: CrafterProfile = from_value(profile)?;
     let result: Vec<Actions> = solve(
-        status.clone(),
+        status,
         target_quality,
         use_manipultaion,
-        use_heart_and_soul,
-        use_quick_innovation,
         use_trained_eye,
         backload_progress,
         adversarial,
-        stellar_steady_hand_charges,
+        profile,
     );
     Ok(to_value(&result)?)
 }
 
+/// 实时制作建议，持有求解器以便在多次调用之间复用求解表，与桌面端的`create_solver`对应
+#[wasm_bindgen]
+pub struct Advisor {
+    key: SolverHash,
+    solver: QualitySolver,
+}
+
+#[wasm_bindgen]
+impl Advisor {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        status: JsValue,
+        use_manipulation: bool,
+        use_waste_not: usize,
+        use_observe: bool,
+        profile: JsValue,
+    ) -> Result<Advisor, JsValue> {
+        let status: Status = from_value(status)?;
+        let profile: CrafterProfile = from_value(profile)?;
+        let key = SolverHash {
+            attributes: status.attributes,
+            recipe: status.recipe,
+        };
+        let solver = QualitySolver::new(
+            status,
+            use_manipulation,
+            use_waste_not,
+            use_observe,
+            profile,
+        );
+        Ok(Self { key, solver })
+    }
+
+    /// `budget`不超过[`app_libs::analyzer::advisor::MAX_BUDGET`]
+    pub fn advise(&self, status: JsValue, budget: usize) -> Result<JsValue, JsValue> {
+        use app_libs::analyzer::advisor;
+        let status: Status = from_value(status)?;
+        let key = SolverHash {
+            attributes: status.attributes,
+            recipe: status.recipe,
+        };
+        if key != self.key {
+            return Err("solver-doesn-t-exist".into());
+        }
+        let result = advisor::advise(&self.solver, &status, budget);
+        Ok(to_value(&result)?)
+    }
+}
+
+#[wasm_bindgen]
+pub fn pareto_solve(
+    status: JsValue,
+    samples: usize,
+    use_manipulation: bool,
+    use_trained_eye: bool,
+    profile: JsValue,
+    initial_quality: Option<u32>,
+) -> Result<JsValue, JsValue> {
+    use app_libs::solver::pareto_solver::solve;
+    let status = with_initial_quality(from_value(status)?, initial_quality);
+    let profile: CrafterProfile = from_value(profile)?;
+    let result = solve(status, samples, use_manipulation, use_trained_eye, profile);
+    Ok(to_value(&result)?)
+}
+
 #[wasm_bindgen]
 pub fn rand_simulation(
     status: JsValue,
@@ -170,6 +253,66 @@ pub fn rand_collectables_simulation(
     Ok(to_value(&result)?)
 }
 
+#[wasm_bindgen]
+pub fn attributes_addon(attrs: JsValue, enhancers: JsValue) -> Result<JsValue, JsValue> {
+    use app_libs::enhancer::Enhancer;
+    let attrs: Attributes = from_value(attrs)?;
+    let enhancers: Vec<Enhancer> = from_value(enhancers)?;
+    let result = app_libs::enhancer::attributes_addon(&attrs, &enhancers);
+    Ok(to_value(&result)?)
+}
+
+#[wasm_bindgen]
+pub fn enhanced_attributes(attrs: JsValue, enhancers: JsValue) -> Result<JsValue, JsValue> {
+    use app_libs::enhancer::Enhancer;
+    let attrs: Attributes = from_value(attrs)?;
+    let enhancers: Vec<Enhancer> = from_value(enhancers)?;
+    let result = app_libs::enhancer::enhanced_attributes(attrs, &enhancers);
+    Ok(to_value(&result)?)
+}
+
+#[wasm_bindgen]
+pub fn optimize_enhancers(
+    status: JsValue,
+    meals: JsValue,
+    medicines: JsValue,
+    target: JsValue,
+    use_manipulation: bool,
+    profile: JsValue,
+) -> Result<JsValue, JsValue> {
+    use app_libs::enhancer::{Enhancer, OptimizeTarget, optimize};
+    let status: Status = from_value(status)?;
+    let meals: Vec<Enhancer> = from_value(meals)?;
+    let medicines: Vec<Enhancer> = from_value(medicines)?;
+    let target: OptimizeTarget = from_value(target)?;
+    let profile: CrafterProfile = from_value(profile)?;
+    let result = optimize(
+        status,
+        &meals,
+        &medicines,
+        target,
+        use_manipulation,
+        profile,
+    );
+    Ok(to_value(&result)?)
+}
+
+#[wasm_bindgen]
+pub fn optimize_melds(
+    current: JsValue,
+    target: JsValue,
+    gear: JsValue,
+    materia: JsValue,
+) -> Result<JsValue, JsValue> {
+    use app_libs::melding::{GearPiece, MateriaGrade, Stats, optimize};
+    let current: Stats = from_value(current)?;
+    let target: Stats = from_value(target)?;
+    let gear: Vec<GearPiece> = from_value(gear)?;
+    let materia: Vec<MateriaGrade> = from_value(materia)?;
+    let result = optimize(current, target, &gear, &materia);
+    Ok(to_value(&result)?)
+}
+
 #[wasm_bindgen]
 pub fn calc_attributes_scope(status: JsValue, actions: JsValue) -> Result<JsValue, JsValue> {
     let status: Status = from_value(status)?;
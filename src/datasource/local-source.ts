@@ -23,9 +23,20 @@ import {
 } from '@/libs/Craft';
 import { Enhancer } from '@/libs/Enhancer';
 import {
+    Bom,
+    BomRequest,
+    CraftingPlan,
     CraftType,
+    DataInfo,
     DataSourceResult,
     DataSourceType,
+    EquippedGear,
+    GearAttributes,
+    ItemSources,
+    MeldPlan,
+    MeldRequest,
+    NotebookFilter,
+    RecipeNotebooks,
     RecipesSourceResult,
     TemporaryActionInfo,
 } from './source';
@@ -33,6 +44,12 @@ import {
 export class LocalRecipeSource {
     public sourceType = DataSourceType.Realtime;
     invoke = import('@tauri-apps/api/core').then(pkg => pkg.invoke);
+    // 名称使用的语言，数据库中没有该语言的翻译时使用导入时的语言
+    lang?: string;
+
+    constructor(lang?: string) {
+        this.lang = lang;
+    }
 
     async recipeTable(
         page: number,
@@ -41,6 +58,7 @@ export class LocalRecipeSource {
         craftTypeId?: number,
         jobLevelMin?: number,
         jobLevelMax?: number,
+        filter?: NotebookFilter,
     ): Promise<RecipesSourceResult> {
         if (searchName === undefined) {
             searchName = '';
@@ -54,6 +72,32 @@ export class LocalRecipeSource {
             recipeLevel: rlv,
             jobLevelMin,
             jobLevelMax,
+            ...filter,
+            lang: this.lang,
+        });
+        return { results, totalPages };
+    }
+
+    async searchRecipes(
+        page: number,
+        query: string,
+        rlv?: number,
+        craftTypeId?: number,
+        jobLevelMin?: number,
+        jobLevelMax?: number,
+        filter?: NotebookFilter,
+    ): Promise<RecipesSourceResult> {
+        let [results, totalPages]: [RecipeInfo[], number] = await (
+            await this.invoke
+        )('search_recipes', {
+            pageId: page - 1,
+            query,
+            craftTypeId,
+            recipeLevel: rlv,
+            jobLevelMin,
+            jobLevelMax,
+            ...filter,
+            lang: this.lang,
         });
         return { results, totalPages };
     }
@@ -106,7 +150,7 @@ export class LocalRecipeSource {
             category_id,
         } = (await (
             await this.invoke
-        )('item_info', { itemId })) as {
+        )('item_info', { itemId, lang: this.lang })) as {
             id: number;
             name: string;
             level: number;
@@ -128,14 +172,25 @@ export class LocalRecipeSource {
     async craftTypeList(): Promise<CraftType[]> {
         return await (
             await this.invoke
-        )('craft_type');
+        )('craft_type', { lang: this.lang });
     }
+
+    async recipeNotebooks(): Promise<RecipeNotebooks> {
+        return await (await this.invoke)('recipe_notebooks', {
+            lang: this.lang,
+        });
+    }
+
     async medicineTable(_page: number): Promise<DataSourceResult<Enhancer>> {
-        const results: Enhancer[] = await (await this.invoke)('medicine_table');
+        const results: Enhancer[] = await (
+            await this.invoke
+        )('medicine_table', { lang: this.lang });
         return { results, totalPages: 1 };
     }
     async mealsTable(_page: number): Promise<DataSourceResult<Enhancer>> {
-        const results: Enhancer[] = await (await this.invoke)('meals_table');
+        const results: Enhancer[] = await (
+            await this.invoke
+        )('meals_table', { lang: this.lang });
         return { results, totalPages: 1 };
     }
 
@@ -146,4 +201,46 @@ export class LocalRecipeSource {
             await this.invoke
         )('temporary_action_info', { recipeId });
     }
+
+    async initialQuality(
+        recipeId: number,
+        hqAmounts: Record<number, number>,
+    ): Promise<number> {
+        return await (
+            await this.invoke
+        )('initial_quality', { recipeId, hqAmounts });
+    }
+
+    async itemSources(itemIds: number[]): Promise<ItemSources[]> {
+        return await (await this.invoke)('item_sources', {
+            itemIds,
+            lang: this.lang,
+        });
+    }
+
+    async resolveBom(request: BomRequest): Promise<Bom> {
+        return await (await this.invoke)('resolve_bom', {
+            request,
+            lang: this.lang,
+        });
+    }
+
+    async craftingPlan(request: BomRequest): Promise<CraftingPlan> {
+        return await (await this.invoke)('crafting_plan', {
+            request,
+            lang: this.lang,
+        });
+    }
+
+    async gearAttributes(gearset: EquippedGear[]): Promise<GearAttributes> {
+        return await (await this.invoke)('gear_attributes', { gearset });
+    }
+
+    async optimizeMelds(request: MeldRequest): Promise<MeldPlan | null> {
+        return await (await this.invoke)('optimize_gearset_melds', { request });
+    }
+
+    async dataInfo(): Promise<DataInfo> {
+        return await (await this.invoke)('data_info');
+    }
 }
@@ -32,20 +32,50 @@ export interface DataSource {
         craftTypeId?: number,
         jobLevelMin?: number,
         jobLevelMax?: number,
+        filter?: NotebookFilter,
+    ): Promise<RecipesSourceResult>;
+    // 按相关度排序的全文搜索，支持前缀、拼音与假名匹配
+    searchRecipes?(
+        page: number,
+        query: string,
+        rlv?: number,
+        craftTypeId?: number,
+        jobLevelMin?: number,
+        jobLevelMax?: number,
+        filter?: NotebookFilter,
     ): Promise<RecipesSourceResult>;
     recipesIngredients(recipeId: number): Promise<ItemWithAmount[]>;
+    // 物品的配方、商店与采集点，用于区分制作、购买与采集
+    itemSources?(itemIds: number[]): Promise<ItemSources[]>;
     recipeLevelTable(rlv: number): Promise<RecipeLevel>;
     recipeLevelTablebyJobLevel?(job_level: number): Promise<RecipeLevel | null>;
     recipeCollectableShopRefine?(
         recipeId: number,
     ): Promise<CollectablesShopRefine>;
     recipeInfo?(recipeId: number): Promise<RecipeInfo>;
+    // 根据每种素材使用的HQ数量（物品id -> 数量）计算初期品质
+    initialQuality?(
+        recipeId: number,
+        hqAmounts: Record<number, number>,
+    ): Promise<number>;
     itemInfo(id: number): Promise<Item>;
     craftTypeList(): Promise<CraftType[]>;
+    // 制作笔记的分类、页与秘籍，用于按资料片、笔记页或秘籍筛选配方
+    recipeNotebooks?(): Promise<RecipeNotebooks>;
 
     medicineTable(page: number): Promise<DataSourceResult<Enhancer>>;
     mealsTable(page: number): Promise<DataSourceResult<Enhancer>>;
     temporaryActionInfo?(recipeId: number): Promise<TemporaryActionInfo | null>;
+    // 展开素材树并汇总数量，不支持的数据源由前端逐个查询配方
+    resolveBom?(request: BomRequest): Promise<Bom>;
+    // 按依赖关系分阶段、按职业分组的制作顺序
+    craftingPlan?(request: BomRequest): Promise<CraftingPlan>;
+    // 根据装备与镶嵌的魔晶石计算制作属性
+    gearAttributes?(gearset: EquippedGear[]): Promise<GearAttributes>;
+    // 根据装备与可购买的魔晶石挑选最便宜的镶嵌方案，无法达到目标时为null
+    optimizeMelds?(request: MeldRequest): Promise<MeldPlan | null>;
+    // 数据库对应的游戏版本、语言与导入时间
+    dataInfo?(): Promise<DataInfo>;
 }
 
 export interface CraftType {
@@ -53,6 +83,31 @@ export interface CraftType {
     name: string;
 }
 
+export interface NotebookFilter {
+    // 制作笔记分类的id，即配方所属的资料片
+    expansion?: number;
+    notebookDivisionId?: number;
+    secretRecipeBookId?: number;
+}
+
+export interface RecipeNotebooks {
+    categories: {
+        id: number;
+        name: string;
+    }[];
+    divisions: {
+        id: number;
+        name: string;
+        notebook_division_category_id: number | null;
+        craft_opening_level: number;
+    }[];
+    secret_recipe_books: {
+        id: number;
+        item_id: number;
+        name: string;
+    }[];
+}
+
 export enum DataSourceType {
     Realtime,
     RemoteRealtime,
@@ -64,6 +119,158 @@ export interface TemporaryActionInfo {
     count: number;
 }
 
+export interface ItemSources {
+    item_id: number;
+    recipes: number[];
+    gil_shops: {
+        shop_id: number;
+        shop_name: string;
+        price: number;
+    }[];
+    special_shops: {
+        shop_id: number;
+        shop_name: string;
+        count: number;
+        // [物品id或货币类型, 数量]
+        costs: [number, number][];
+    }[];
+    gathering: {
+        gathering_point_base_id: number;
+        // 0采掘、1碎石、2采伐、3割草
+        gathering_type: number;
+        gathering_level: number;
+        gathering_item_level: number;
+        is_limited: boolean;
+        places: string[];
+    }[];
+}
+
+export interface BomRequest {
+    // [物品id, 数量]
+    targets: [number, number][];
+    holdings?: Record<number, number>;
+    // 物品id -> 配方id
+    recipes?: Record<number, number>;
+    include_crystals?: boolean;
+}
+
+export interface BomNode {
+    item_id: number;
+    amount: number;
+    recipe_id: number | null;
+    children: BomNode[];
+}
+
+export interface BomItem {
+    item_id: number;
+    item_name: string;
+    required: number;
+    required_by: [number, number][];
+    holding: number;
+    recipe_id: number | null;
+    craft_type_id: number | null;
+    item_amount: number;
+    crafts: number;
+    leftover: number;
+    depth: number;
+}
+
+export interface ShoppingItem {
+    item_id: number;
+    item_name: string;
+    amount: number;
+}
+
+export interface Bom {
+    tree: BomNode[];
+    items: BomItem[];
+    shopping_list: ShoppingItem[];
+}
+
+export interface PlanStep {
+    recipe_id: number;
+    item_id: number;
+    item_name: string;
+    crafts: number;
+    amount: number;
+    leftover: number;
+    ingredients: [number, number][];
+}
+
+export interface JobGroup {
+    stage: number;
+    craft_type_id: number;
+    craft_type_name: string;
+    steps: PlanStep[];
+}
+
+export interface JobSummary {
+    craft_type_id: number;
+    craft_type_name: string;
+    recipes: number;
+    crafts: number;
+}
+
+export interface CraftingPlan {
+    shopping_list: ShoppingItem[];
+    groups: JobGroup[];
+    jobs: JobSummary[];
+}
+
+export interface EquippedGear {
+    item_id: number;
+    hq?: boolean;
+    // 镶嵌的魔晶石的物品id
+    materia?: number[];
+}
+
+export interface GearAttributes {
+    craftsmanship: number;
+    control: number;
+    craft_points: number;
+    // 能够穿戴全部装备的职业，与Jobs的值一致
+    compatible_jobs: string[];
+}
+
+export interface MeldStats {
+    craftsmanship: number;
+    control: number;
+    craft_points: number;
+}
+
+export interface MateriaOffer {
+    item_id: number;
+    // 单个魔晶石的价格
+    cost: number;
+    // 在第1、2、3……个禁断孔中镶嵌的成功率（百分比）
+    overmeld_rates?: number[];
+}
+
+export interface MeldRequest {
+    gearset: EquippedGear[];
+    materia: MateriaOffer[];
+    // 包括角色自身属性在内的当前属性
+    current: MeldStats;
+    target: MeldStats;
+}
+
+export interface MeldPlan {
+    // 每件装备按孔的顺序镶嵌的魔晶石物品id，与gearset的顺序一致
+    melds: number[][];
+    // 预期的总花费，禁断镶嵌按成功率折算
+    cost: number;
+    stats: MeldStats;
+}
+
+export interface DataInfo {
+    schema_version: number | null;
+    game_version: string | null;
+    language: string | null;
+    // Unix时间戳（秒）
+    imported_at: number | null;
+    tool_version: string | null;
+}
+
 export interface DataSourceResult<T> {
     results: T[];
     totalPages: number;
@@ -22,9 +22,20 @@ import {
     RecipeLevel,
 } from '@/libs/Craft';
 import {
+    Bom,
+    BomRequest,
+    CraftingPlan,
     CraftType,
+    DataInfo,
     DataSourceResult,
     DataSourceType,
+    EquippedGear,
+    GearAttributes,
+    ItemSources,
+    MeldPlan,
+    MeldRequest,
+    NotebookFilter,
+    RecipeNotebooks,
     RecipesSourceResult,
     TemporaryActionInfo,
 } from './source';
@@ -47,6 +58,7 @@ export class WebSource {
         craftTypeId?: number,
         jobLevelMin?: number,
         jobLevelMax?: number,
+        filter?: NotebookFilter,
     ): Promise<RecipesSourceResult> {
         if (searchName === undefined) {
             searchName = '';
@@ -55,6 +67,46 @@ export class WebSource {
             page_id: String(page - 1),
             search_name: '%' + searchName + '%',
         });
+        return this.recipeList(
+            'recipe_table',
+            query,
+            rlv,
+            craftTypeId,
+            jobLevelMin,
+            jobLevelMax,
+            filter,
+        );
+    }
+
+    async searchRecipes(
+        page: number,
+        query: string,
+        rlv?: number,
+        craftTypeId?: number,
+        jobLevelMin?: number,
+        jobLevelMax?: number,
+        filter?: NotebookFilter,
+    ): Promise<RecipesSourceResult> {
+        return this.recipeList(
+            'search_recipes',
+            new URLSearchParams({ page_id: String(page - 1), query }),
+            rlv,
+            craftTypeId,
+            jobLevelMin,
+            jobLevelMax,
+            filter,
+        );
+    }
+
+    private async recipeList(
+        path: string,
+        query: URLSearchParams,
+        rlv?: number,
+        craftTypeId?: number,
+        jobLevelMin?: number,
+        jobLevelMax?: number,
+        filter?: NotebookFilter,
+    ): Promise<RecipesSourceResult> {
         if (rlv !== undefined) {
             query.set('rlv', String(rlv));
         }
@@ -67,9 +119,24 @@ export class WebSource {
         if (jobLevelMax !== undefined) {
             query.set('job_level_max', String(jobLevelMax));
         }
+        if (filter?.expansion !== undefined) {
+            query.set('expansion', String(filter.expansion));
+        }
+        if (filter?.notebookDivisionId !== undefined) {
+            query.set(
+                'notebook_division_id',
+                String(filter.notebookDivisionId),
+            );
+        }
+        if (filter?.secretRecipeBookId !== undefined) {
+            query.set(
+                'secret_recipe_book_id',
+                String(filter.secretRecipeBookId),
+            );
+        }
 
         const url =
-            new URL('recipe_table', this.base).toString() +
+            new URL(path, this.base).toString() +
             '?' +
             query.toString();
         const resp = await fetch(url, {
@@ -208,6 +275,16 @@ export class WebSource {
         return (await resp.json()) as CraftType[];
     }
 
+    async recipeNotebooks(): Promise<RecipeNotebooks> {
+        const url = new URL('recipe_notebooks', this.base).toString();
+        const resp = await fetch(url, {
+            method: 'GET',
+            mode: 'cors',
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as RecipeNotebooks;
+    }
+
     async medicineTable(_page: number): Promise<DataSourceResult<Enhancer>> {
         const url = new URL('medicine_table', this.base).toString();
         const resp = await fetch(url, {
@@ -244,6 +321,88 @@ export class WebSource {
         if (!resp.ok) throw resp.status;
         return (await resp.json()) as TemporaryActionInfo;
     }
+
+    async initialQuality(
+        recipeId: number,
+        hqAmounts: Record<number, number>,
+    ): Promise<number> {
+        const resp = await fetch(new URL('initial_quality', this.base), {
+            method: 'POST',
+            mode: 'cors',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({
+                recipe_id: recipeId,
+                hq_amounts: hqAmounts,
+            }),
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as number;
+    }
+
+    async itemSources(itemIds: number[]): Promise<ItemSources[]> {
+        const query = new URLSearchParams({ item_ids: itemIds.join(',') });
+        const url = new URL('item_sources', this.base);
+        url.search = query.toString();
+        const resp = await fetch(url, {
+            method: 'GET',
+            mode: 'cors',
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as ItemSources[];
+    }
+
+    async resolveBom(request: BomRequest): Promise<Bom> {
+        const resp = await fetch(new URL('bom', this.base), {
+            method: 'POST',
+            mode: 'cors',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(request),
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as Bom;
+    }
+
+    async craftingPlan(request: BomRequest): Promise<CraftingPlan> {
+        const resp = await fetch(new URL('crafting_plan', this.base), {
+            method: 'POST',
+            mode: 'cors',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(request),
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as CraftingPlan;
+    }
+
+    async gearAttributes(gearset: EquippedGear[]): Promise<GearAttributes> {
+        const resp = await fetch(new URL('gear_attributes', this.base), {
+            method: 'POST',
+            mode: 'cors',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(gearset),
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as GearAttributes;
+    }
+
+    async optimizeMelds(request: MeldRequest): Promise<MeldPlan | null> {
+        const resp = await fetch(new URL('optimize_melds', this.base), {
+            method: 'POST',
+            mode: 'cors',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(request),
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as MeldPlan | null;
+    }
+
+    async dataInfo(): Promise<DataInfo> {
+        const resp = await fetch(new URL('data_info', this.base), {
+            method: 'GET',
+            mode: 'cors',
+        });
+        if (!resp.ok) throw resp.status;
+        return (await resp.json()) as DataInfo;
+    }
 }
 
 export const YYYYGamesApiBase = 'https://tnze.yyyy.games/api/datasource/';
@@ -417,6 +417,12 @@ export interface RecipeInfo {
     can_hq: boolean;
     is_expert: boolean;
     recipe_notebook_list: number;
+    // 配方所在的制作笔记页
+    notebook_division_id?: number | null;
+    notebook_division?: string | null;
+    // 解锁配方的秘籍
+    secret_recipe_book_id?: number | null;
+    secret_recipe_book?: string | null;
 }
 
 export interface RecipeRequirements {
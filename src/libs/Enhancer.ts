@@ -15,6 +15,17 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 import { Attributes } from './Craft';
+import { isTauri } from './Consts';
+
+if (import.meta.env.VITE_BESTCRAFT_TARGET == 'tauri') {
+    var pkgTauri = import('@tauri-apps/api/core');
+} else {
+    var pkgWasm = (async () => {
+        const m = await import('@/../pkg-wasm/app_wasm');
+        await m.default();
+        return m;
+    })();
+}
 
 export interface Enhancer {
     name: string;
@@ -29,45 +40,35 @@ export interface Enhancer {
     cp_max?: number;
 }
 
-export function calculateEnhancedAttributsAddon(
-    attributes: Attributes,
+export interface AttributesAddon {
+    cm: number;
+    ct: number;
+    cp: number;
+}
+
+// 加成的计算由app-libs完成，每一项都以未加成的属性为基数
+export async function attributesAddon(
+    attrs: Attributes,
     ...enhancers: Enhancer[]
-): { cm: number; ct: number; cp: number } {
-    let cm = 0,
-        ct = 0,
-        cp = 0;
-    for (const enh of enhancers) {
-        if (enh.cm && enh.cm_max) {
-            cm += Math.floor(
-                Math.min((attributes.craftsmanship * enh.cm) / 100, enh.cm_max),
-            );
-        }
-        if (enh.ct && enh.ct_max) {
-            ct += Math.floor(
-                Math.min((attributes.control * enh.ct) / 100, enh.ct_max),
-            );
-        }
-        if (enh.cp && enh.cp_max) {
-            cp += Math.floor(
-                Math.min((attributes.craft_points * enh.cp) / 100, enh.cp_max),
-            );
-        }
+): Promise<AttributesAddon> {
+    if (isTauri) {
+        let { invoke } = await pkgTauri;
+        return invoke('attributes_addon', { attrs, enhancers });
+    } else {
+        let { attributes_addon } = await pkgWasm;
+        return attributes_addon(attrs, enhancers);
     }
-    return { cm, ct, cp };
 }
 
-export function calculateEnhancedAttributsAbs(
-    attributes: Attributes,
+export async function enhancedAttributes(
+    attrs: Attributes,
     ...enhancers: Enhancer[]
-): Attributes {
-    const { cm, ct, cp } = calculateEnhancedAttributsAddon(
-        attributes,
-        ...enhancers,
-    );
-    return {
-        level: attributes.level,
-        craftsmanship: attributes.craftsmanship + cm,
-        control: attributes.control + ct,
-        craft_points: attributes.craft_points + cp,
-    };
+): Promise<Attributes> {
+    if (isTauri) {
+        let { invoke } = await pkgTauri;
+        return invoke('enhanced_attributes', { attrs, enhancers });
+    } else {
+        let { enhanced_attributes } = await pkgWasm;
+        return enhanced_attributes(attrs, enhancers);
+    }
 }
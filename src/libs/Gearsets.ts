@@ -17,6 +17,7 @@
 import { JSONSchemaType } from 'ajv';
 import { Attributes, AttributesSchema, Jobs, JobsSchema } from './Craft';
 import { fluent } from '@/fluent';
+import { EquippedGear } from '@/datasource/source';
 
 const $t = fluent.format;
 
@@ -25,8 +26,20 @@ export interface GearsetsRow {
     name?: string;
     value: Attributes;
     compatibleJobs: Jobs[];
+    // 用于计算属性的装备与镶嵌的魔晶石
+    equipped?: EquippedGear[];
 }
 
+const EquippedGearSchema: JSONSchemaType<EquippedGear> = {
+    type: 'object',
+    properties: {
+        item_id: { type: 'number' },
+        hq: { type: 'boolean', nullable: true },
+        materia: { type: 'array', items: { type: 'number' }, nullable: true },
+    },
+    required: ['item_id'],
+};
+
 export const GearsetsRowSchema: JSONSchemaType<GearsetsRow> = {
     type: 'object',
     properties: {
@@ -34,6 +47,7 @@ export const GearsetsRowSchema: JSONSchemaType<GearsetsRow> = {
         name: { type: 'string', nullable: true },
         value: AttributesSchema,
         compatibleJobs: { type: 'array', items: JobsSchema, uniqueItems: true },
+        equipped: { type: 'array', items: EquippedGearSchema, nullable: true },
     },
     required: ['id', 'value', 'compatibleJobs'],
 };
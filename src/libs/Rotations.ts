@@ -0,0 +1,102 @@
+// This file is part of BestCraft.
+// Copyright (C) 2026 Tnze
+//
+// BestCraft is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// BestCraft is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+import { Actions, Attributes } from './Craft';
+import { YYYYGamesApiBase } from '@/datasource/web-source';
+import { isTauri } from './Consts';
+
+// 网页版的地址，客户端中生成的分享链接也指向网页版
+const WebAppBase = 'https://tnze.yyyy.games/';
+
+export interface SharedRotation {
+    id: string;
+    recipe_id: number;
+    attributes: Attributes;
+    actions: Actions[];
+    notes: string;
+    created_at: number;
+}
+
+// 打开分享链接时先按配方id选择配方，进入编辑器后再读取分享的技能序列
+export function rotationUrl(id: string, recipeId: number): string {
+    const base = isTauri
+        ? WebAppBase
+        : window.location.origin + window.location.pathname;
+    const query = new URLSearchParams({
+        recipeId: String(recipeId),
+        rotation: id,
+    });
+    return base + '#/recipe?' + query.toString();
+}
+
+export async function saveRotation(
+    recipeId: number,
+    attributes: Attributes,
+    actions: Actions[],
+    notes: string,
+    base: string = YYYYGamesApiBase,
+): Promise<string> {
+    const resp = await fetch(new URL('rotations', base), {
+        method: 'POST',
+        mode: 'cors',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({
+            recipe_id: recipeId,
+            attributes,
+            actions,
+            notes,
+        }),
+    });
+    if (!resp.ok) throw resp.status;
+    const { id } = (await resp.json()) as { id: string };
+    return id;
+}
+
+export async function loadRotation(
+    id: string,
+    base: string = YYYYGamesApiBase,
+): Promise<SharedRotation | null> {
+    const url = new URL('rotations/' + encodeURIComponent(id), base);
+    const resp = await fetch(url, {
+        method: 'GET',
+        mode: 'cors',
+    });
+    if (resp.status == 404) return null;
+    if (!resp.ok) throw resp.status;
+    return (await resp.json()) as SharedRotation;
+}
+
+export async function searchRotations(
+    recipeId: number,
+    page: number,
+    base: string = YYYYGamesApiBase,
+): Promise<{ data: SharedRotation[]; totalPages: number }> {
+    const query = new URLSearchParams({
+        recipe_id: String(recipeId),
+        page_id: String(page - 1),
+    });
+    const url = new URL('rotations', base).toString() + '?' + query.toString();
+    const resp = await fetch(url, {
+        method: 'GET',
+        mode: 'cors',
+    });
+    if (!resp.ok) throw resp.status;
+    const { data, p: totalPages } = (await resp.json()) as {
+        data: SharedRotation[];
+        p: number;
+    };
+    return { data, totalPages };
+}
@@ -49,16 +49,35 @@ if (isTauri) {
     };
 }
 
+/// 所有求解器共用的制作者配置
+export interface CrafterProfile {
+    specialist: boolean;
+    heart_and_soul: boolean;
+    quick_innovation: boolean;
+    stellar_steady_hand_charges: number;
+}
+
+export function newCrafterProfile(specialist: boolean): CrafterProfile {
+    return {
+        specialist,
+        heart_and_soul: specialist,
+        quick_innovation: specialist,
+        stellar_steady_hand_charges: 0,
+    };
+}
+
 export async function create_solver(
     status: Status,
     useManipulation: boolean,
     useObserve: boolean,
+    profile: CrafterProfile = newCrafterProfile(false),
 ) {
     let { invoke } = await pkgTauri;
     return invoke('create_solver', {
         status,
         useManipulation,
         useObserve,
+        profile,
     });
 }
 
@@ -84,9 +103,15 @@ export async function dfs_solve(
     status: Status,
     depth: number,
     specialist: boolean,
+    initialQuality?: number,
 ): Promise<Actions[]> {
     clarityReport('runDfsSolver');
-    const args = { status, depth, specialist };
+    const args = {
+        status,
+        depth,
+        profile: newCrafterProfile(specialist),
+        initialQuality,
+    };
     if (isTauri) {
         return (await pkgTauri).invoke('dfs_solve', args);
     } else {
@@ -98,9 +123,15 @@ export async function nq_solve(
     status: Status,
     depth: number,
     specialist: boolean,
+    initialQuality?: number,
 ): Promise<Actions[]> {
     clarityReport('runNqSolver');
-    const args = { status, depth, specialist };
+    const args = {
+        status,
+        depth,
+        profile: newCrafterProfile(specialist),
+        initialQuality,
+    };
     if (isTauri) {
         return (await pkgTauri).invoke('nq_solve', args);
     } else {
@@ -116,6 +147,8 @@ export async function reflect_solve(
     useManipulation: boolean,
     useWasteNot: number,
     useObserve: boolean,
+    profile: CrafterProfile = newCrafterProfile(false),
+    initialQuality?: number,
 ): Promise<Actions[]> {
     clarityReport('runReflectSolver');
     if (isTauri) {
@@ -125,9 +158,16 @@ export async function reflect_solve(
             useManipulation,
             useWasteNot,
             useObserve,
+            profile,
+            initialQuality,
         });
     } else {
-        return invokeWasmSolver('reflect_solve', { status, useObserve });
+        return invokeWasmSolver('reflect_solve', {
+            status,
+            useObserve,
+            profile,
+            initialQuality,
+        });
     }
 }
 
@@ -141,18 +181,23 @@ export async function raphael_solve(
     backloadProgress: boolean,
     adversarial: boolean,
     stellarSteadyHandCharges: number,
+    initialQuality?: number,
 ): Promise<Actions[]> {
     clarityReport('runRaphaelSolver');
     const args = {
         status,
         targetQuality,
         useManipulation,
-        useHeartAndSoul,
-        useQuickInnovation,
         useTrainedEye,
         backloadProgress,
         adversarial,
-        stellarSteadyHandCharges,
+        profile: {
+            specialist: useHeartAndSoul || useQuickInnovation,
+            heart_and_soul: useHeartAndSoul,
+            quick_innovation: useQuickInnovation,
+            stellar_steady_hand_charges: stellarSteadyHandCharges,
+        },
+        initialQuality,
     };
     if (isTauri) {
         let { invoke } = await pkgTauri;
@@ -39,22 +39,31 @@ onmessage = async e => {
             case 'dfs_solve':
             case 'nq_solve':
                 let solve = name == 'dfs_solve' ? dfs_solve : nq_solve;
-                result = solve(args.status, args.depth, args.specialist);
+                result = solve(
+                    args.status,
+                    args.depth,
+                    args.profile,
+                    args.initialQuality,
+                );
                 break;
             case 'reflect_solve':
-                result = reflect_solve(args.status, args.useObserve);
+                result = reflect_solve(
+                    args.status,
+                    args.useObserve,
+                    args.profile,
+                    args.initialQuality,
+                );
                 break;
             case 'raphael_solve':
                 result = raphael_solve(
                     args.status,
                     args.targetQuality,
                     args.useManipulation,
-                    args.useHeartAndSoul,
-                    args.useQuickInnovation,
                     args.useTrainedEye,
                     args.backloadProgress,
                     args.adversarial,
-                    args.stellarSteadyHandCharges,
+                    args.profile,
+                    args.initialQuality,
                 );
         }
         postMessage(result);
@@ -17,7 +17,7 @@
 import { defineStore } from 'pinia';
 
 import useSettingStore from '@/stores/settings';
-import { DataSource } from '@/datasource/source';
+import { Bom, DataSource } from '@/datasource/source';
 import { ItemWithAmount, RecipeInfo } from '@/libs/Craft';
 
 export type ItemID = number;
@@ -105,6 +105,18 @@ export default defineStore('bom', {
                 slot.requiredBy.clear();
             }
 
+            if (ds.resolveBom != undefined) {
+                const bom = await ds.resolveBom({
+                    targets: this.targetItems.map(v => [
+                        v.item.id,
+                        v.getFixRequiredNumber(),
+                    ]),
+                    holdings: Object.fromEntries(this.holdingItems),
+                });
+                this.applyBom(bom);
+                return;
+            }
+
             // Discovering crafting DAG
             const queue = [...this.targetItems];
             const ings = new Map<ItemID, Slot>(queue.map(v => [v.item.id, v]));
@@ -240,6 +252,52 @@ export default defineStore('bom', {
             this.ingredients = sorted;
         },
 
+        // 使用数据源计算的结果，颜色与深度的含义与前端计算时相同
+        applyBom(bom: Bom) {
+            const ings = new Map<ItemID, Slot>(
+                this.targetItems.map(v => [v.item.id, v]),
+            );
+            const successors = new Map<ItemID, ItemID[]>();
+            const sorted = bom.items.map(v => {
+                let slot = ings.get(v.item_id);
+                if (slot == undefined) {
+                    slot = new Slot({ id: v.item_id, name: v.item_name });
+                    ings.set(v.item_id, slot);
+                }
+                for (const [by, amount] of v.required_by) {
+                    slot.addRequiredBy(by, amount);
+                    successors.set(by, [
+                        ...(successors.get(by) ?? []),
+                        v.item_id,
+                    ]);
+                }
+                const n = v.required - v.holding;
+                slot.type =
+                    v.required > 0
+                        ? n > 0
+                            ? 'required'
+                            : 'completed'
+                        : 'not-required';
+                slot.wasted = v.leftover;
+                slot.depth = v.depth;
+                return slot;
+            });
+            for (const slot of sorted.toReversed()) {
+                if (slot.type != 'required') {
+                    continue;
+                }
+                const ss = successors.get(slot.item.id);
+                const isEnough = (s: number) => {
+                    const typ = ings.get(s)?.type;
+                    return typ == 'completed' || typ == 'crafted';
+                };
+                if (ss != undefined && ss.every(isEnough)) {
+                    slot.type = 'crafted';
+                }
+            }
+            this.ingredients = sorted;
+        },
+
         async findRecipe(
             dataSource: DataSource,
             itemId: ItemID,
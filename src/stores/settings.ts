@@ -35,7 +35,8 @@ export type DataSourceLangID =
     | 'ko';
 
 export const dataSourceList: Map<string, DataSourceLangID[]> = new Map([
-    ['local', []],
+    // 本地数据库包含导入时写入的全部语言的翻译
+    ['local', ['zh-CN', 'zh-TW', 'en', 'de', 'fr', 'ja', 'ko']],
     ['yyyy.games', ['zh-CN', 'zh-TW', 'en', 'de', 'fr', 'ja']],
     // ['xivapi', ['en', 'de', 'fr', 'ja']],
     // ['cafe-xivapi', ['zh-CN', 'zh-TW', 'ja', 'en', 'de', 'fr', 'ko']],
@@ -83,7 +84,7 @@ export default defineStore('settings', {
                 let localSource = async () => {
                     let { LocalRecipeSource } =
                         await import('../datasource/local-source');
-                    return new LocalRecipeSource();
+                    return new LocalRecipeSource(dataSourceLanguage);
                 };
                 dataSources['local'] = localSource;
                 defaultSource = localSource;